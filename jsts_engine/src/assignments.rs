@@ -0,0 +1,348 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+/// The JavaScript operators recognized by the line tokenizer, the longest ones first.
+const JS_OPERATORS: [&str; 38] = [
+    ">>>=", "...", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "=>", "==", "!=",
+    "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+    "**", "<<", ">>", "=", "<", ">", "!", "?",
+];
+
+/// The JavaScript assignment operators.
+const JS_ASSIGNMENT_OPERATORS: [&str; 16] = [
+    "=", "+=", "-=", "*=", "/=", "%=", "**=", "<<=", ">>=", ">>>=", "&=", "|=", "^=", "&&=", "||=",
+    "??=",
+];
+
+/// A token of a single JavaScript line used to find the assigned variables.
+enum LineToken {
+    /// An identifier or a keyword.
+    Identifier(String),
+    /// An operator or a punctuator.
+    Punctuator(String),
+    /// A literal value, e.g. a string or a number.
+    Literal,
+}
+
+/// Splits a single JavaScript line into tokens skipping the comments and the contents of the literals.
+///
+/// # Arguments
+/// * `line` - The JavaScript line.
+///
+/// # Returns
+/// The tokens of the line.
+fn tokenize_line(line: &str) -> Vec<LineToken> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            break;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' || c == '`' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                // Skip the escaped character.
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(LineToken::Literal);
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            tokens.push(LineToken::Identifier(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit() {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            tokens.push(LineToken::Literal);
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 4)].iter().collect();
+            let operator = match JS_OPERATORS
+                .iter()
+                .find(|operator| rest.starts_with(**operator))
+            {
+                Some(operator) => operator.to_string(),
+                None => c.to_string(),
+            };
+            i += operator.chars().count();
+            tokens.push(LineToken::Punctuator(operator));
+        }
+    }
+
+    tokens
+}
+
+/// Checks if the token is the specified punctuator.
+///
+/// # Arguments
+/// * `token` - The token to check.
+/// * `value` - The punctuator to compare to.
+///
+/// # Returns
+/// `true` if the token is the specified punctuator; `false` otherwise.
+fn is_punctuator(token: Option<&LineToken>, value: &str) -> bool {
+    match token {
+        Some(LineToken::Punctuator(punctuator)) => punctuator == value,
+        _ => false,
+    }
+}
+
+/// Pushes a name to the list of names if it doesn't exist in the list already.
+///
+/// # Arguments
+/// * `names` - The list of names.
+/// * `name` - The name to push.
+fn push_unique(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|existing| existing == name) {
+        names.push(name.to_string());
+    }
+}
+
+/// Skips the tokens of an expression until a comma or a semicolon outside of brackets
+/// or a closing bracket not opened within the expression.
+///
+/// # Arguments
+/// * `tokens` - The line tokens.
+/// * `i` - The index of the first token of the expression.
+///
+/// # Returns
+/// The index of the token ending the expression.
+fn skip_expression(tokens: &[LineToken], mut i: usize) -> usize {
+    let mut depth = 0;
+    while let Some(token) = tokens.get(i) {
+        if let LineToken::Punctuator(punctuator) = token {
+            match punctuator.as_str() {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => {
+                    if depth == 0 {
+                        return i;
+                    }
+                    depth -= 1;
+                }
+                "," | ";" if depth == 0 => return i,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Collects the names bound by a destructuring pattern.
+///
+/// # Arguments
+/// * `tokens` - The line tokens.
+/// * `i` - The index of the opening bracket of the pattern.
+/// * `names` - The list to collect the names into.
+///
+/// # Returns
+/// The index of the token following the pattern.
+fn collect_pattern(tokens: &[LineToken], mut i: usize, names: &mut Vec<String>) -> usize {
+    i += 1;
+    loop {
+        match tokens.get(i) {
+            None => return i,
+            Some(LineToken::Punctuator(punctuator)) => match punctuator.as_str() {
+                "}" | "]" => return i + 1,
+                "{" | "[" => i = collect_pattern(tokens, i, names),
+                "=" => i = skip_expression(tokens, i + 1),
+                _ => i += 1,
+            },
+            Some(LineToken::Identifier(name)) => {
+                // A property key of an object pattern is followed by the bound name.
+                if !is_punctuator(tokens.get(i + 1), ":") {
+                    push_unique(names, name);
+                }
+                i += 1;
+            }
+            Some(LineToken::Literal) => i += 1,
+        }
+    }
+}
+
+/// Collects the names declared by a `let`, `const` or `var` declaration list.
+///
+/// # Arguments
+/// * `tokens` - The line tokens.
+/// * `i` - The index of the first token after the declaration keyword.
+/// * `names` - The list to collect the names into.
+///
+/// # Returns
+/// The index of the token following the declaration list.
+fn collect_declaration(tokens: &[LineToken], mut i: usize, names: &mut Vec<String>) -> usize {
+    loop {
+        match tokens.get(i) {
+            Some(LineToken::Identifier(name)) => {
+                push_unique(names, name);
+                i += 1;
+            }
+            Some(LineToken::Punctuator(punctuator)) if punctuator == "{" || punctuator == "[" => {
+                i = collect_pattern(tokens, i, names);
+            }
+            _ => return i,
+        }
+
+        if is_punctuator(tokens.get(i), "=") {
+            i = skip_expression(tokens, i + 1);
+        }
+
+        if !is_punctuator(tokens.get(i), ",") {
+            return i;
+        }
+        i += 1;
+    }
+}
+
+/// Gets the names of the variables declared or assigned at the top level of a single JavaScript line,
+/// e.g. `a` and `b` from `let a = 1, b = a + 1;` or `c` from `c += 2`. The names within brackets, e.g.
+/// the parameters and the block-scoped variables of a function, are skipped.
+///
+/// # Arguments
+/// * `line` - The JavaScript line.
+///
+/// # Returns
+/// The names of the assigned variables in the order of appearance.
+pub(crate) fn assigned_identifiers(line: &str) -> Vec<String> {
+    top_level_identifiers(line, false)
+}
+
+/// Gets the names of the variables declared with `let`, `const` or `class` at the top level of a single
+/// JavaScript line. The names are lexical bindings of the script instead of properties of the global object.
+///
+/// # Arguments
+/// * `line` - The JavaScript line.
+///
+/// # Returns
+/// The names of the declared variables in the order of appearance.
+pub(crate) fn lexical_declarations(line: &str) -> Vec<String> {
+    top_level_identifiers(line, true)
+}
+
+/// Gets the names of the variables declared or assigned at the top level of a single JavaScript line.
+///
+/// # Arguments
+/// * `line` - The JavaScript line.
+/// * `lexical_only` - A flag indicating whether to get only the names declared with `let`, `const` or `class`.
+///
+/// # Returns
+/// The names of the variables in the order of appearance.
+fn top_level_identifiers(line: &str, lexical_only: bool) -> Vec<String> {
+    let tokens = tokenize_line(line);
+    let mut names: Vec<String> = Vec::new();
+    // The names declared with `var` when only the lexical declarations are requested.
+    let mut skipped: Vec<String> = Vec::new();
+
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let previous = if i > 0 { tokens.get(i - 1) } else { None };
+
+        let name = match &tokens[i] {
+            LineToken::Punctuator(punctuator) => {
+                match punctuator.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                i += 1;
+                continue;
+            }
+            // Skip the names within brackets and the property accesses, e.g. `a.b = 1`.
+            LineToken::Identifier(_)
+                if depth > 0 || is_punctuator(previous, ".") || is_punctuator(previous, "?.") =>
+            {
+                i += 1;
+                continue;
+            }
+            LineToken::Identifier(name) => name,
+            LineToken::Literal => {
+                i += 1;
+                continue;
+            }
+        };
+
+        if name == "let" || name == "const" || name == "var" {
+            let target = if lexical_only && name == "var" {
+                &mut skipped
+            } else {
+                &mut names
+            };
+            i = collect_declaration(&tokens, i + 1, target);
+            continue;
+        }
+
+        if name == "class" {
+            if let Some(LineToken::Identifier(class_name)) = tokens.get(i + 1) {
+                push_unique(&mut names, class_name);
+            }
+            i += 2;
+            continue;
+        }
+
+        if lexical_only {
+            i += 1;
+            continue;
+        }
+
+        let next = tokens.get(i + 1);
+        let is_assignment = match next {
+            Some(LineToken::Punctuator(operator)) => {
+                JS_ASSIGNMENT_OPERATORS.contains(&operator.as_str())
+            }
+            _ => false,
+        };
+        let is_update = is_punctuator(next, "++")
+            || is_punctuator(next, "--")
+            || is_punctuator(previous, "++")
+            || is_punctuator(previous, "--");
+
+        if is_assignment || is_update {
+            push_unique(&mut names, name);
+        }
+
+        i += 1;
+    }
+
+    names
+}
@@ -0,0 +1,188 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use crate::types::{LogEntry, LogLevel};
+
+/// The console calls captured during a script run, stored into the isolate slot so that
+/// each isolate captures its own calls regardless of the thread it runs on.
+#[derive(Default)]
+pub(crate) struct CapturedLogs {
+    /// The captured console calls.
+    pub entries: Vec<LogEntry>,
+    /// The line being evaluated in the line-by-line mode.
+    pub line_number: Option<i32>,
+}
+
+/// Creates a global object template with the console capture functions bound to it.
+/// The script console calls are bound to the functions with [replace_console_calls].
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+///
+/// # Returns
+/// The global object template.
+pub(crate) fn console_global_template<'s>(
+    scope: &mut v8::HandleScope<'s, ()>,
+) -> v8::Local<'s, v8::ObjectTemplate> {
+    let object_template = v8::ObjectTemplate::new(scope);
+
+    bind_function(
+        scope,
+        object_template,
+        "console_log",
+        js_console_log_capture,
+    );
+    bind_function(
+        scope,
+        object_template,
+        "console_warn",
+        js_console_warn_capture,
+    );
+    bind_function(
+        scope,
+        object_template,
+        "console_error",
+        js_console_error_capture,
+    );
+
+    object_template
+}
+
+/// Replaces the console.log(), console.warn(), and console.error() calls with the injected capture functions
+/// as the console functions are not outputted anywhere.
+///
+/// # Arguments
+/// * `code` - The script code.
+///
+/// # Returns
+/// The script code with the console calls replaced.
+pub(crate) fn replace_console_calls(code: &str) -> String {
+    code.replace("console.log(", "console_log(")
+        .replace("console.warn(", "console_warn(")
+        .replace("console.error(", "console_error(")
+}
+
+/// Binds a Rust function to a named function of an object template.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `object_template` - The object template to bind the function to.
+/// * `name` - The name of the function.
+/// * `callback` - The Rust function to call.
+fn bind_function(
+    scope: &mut v8::HandleScope<'_, ()>,
+    object_template: v8::Local<v8::ObjectTemplate>,
+    name: &str,
+    callback: impl v8::MapFnTo<v8::FunctionCallback>,
+) {
+    let function_template = v8::FunctionTemplate::new(scope, callback);
+    let name = v8::String::new(scope, name).unwrap();
+    object_template.set(name.into(), function_template.into());
+}
+
+/// Formats the v8 log call arguments to a string.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `args` - The v8 arguments.
+///
+/// # Returns
+/// The formatted log call string.
+fn format_js_log(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments) -> String {
+    let mut result: Vec<String> = Vec::new();
+    for i in 0..args.length() {
+        let arg = match args.get(i).to_string(scope) {
+            Some(arg) => arg.to_rust_string_lossy(scope),
+            None => "".to_string(),
+        };
+        result.push(arg);
+    }
+
+    result.join(" ")
+}
+
+/// Pushes a captured console call into the captured logs in the isolate slot.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `level` - The level of the console call.
+/// * `args` - The v8 arguments.
+fn push_log_entry(
+    scope: &mut v8::HandleScope,
+    level: LogLevel,
+    args: v8::FunctionCallbackArguments,
+) {
+    let message = format_js_log(scope, args);
+
+    if let Some(logs) = scope.get_slot_mut::<CapturedLogs>() {
+        let line_number = logs.line_number;
+        logs.entries.push(LogEntry {
+            level,
+            message,
+            line_number,
+        });
+    }
+}
+
+/// Captures a `console.log()` call.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `args` - The v8 arguments.
+/// * `_rv` - The v8 return value.
+fn js_console_log_capture(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut _rv: v8::ReturnValue,
+) {
+    push_log_entry(scope, LogLevel::Log, args);
+}
+
+/// Captures a `console.warn()` call.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `args` - The v8 arguments.
+/// * `_rv` - The v8 return value.
+fn js_console_warn_capture(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut _rv: v8::ReturnValue,
+) {
+    push_log_entry(scope, LogLevel::Warn, args);
+}
+
+/// Captures a `console.error()` call.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `args` - The v8 arguments.
+/// * `_rv` - The v8 return value.
+fn js_console_error_capture(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut _rv: v8::ReturnValue,
+) {
+    push_log_entry(scope, LogLevel::Error, args);
+}
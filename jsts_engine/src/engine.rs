@@ -0,0 +1,610 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    sync::Once,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    console::{console_global_template, replace_console_calls, CapturedLogs},
+    exception::exception_details,
+    snapshot::Snapshots,
+    testing::{install_test_framework, parse_test_results, run_tests_script, RESET_TESTS_SCRIPT},
+    transpile::transpile_script,
+    types::{
+        GlobalVariable, LineByLineLog, LineValueSnapshot, LogEntry, ScriptException, ScriptValue,
+        TestReport, ValueSnapshot,
+    },
+    values::{global_property_names, snapshot_line, snapshot_value, GlobalVariableCollector},
+};
+
+static V8_INIT: Once = Once::new();
+
+/// The language of the script code to run.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptLanguage {
+    /// The code is run as is.
+    #[default]
+    JavaScript,
+    /// The code is transpiled into JavaScript before the run.
+    TypeScript,
+    /// The JavaScript code with JSX elements is transpiled into JavaScript before the run.
+    Jsx,
+    /// The TypeScript code with JSX elements is transpiled into JavaScript before the run.
+    Tsx,
+}
+
+/// The options of a script run.
+#[derive(Clone, Default, Debug)]
+pub struct RunOptions {
+    /// The language of the script code.
+    pub language: ScriptLanguage,
+    /// A flag indicating whether to evaluate each line separately or the entire code at once.
+    pub evaluate_per_line: bool,
+    /// A flag indicating whether to capture the value snapshots of each line in the line-by-line mode.
+    pub capture_line_values: bool,
+    /// A flag indicating whether to collect the variables the script defined into the global object.
+    pub collect_globals: bool,
+}
+
+/// The outcome of a script run.
+#[derive(Default, Debug)]
+pub struct RunOutcome {
+    /// The results of the script run; a single result unless evaluated line by line.
+    /// The empty lines are not evaluated and have an empty `undefined` result.
+    pub results: Vec<Result<ScriptValue, ScriptException>>,
+    /// The durations of the evaluations, one for each result.
+    pub durations: Vec<Duration>,
+    /// The captured console calls of the script run.
+    pub logs: Vec<LogEntry>,
+    /// The value snapshots of the lines if requested with [RunOptions::capture_line_values].
+    pub line_value_snapshots: Vec<LineValueSnapshot>,
+    /// The global variables if requested with [RunOptions::collect_globals]. The variables are expanded
+    /// with [Session::expand_global_variable] while the session lives.
+    pub global_variables: Vec<GlobalVariable>,
+    /// A flag indicating whether the script execution was terminated with a [TerminateHandle].
+    pub terminated: bool,
+}
+
+impl RunOutcome {
+    /// Gets the first exception thrown by the script run.
+    ///
+    /// # Returns
+    /// The details of the exception; `None` if the script run succeeded.
+    pub fn exception(&self) -> Option<&ScriptException> {
+        self.results.iter().find_map(|result| result.as_ref().err())
+    }
+
+    /// Gets the console calls made outside the line-by-line mode formatted with the level prefix.
+    ///
+    /// # Returns
+    /// The formatted console calls, e.g. `LOG: message`.
+    pub fn log_stack(&self) -> Vec<String> {
+        self.logs
+            .iter()
+            .filter(|entry| entry.line_number.is_none())
+            .map(|entry| entry.to_string())
+            .collect()
+    }
+
+    /// Gets the console calls made in the line-by-line mode grouped by the line.
+    ///
+    /// # Returns
+    /// The formatted console calls by the line number.
+    pub fn log_stack_lines(&self) -> Vec<LineByLineLog> {
+        let mut result: Vec<LineByLineLog> = Vec::new();
+
+        for entry in &self.logs {
+            let line_number = match entry.line_number {
+                Some(line_number) => line_number,
+                None => continue,
+            };
+
+            match result
+                .iter_mut()
+                .find(|line| line.line_number == line_number)
+            {
+                Some(line) => line.lines.push(entry.to_string()),
+                None => result.push(LineByLineLog {
+                    line_number,
+                    lines: vec![entry.to_string()],
+                }),
+            }
+        }
+
+        result
+    }
+}
+
+/// The script engine. Creating the engine initializes the v8 platform once per process.
+pub struct Engine {
+    _private: (),
+}
+
+impl Engine {
+    /// Creates the script engine initializing the v8 platform if not already initialized.
+    ///
+    /// # Returns
+    /// The script engine.
+    pub fn new() -> Self {
+        V8_INIT.call_once(|| {
+            let platform = v8::new_default_platform(0, false).make_shared();
+            v8::V8::initialize_platform(platform);
+            v8::V8::initialize();
+        });
+
+        Self { _private: () }
+    }
+
+    /// Creates a new session with its own isolate. The session keeps its variables between the runs.
+    ///
+    /// # Returns
+    /// The session.
+    pub fn create_session(&self) -> Session {
+        Session::new()
+    }
+
+    /// Runs the script code in a new session.
+    ///
+    /// # Arguments
+    /// * `code` - The script code to run.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The outcome of the script run.
+    pub fn run(&self, code: &str, options: &RunOptions) -> RunOutcome {
+        self.create_session().run(code, options)
+    }
+
+    /// Runs the script code lines one by one in a new session.
+    ///
+    /// # Arguments
+    /// * `lines` - The script code lines to run.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The outcome of the script run with a result for each line.
+    pub fn run_lines(&self, lines: &[String], options: &RunOptions) -> RunOutcome {
+        self.create_session().run_lines(lines, options)
+    }
+
+    /// Runs the script code and the tests it registered in a new session, see [Session::run_tests].
+    ///
+    /// # Arguments
+    /// * `code` - The script code with the tests.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The test report.
+    pub fn run_tests(&self, code: &str, options: &RunOptions) -> TestReport {
+        self.create_session().run_tests(code, options)
+    }
+
+    /// Runs the script code and the tests it registered in a new session matching the
+    /// `toMatchSnapshot` assertions against the stored snapshots, see [Session::run_tests_with_snapshots].
+    ///
+    /// # Arguments
+    /// * `code` - The script code with the tests.
+    /// * `options` - The options of the script run.
+    /// * `snapshots` - The stored snapshots; the written and updated snapshots are stored into these.
+    /// * `update` - A flag indicating whether to update the mismatched and remove the obsolete snapshots.
+    ///
+    /// # Returns
+    /// The test report.
+    pub fn run_tests_with_snapshots(
+        &self,
+        code: &str,
+        options: &RunOptions,
+        snapshots: &mut Snapshots,
+        update: bool,
+    ) -> TestReport {
+        self.create_session()
+            .run_tests_with_snapshots(code, options, snapshots, update)
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle to terminate the script run of a session from another thread.
+#[derive(Clone)]
+pub struct TerminateHandle {
+    /// The thread safe handle of the session isolate.
+    isolate_handle: v8::IsolateHandle,
+}
+
+impl TerminateHandle {
+    /// Terminates the script run in progress.
+    ///
+    /// # Returns
+    /// `true` if the termination was requested; `false` if the session no longer exists.
+    pub fn terminate(&self) -> bool {
+        self.isolate_handle.terminate_execution()
+    }
+}
+
+/// A script session owning a v8 isolate and a context. The variables defined by a run are
+/// visible to the following runs until the session is reset. The session must stay on the
+/// thread it was created on.
+pub struct Session {
+    /// The v8 isolate of the session.
+    isolate: v8::OwnedIsolate,
+    /// The script context of the session.
+    context: v8::Global<v8::Context>,
+    /// The names of the built-in and injected global properties excluded from the global variables.
+    excluded_names: Vec<String>,
+    /// The expandable global variables of the last run collecting them.
+    variables: GlobalVariableCollector,
+}
+
+impl Session {
+    /// Creates a new session. The v8 platform must be initialized with [Engine::new] first.
+    ///
+    /// # Returns
+    /// The session.
+    fn new() -> Self {
+        let mut isolate = v8::Isolate::new(Default::default());
+        let (context, excluded_names) = Self::create_context(&mut isolate);
+
+        Self {
+            isolate,
+            context,
+            excluded_names,
+            variables: GlobalVariableCollector::default(),
+        }
+    }
+
+    /// Creates a new script context with the console capture functions.
+    ///
+    /// # Arguments
+    /// * `isolate` - The v8 isolate to create the context into.
+    ///
+    /// # Returns
+    /// The script context and the names of the global properties existing before any script run.
+    fn create_context(isolate: &mut v8::OwnedIsolate) -> (v8::Global<v8::Context>, Vec<String>) {
+        // A termination requested after the previous run finished must not abort building the context.
+        isolate.cancel_terminate_execution();
+
+        let scope = &mut v8::HandleScope::new(isolate);
+        let object_template = console_global_template(scope);
+
+        let context = v8::Context::new(
+            scope,
+            v8::ContextOptions {
+                global_template: Some(object_template),
+                ..Default::default()
+            },
+        );
+
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        install_test_framework(scope);
+
+        // The properties existing before the script run are built-ins or the injected console and test functions.
+        let excluded_names = global_property_names(scope, context);
+
+        (v8::Global::new(scope, context), excluded_names)
+    }
+
+    /// Resets the session into a new context discarding the variables of the previous runs.
+    pub fn reset(&mut self) {
+        let (context, excluded_names) = Self::create_context(&mut self.isolate);
+        self.context = context;
+        self.excluded_names = excluded_names;
+        self.variables = GlobalVariableCollector::default();
+    }
+
+    /// Gets the child values of an expandable global variable of the last run, or of a child value of
+    /// one, see [GlobalVariable::handle].
+    ///
+    /// # Arguments
+    /// * `handle` - The handle of the value to expand.
+    ///
+    /// # Returns
+    /// The child values of the value; `None` if the handle was not found.
+    pub fn expand_global_variable(&mut self, handle: u32) -> Option<Vec<GlobalVariable>> {
+        let scope = &mut v8::HandleScope::with_context(&mut self.isolate, &self.context);
+        // A getter of a child value may throw.
+        let scope = &mut v8::TryCatch::new(scope);
+        self.variables.expand(scope, handle)
+    }
+
+    /// Gets a handle to terminate the script run of the session from another thread.
+    ///
+    /// # Returns
+    /// The terminate handle.
+    pub fn terminate_handle(&self) -> TerminateHandle {
+        TerminateHandle {
+            isolate_handle: self.isolate.thread_safe_handle(),
+        }
+    }
+
+    /// Runs the script code in the session context.
+    ///
+    /// # Arguments
+    /// * `code` - The script code to run.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The outcome of the script run.
+    pub fn run(&mut self, code: &str, options: &RunOptions) -> RunOutcome {
+        if options.evaluate_per_line {
+            let lines: Vec<&str> = code
+                .split('\n')
+                .map(|line| line.trim_end_matches('\r'))
+                .collect();
+            self.run_units(&lines, true, options)
+        } else {
+            self.run_units(&[code], false, options)
+        }
+    }
+
+    /// Runs the script code lines one by one in the session context regardless of
+    /// [RunOptions::evaluate_per_line], e.g. lines which were transpiled separately.
+    ///
+    /// # Arguments
+    /// * `lines` - The script code lines to run.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The outcome of the script run with a result for each line.
+    pub fn run_lines(&mut self, lines: &[String], options: &RunOptions) -> RunOutcome {
+        let lines: Vec<&str> = lines.iter().map(|line| line.as_str()).collect();
+        self.run_units(&lines, true, options)
+    }
+
+    /// Runs the script code in the session context and then the tests it registered with
+    /// `describe`, `it`/`test`, `beforeEach` and `afterEach` of the built-in test framework.
+    ///
+    /// # Arguments
+    /// * `code` - The script code with the tests.
+    /// * `options` - The options of the script run; the code is always evaluated at once.
+    ///
+    /// # Returns
+    /// The test report.
+    pub fn run_tests(&mut self, code: &str, options: &RunOptions) -> TestReport {
+        self.run_tests_with_snapshots(code, options, &mut Snapshots::default(), false)
+    }
+
+    /// Runs the script code and the tests it registered like [Session::run_tests] matching the
+    /// `toMatchSnapshot` assertions against the stored snapshots. A value without a stored snapshot
+    /// is stored as a new snapshot, a mismatching value fails the test unless in the update mode.
+    ///
+    /// # Arguments
+    /// * `code` - The script code with the tests.
+    /// * `options` - The options of the script run; the code is always evaluated at once.
+    /// * `snapshots` - The stored snapshots; the written and updated snapshots are stored into these.
+    /// * `update` - A flag indicating whether to update the mismatched and remove the obsolete snapshots.
+    ///
+    /// # Returns
+    /// The test report.
+    pub fn run_tests_with_snapshots(
+        &mut self,
+        code: &str,
+        options: &RunOptions,
+        snapshots: &mut Snapshots,
+        update: bool,
+    ) -> TestReport {
+        let started = Instant::now();
+
+        self.run(RESET_TESTS_SCRIPT, &RunOptions::default());
+
+        let body_options = RunOptions {
+            evaluate_per_line: false,
+            ..options.clone()
+        };
+        let body = self.run(code, &body_options);
+
+        // The tests registered before a script body exception are run anyway.
+        let tests = if body.terminated {
+            RunOutcome::default()
+        } else {
+            self.run(
+                &run_tests_script(&snapshots.snapshots, update),
+                &RunOptions::default(),
+            )
+        };
+
+        let complete = body.exception().is_none();
+
+        let mut report = match tests.results.into_iter().next() {
+            Some(Ok(value)) => match parse_test_results(&value.text) {
+                Ok((mut report, snapshot_results)) => {
+                    report.snapshots =
+                        snapshots.apply(snapshot_results, &report.tests, complete, update);
+                    report
+                }
+                Err(e) => TestReport {
+                    script_exception: Some(ScriptException::from_message(e)),
+                    ..Default::default()
+                },
+            },
+            Some(Err(exception)) => TestReport {
+                script_exception: Some(exception),
+                ..Default::default()
+            },
+            None => TestReport::default(),
+        };
+
+        if let Some(exception) = body.exception() {
+            report.script_exception = Some(exception.clone());
+        }
+
+        report.logs = body.logs;
+        report.logs.extend(tests.logs);
+        report.duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        report
+    }
+
+    /// Runs the script code units in the session context.
+    ///
+    /// # Arguments
+    /// * `units` - The entire script code or the script code lines.
+    /// * `per_line` - A flag indicating whether the units are lines evaluated line by line.
+    /// * `options` - The options of the script run.
+    ///
+    /// # Returns
+    /// The outcome of the script run.
+    fn run_units(&mut self, units: &[&str], per_line: bool, options: &RunOptions) -> RunOutcome {
+        // A termination requested after the previous run finished must not cancel this run.
+        self.isolate.cancel_terminate_execution();
+        self.isolate.set_slot(CapturedLogs::default());
+
+        let mut outcome = RunOutcome::default();
+        // The names declared with `let`, `const` or `class` on the lines run so far.
+        let mut lexical_names = Vec::new();
+
+        {
+            let scope = &mut v8::HandleScope::with_context(&mut self.isolate, &self.context);
+
+            for (i, unit) in units.iter().enumerate() {
+                let started = Instant::now();
+
+                if per_line {
+                    if let Some(logs) = scope.get_slot_mut::<CapturedLogs>() {
+                        logs.line_number = Some(i as i32);
+                    }
+
+                    // Skip empty lines
+                    if unit.trim() == "" {
+                        outcome.results.push(Ok(empty_value()));
+                        outcome.durations.push(Duration::ZERO);
+                        continue;
+                    }
+                }
+
+                let code = match prepare_code(unit, options.language) {
+                    Ok(code) => code,
+                    Err(e) => {
+                        outcome.results.push(Err(ScriptException::from_message(e)));
+                        outcome.durations.push(started.elapsed());
+                        continue;
+                    }
+                };
+
+                let scope = &mut v8::TryCatch::new(scope);
+
+                let script = match v8::String::new(scope, code.as_str()) {
+                    Some(source) => v8::Script::compile(scope, source, None),
+                    None => None,
+                };
+
+                let value = match script {
+                    Some(script) => script.run(scope),
+                    None => None,
+                };
+
+                if per_line && options.capture_line_values {
+                    outcome.line_value_snapshots.push(snapshot_line(
+                        scope,
+                        i as i32,
+                        &code,
+                        value,
+                        &mut lexical_names,
+                    ));
+                }
+
+                let result = match value {
+                    Some(value) => {
+                        let snapshot = snapshot_value(scope, value);
+                        // Symbols throw on the string conversion, the preview is used for them instead.
+                        let text = match value.to_string(scope) {
+                            Some(text) => text.to_rust_string_lossy(scope),
+                            None => snapshot.preview.clone(),
+                        };
+                        Ok(ScriptValue { text, snapshot })
+                    }
+                    None => {
+                        outcome.terminated = scope.has_terminated();
+                        Err(exception_details(scope))
+                    }
+                };
+
+                outcome.results.push(result);
+                outcome.durations.push(started.elapsed());
+
+                if outcome.terminated {
+                    break;
+                }
+            }
+
+            // Capture the variables the script defined even if the script run failed midway.
+            if options.collect_globals && !outcome.terminated {
+                let scope = &mut v8::TryCatch::new(scope);
+                let context = scope.get_current_context();
+                outcome.global_variables =
+                    self.variables
+                        .collect_globals(scope, context, &self.excluded_names);
+            }
+        }
+
+        if let Some(logs) = self.isolate.remove_slot::<CapturedLogs>() {
+            outcome.logs = logs.entries;
+        }
+
+        outcome
+    }
+}
+
+/// Transpiles the code if needed and binds the console calls to the capture functions.
+///
+/// # Arguments
+/// * `code` - The script code.
+/// * `language` - The language of the script code.
+///
+/// # Returns
+/// The JavaScript code to run; Error if the code failed to transpile.
+fn prepare_code(code: &str, language: ScriptLanguage) -> Result<String, String> {
+    let code = match transpile_script(code, language) {
+        Ok(code) => code,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    Ok(replace_console_calls(&code))
+}
+
+/// Gets the result of a line which was not evaluated, e.g. an empty line.
+///
+/// # Returns
+/// An empty `undefined` value.
+fn empty_value() -> ScriptValue {
+    ScriptValue {
+        text: "".to_string(),
+        snapshot: ValueSnapshot {
+            value_type: "undefined".to_string(),
+            preview: "undefined".to_string(),
+            json: None,
+        },
+    }
+}
@@ -0,0 +1,69 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use crate::{types::ScriptException, values::value_to_string};
+
+/// The exception message used when the script execution was terminated, e.g. by a timeout.
+pub const EXECUTION_TERMINATED_MESSAGE: &str = "Script execution was terminated";
+
+/// Gets the details of the exception caught by a v8 try catch scope.
+///
+/// # Arguments
+/// * `scope` - The v8 try catch scope which caught the exception.
+///
+/// # Returns
+/// The details of the exception.
+pub(crate) fn exception_details(scope: &mut v8::TryCatch<v8::HandleScope>) -> ScriptException {
+    let message = match scope.exception() {
+        Some(exception) => value_to_string(scope, exception),
+        // The script execution was terminated.
+        None => EXECUTION_TERMINATED_MESSAGE.to_string(),
+    };
+
+    let (line_number, column, source_line) = match scope.message() {
+        Some(message) => {
+            let source_line = message
+                .get_source_line(scope)
+                .map(|line| line.to_rust_string_lossy(scope));
+            (
+                message.get_line_number(scope),
+                Some(message.get_start_column() + 1),
+                source_line,
+            )
+        }
+        None => (None, None, None),
+    };
+
+    let stack = scope
+        .stack_trace()
+        .map(|stack| value_to_string(scope, stack));
+
+    ScriptException {
+        message,
+        line_number,
+        column,
+        source_line,
+        stack,
+    }
+}
@@ -0,0 +1,50 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+//! The JavaScript and TypeScript evaluation engine of JsTsRunner on top of v8.
+//!
+//! The engine runs the scripts in sessions, captures the console calls and the values of the
+//! script run, and evaluates the scripts either at once or line by line. The scripts can define
+//! tests with the built-in `describe`, `it`/`test` and `expect` functions, see [Session::run_tests],
+//! and match values against snapshots stored next to the script, see [Snapshots].
+
+mod assignments;
+mod console;
+mod engine;
+mod exception;
+mod snapshot;
+mod testing;
+mod transpile;
+mod types;
+mod values;
+
+pub use engine::{Engine, RunOptions, RunOutcome, ScriptLanguage, Session, TerminateHandle};
+pub use exception::EXECUTION_TERMINATED_MESSAGE;
+pub use snapshot::{Snapshots, SNAPSHOT_FILE_EXTENSION};
+pub use transpile::{transpile_script, transpile_typescript};
+pub use types::{
+    GlobalVariable, LineByLineLog, LineValueSnapshot, LogEntry, LogLevel, NamedValueSnapshot,
+    ScriptException, ScriptValue, SnapshotMismatch, SnapshotStatus, SnapshotSummary,
+    TestCaseResult, TestFailure, TestReport, TestStatus, ValueSnapshot,
+};
@@ -0,0 +1,215 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    testing::assertion_diff,
+    types::{SnapshotMismatch, SnapshotStatus, SnapshotSummary, TestCaseResult, TestStatus},
+};
+
+/// The version of the snapshot file format.
+const SNAPSHOT_FILE_VERSION: u32 = 1;
+
+/// The extension appended to the script file name to get the name of its snapshot file.
+pub const SNAPSHOT_FILE_EXTENSION: &str = "snap";
+
+/// The result of a single `toMatchSnapshot` assertion reported by the test framework.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SnapshotResult {
+    /// The key of the snapshot.
+    pub key: String,
+    /// The result of the assertion.
+    pub status: SnapshotStatus,
+    /// The stored snapshot; `None` if the snapshot was written.
+    pub expected: Option<String>,
+    /// The formatted value.
+    pub actual: String,
+}
+
+/// The stored snapshots of a script, saved into a sidecar file next to the script file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshots {
+    /// The version of the snapshot file format.
+    #[serde(default)]
+    pub version: u32,
+    /// The formatted snapshot values by their keys.
+    pub snapshots: BTreeMap<String, String>,
+}
+
+impl Snapshots {
+    /// Gets the path of the snapshot file of a script file, e.g. `transform.ts.snap` for `transform.ts`.
+    ///
+    /// # Arguments
+    /// * `script_file` - The script file.
+    ///
+    /// # Returns
+    /// The path of the snapshot file.
+    pub fn sidecar_path(script_file: &Path) -> PathBuf {
+        let mut file_name = script_file.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(SNAPSHOT_FILE_EXTENSION);
+        script_file.with_file_name(file_name)
+    }
+
+    /// Loads the snapshots from a snapshot file.
+    ///
+    /// # Arguments
+    /// * `path` - The snapshot file.
+    ///
+    /// # Returns
+    /// The snapshots, empty if the file does not exist; Error if the file could not be read.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    }
+
+    /// Saves the snapshots into a snapshot file. The file is removed if there are no snapshots left.
+    ///
+    /// # Arguments
+    /// * `path` - The snapshot file.
+    ///
+    /// # Returns
+    /// Ok if the snapshots were saved; Error otherwise.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if self.snapshots.is_empty() {
+            return match fs::remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            };
+        }
+
+        let file = Self {
+            version: SNAPSHOT_FILE_VERSION,
+            snapshots: self.snapshots.clone(),
+        };
+
+        match serde_json::to_string_pretty(&file) {
+            Ok(json) => fs::write(path, json + "\n").map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Replaces the stored snapshots of the mismatched values with the values, e.g. after reviewing them.
+    ///
+    /// # Arguments
+    /// * `mismatches` - The mismatched values to accept as the new snapshots.
+    ///
+    /// # Returns
+    /// The number of the snapshots replaced.
+    pub fn accept(&mut self, mismatches: &[SnapshotMismatch]) -> usize {
+        for mismatch in mismatches {
+            self.snapshots
+                .insert(mismatch.key.clone(), mismatch.actual.clone());
+        }
+
+        mismatches.len()
+    }
+
+    /// Stores the written and updated snapshots of a test run and summarizes the snapshot assertions.
+    ///
+    /// # Arguments
+    /// * `results` - The snapshot assertion results of the test run.
+    /// * `tests` - The test results of the test run.
+    /// * `complete` - A flag indicating whether all the tests of the script were registered, i.e. the script body did not fail.
+    /// * `update` - A flag indicating whether the test run was in the update mode removing the obsolete snapshots.
+    ///
+    /// # Returns
+    /// The summary of the snapshot assertions.
+    pub(crate) fn apply(
+        &mut self,
+        results: Vec<SnapshotResult>,
+        tests: &[TestCaseResult],
+        complete: bool,
+        update: bool,
+    ) -> SnapshotSummary {
+        let mut summary = SnapshotSummary::default();
+
+        // The snapshots of the skipped and failed tests are kept as the tests may not have reached them.
+        let incomplete_tests: Vec<String> = tests
+            .iter()
+            .filter(|test| test.status != TestStatus::Passed)
+            .map(|test| {
+                let mut names = test.suite.clone();
+                names.push(test.name.clone());
+                names.join(" ")
+            })
+            .collect();
+
+        if complete {
+            summary.obsolete = self
+                .snapshots
+                .keys()
+                .filter(|key| !results.iter().any(|result| &result.key == *key))
+                .filter(|key| {
+                    !incomplete_tests.iter().any(|name| {
+                        key.starts_with(&format!("{} ", name))
+                            || key.starts_with(&format!("{}: ", name))
+                    })
+                })
+                .cloned()
+                .collect();
+        }
+
+        for result in results {
+            match result.status {
+                SnapshotStatus::Matched => summary.matched += 1,
+                SnapshotStatus::Written => summary.written += 1,
+                SnapshotStatus::Updated => summary.updated += 1,
+                SnapshotStatus::Mismatched => {
+                    let expected = result.expected.unwrap_or_default();
+                    summary.mismatched.push(SnapshotMismatch {
+                        diff: assertion_diff(&expected, &result.actual),
+                        key: result.key,
+                        expected,
+                        actual: result.actual,
+                    });
+                    continue;
+                }
+            }
+
+            self.snapshots.insert(result.key, result.actual);
+        }
+
+        if update {
+            for key in &summary.obsolete {
+                self.snapshots.remove(key);
+            }
+        }
+
+        summary
+    }
+}
@@ -0,0 +1,505 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+// The test framework injected into the script context: describe, it/test, beforeEach/afterEach and expect.
+// The tests are collected while the script body runs and run afterwards by the engine with __jsTsRunnerTests.run().
+(function (global) {
+    "use strict";
+
+    /**
+     * Creates a new describe block.
+     * @param {string} name - The name of the block; empty for the root block.
+     * @param {object | null} parent - The parent block.
+     * @param {boolean} skip - Whether the tests of the block are skipped.
+     * @returns {object} The describe block.
+     */
+    const createSuite = (name, parent, skip) => ({ name, parent, skip, beforeEach: [], afterEach: [] });
+
+    let rootSuite = createSuite("", null, false);
+    let currentSuite = rootSuite;
+    let tests = [];
+
+    // The stored snapshots by their keys, the snapshot update mode and the snapshot results of the current run.
+    let storedSnapshots = {};
+    let updateSnapshots = false;
+    let snapshotResults = [];
+
+    // The full name of the running test and the number of the snapshots it has matched so far.
+    let currentTest = null;
+
+    /**
+     * An assertion failure with the matcher details for the test report.
+     */
+    class AssertionError extends Error {
+        constructor(message, matcher, expected, actual) {
+            super(message);
+            this.name = "AssertionError";
+            this.matcher = matcher;
+            this.expected = expected;
+            this.actual = actual;
+        }
+    }
+
+    /**
+     * Formats a value into a readable multi-line string used in the assertion messages and diffs.
+     * @param {*} value - The value to format.
+     * @param {string} indent - The current indentation.
+     * @param {Array} seen - The objects being formatted to detect the circular references.
+     * @returns {string} The formatted value.
+     */
+    const format = (value, indent = "", seen = []) => {
+        if (value === undefined) {
+            return "undefined";
+        }
+        if (value === null) {
+            return "null";
+        }
+        if (typeof value === "string") {
+            return JSON.stringify(value);
+        }
+        if (typeof value === "number") {
+            return Object.is(value, -0) ? "-0" : String(value);
+        }
+        if (typeof value === "bigint") {
+            return `${value}n`;
+        }
+        if (typeof value === "symbol") {
+            return value.toString();
+        }
+        if (typeof value === "function") {
+            return `[Function ${value.name || "anonymous"}]`;
+        }
+        if (value instanceof Date) {
+            return `Date(${isNaN(value.getTime()) ? "Invalid Date" : value.toISOString()})`;
+        }
+        if (value instanceof RegExp) {
+            return value.toString();
+        }
+        if (value instanceof Error) {
+            return `${value.name}: ${value.message}`;
+        }
+        if (seen.includes(value)) {
+            return "[Circular]";
+        }
+
+        const innerIndent = indent + "  ";
+        const innerSeen = [...seen, value];
+        const formatEntries = (open, close, entries) =>
+            entries.length === 0 ? `${open}${close}` : `${open}\n${entries.map((entry) => `${innerIndent}${entry},`).join("\n")}\n${indent}${close}`;
+
+        if (Array.isArray(value)) {
+            return formatEntries(
+                "[",
+                "]",
+                value.map((item) => format(item, innerIndent, innerSeen))
+            );
+        }
+        if (value instanceof Map) {
+            return formatEntries(
+                "Map {",
+                "}",
+                [...value.entries()].map(([key, item]) => `${format(key, innerIndent, innerSeen)} => ${format(item, innerIndent, innerSeen)}`)
+            );
+        }
+        if (value instanceof Set) {
+            return formatEntries(
+                "Set {",
+                "}",
+                [...value.values()].map((item) => format(item, innerIndent, innerSeen))
+            );
+        }
+
+        const constructorName = value.constructor && value.constructor !== Object ? `${value.constructor.name} ` : "";
+        return formatEntries(
+            `${constructorName}{`,
+            "}",
+            Object.keys(value).map((key) => `${/^[A-Za-z_$][\w$]*$/.test(key) ? key : JSON.stringify(key)}: ${format(value[key], innerIndent, innerSeen)}`)
+        );
+    };
+
+    /**
+     * Compares two values recursively.
+     * @param {*} a - The first value.
+     * @param {*} b - The second value.
+     * @param {boolean} strict - Whether the undefined properties, the sparse arrays and the types are compared too.
+     * @param {Array} seen - The object pairs being compared to handle the circular references.
+     * @returns {boolean} Whether the values are equal.
+     */
+    const equals = (a, b, strict, seen = []) => {
+        if (Object.is(a, b)) {
+            return true;
+        }
+        if (typeof a !== "object" || typeof b !== "object" || a === null || b === null) {
+            return false;
+        }
+        if (seen.some(([seenA, seenB]) => seenA === a && seenB === b)) {
+            return true;
+        }
+        if (Object.getPrototypeOf(a) !== Object.getPrototypeOf(b) && (strict || Array.isArray(a) !== Array.isArray(b))) {
+            return false;
+        }
+
+        const innerSeen = [...seen, [a, b]];
+
+        if (a instanceof Date || b instanceof Date) {
+            return a instanceof Date && b instanceof Date && Object.is(a.getTime(), b.getTime());
+        }
+        if (a instanceof RegExp || b instanceof RegExp) {
+            return a instanceof RegExp && b instanceof RegExp && a.toString() === b.toString();
+        }
+        if (a instanceof Map || b instanceof Map) {
+            if (!(a instanceof Map && b instanceof Map) || a.size !== b.size) {
+                return false;
+            }
+            for (const [key, value] of a) {
+                if (!b.has(key) || !equals(value, b.get(key), strict, innerSeen)) {
+                    return false;
+                }
+            }
+            return true;
+        }
+        if (a instanceof Set || b instanceof Set) {
+            if (!(a instanceof Set && b instanceof Set) || a.size !== b.size) {
+                return false;
+            }
+            for (const value of a) {
+                if (!b.has(value) && ![...b].some((other) => equals(value, other, strict, innerSeen))) {
+                    return false;
+                }
+            }
+            return true;
+        }
+
+        const keys = (value) => Object.keys(value).filter((key) => strict || value[key] !== undefined);
+        const keysA = keys(a);
+        const keysB = keys(b);
+
+        if (keysA.length !== keysB.length || (Array.isArray(a) && a.length !== b.length)) {
+            return false;
+        }
+
+        return keysA.every((key) => Object.prototype.hasOwnProperty.call(b, key) && equals(a[key], b[key], strict, innerSeen));
+    };
+
+    /**
+     * Creates the matchers for a value.
+     * @param {*} actual - The actual value.
+     * @param {boolean} negated - Whether the matchers are negated with `.not`.
+     * @returns {object} The matchers.
+     */
+    const createMatchers = (actual, negated) => {
+        /**
+         * Asserts a matcher condition.
+         * @param {string} matcher - The name of the matcher.
+         * @param {boolean} pass - Whether the condition holds.
+         * @param {string} description - The description of the expectation.
+         * @param {*} expected - The expected value.
+         * @param {boolean} hasExpected - Whether the matcher has an expected value to show.
+         */
+        const assert = (matcher, pass, description, expected, hasExpected = true) => {
+            if (pass === negated) {
+                const name = negated ? `not.${matcher}` : matcher;
+                const message = `expect(received).${name}(${hasExpected ? "expected" : ""})\n\n${description}`;
+                throw new AssertionError(message, name, hasExpected ? format(expected) : null, format(actual));
+            }
+        };
+
+        const not = negated ? "not " : "";
+
+        const matchers = {
+            toBe: (expected) => assert("toBe", Object.is(actual, expected), `Expected ${format(actual)} ${not}to be ${format(expected)}`, expected),
+            toEqual: (expected) => assert("toEqual", equals(actual, expected, false), `Expected the value ${not}to equal the expected value`, expected),
+            toStrictEqual: (expected) => assert("toStrictEqual", equals(actual, expected, true), `Expected the value ${not}to strictly equal the expected value`, expected),
+            toBeTruthy: () => assert("toBeTruthy", !!actual, `Expected ${format(actual)} ${not}to be truthy`, undefined, false),
+            toBeFalsy: () => assert("toBeFalsy", !actual, `Expected ${format(actual)} ${not}to be falsy`, undefined, false),
+            toBeNull: () => assert("toBeNull", actual === null, `Expected ${format(actual)} ${not}to be null`, null),
+            toBeUndefined: () => assert("toBeUndefined", actual === undefined, `Expected ${format(actual)} ${not}to be undefined`, undefined),
+            toBeDefined: () => assert("toBeDefined", actual !== undefined, `Expected the value ${not}to be defined`, undefined, false),
+            toBeNaN: () => assert("toBeNaN", Number.isNaN(actual), `Expected ${format(actual)} ${not}to be NaN`, NaN),
+            toBeGreaterThan: (expected) => assert("toBeGreaterThan", actual > expected, `Expected ${format(actual)} ${not}to be greater than ${format(expected)}`, expected),
+            toBeGreaterThanOrEqual: (expected) =>
+                assert("toBeGreaterThanOrEqual", actual >= expected, `Expected ${format(actual)} ${not}to be greater than or equal to ${format(expected)}`, expected),
+            toBeLessThan: (expected) => assert("toBeLessThan", actual < expected, `Expected ${format(actual)} ${not}to be less than ${format(expected)}`, expected),
+            toBeLessThanOrEqual: (expected) =>
+                assert("toBeLessThanOrEqual", actual <= expected, `Expected ${format(actual)} ${not}to be less than or equal to ${format(expected)}`, expected),
+            toBeCloseTo: (expected, digits = 2) =>
+                assert("toBeCloseTo", Math.abs(expected - actual) < Math.pow(10, -digits) / 2, `Expected ${format(actual)} ${not}to be close to ${format(expected)} (${digits} digits)`, expected),
+            toBeInstanceOf: (expected) =>
+                assert("toBeInstanceOf", actual instanceof expected, `Expected the value ${not}to be an instance of ${expected && expected.name}`, expected, false),
+            toContain: (expected) =>
+                assert(
+                    "toContain",
+                    actual !== null && actual !== undefined && typeof actual.includes === "function" && actual.includes(expected),
+                    `Expected ${format(actual)} ${not}to contain ${format(expected)}`,
+                    expected
+                ),
+            toContainEqual: (expected) =>
+                assert(
+                    "toContainEqual",
+                    Array.isArray(actual) && actual.some((item) => equals(item, expected, false)),
+                    `Expected the array ${not}to contain an item equal to ${format(expected)}`,
+                    expected
+                ),
+            toHaveLength: (expected) =>
+                assert(
+                    "toHaveLength",
+                    actual !== null && actual !== undefined && actual.length === expected,
+                    `Expected the length ${actual !== null && actual !== undefined ? actual.length : actual} ${not}to be ${expected}`,
+                    expected
+                ),
+            toHaveProperty: (path, ...value) => {
+                const keys = Array.isArray(path) ? path : String(path).split(".");
+                let current = actual;
+                let found = true;
+                for (const key of keys) {
+                    if (current === null || current === undefined || !(key in Object(current))) {
+                        found = false;
+                        break;
+                    }
+                    current = current[key];
+                }
+                const pass = found && (value.length === 0 || equals(current, value[0], false));
+                assert("toHaveProperty", pass, `Expected the value ${not}to have the property ${keys.join(".")}`, value[0], value.length > 0);
+            },
+            toMatch: (expected) =>
+                assert(
+                    "toMatch",
+                    typeof actual === "string" && (expected instanceof RegExp ? expected.test(actual) : actual.includes(expected)),
+                    `Expected ${format(actual)} ${not}to match ${format(expected)}`,
+                    expected
+                ),
+            toThrow: (expected) => {
+                let thrown = null;
+                let didThrow = false;
+                try {
+                    actual();
+                } catch (error) {
+                    didThrow = true;
+                    thrown = error;
+                }
+
+                const message = thrown instanceof Error ? thrown.message : String(thrown);
+                let pass = didThrow;
+                if (didThrow && expected !== undefined) {
+                    if (typeof expected === "function") {
+                        pass = thrown instanceof expected;
+                    } else if (expected instanceof RegExp) {
+                        pass = expected.test(message);
+                    } else {
+                        pass = message.includes(String(expected));
+                    }
+                }
+
+                const description = didThrow ? `The function threw: ${message}` : "The function did not throw";
+                if (pass === negated) {
+                    const name = negated ? "not.toThrow" : "toThrow";
+                    throw new AssertionError(`expect(received).${name}(${expected === undefined ? "" : "expected"})\n\n${description}`, name, expected === undefined ? null : format(expected), didThrow ? format(thrown) : null);
+                }
+            },
+        };
+
+        /**
+         * Compares the value to the stored snapshot of the test, storing the snapshot if the test has none.
+         * @param {string} hint - The optional name of the snapshot to tell the snapshots of the same test apart.
+         */
+        matchers.toMatchSnapshot = (hint) => {
+            if (negated) {
+                throw new Error("toMatchSnapshot cannot be negated.");
+            }
+            if (currentTest === null) {
+                throw new Error("toMatchSnapshot must be called inside a test.");
+            }
+
+            currentTest.snapshotCount++;
+            const key = `${currentTest.fullName}${hint === undefined ? "" : `: ${hint}`} ${currentTest.snapshotCount}`;
+            const value = format(actual);
+            const stored = Object.prototype.hasOwnProperty.call(storedSnapshots, key) ? storedSnapshots[key] : null;
+
+            if (stored === null) {
+                snapshotResults.push({ key, status: "written", expected: null, actual: value });
+            } else if (stored === value) {
+                snapshotResults.push({ key, status: "matched", expected: stored, actual: value });
+            } else if (updateSnapshots) {
+                snapshotResults.push({ key, status: "updated", expected: stored, actual: value });
+            } else {
+                snapshotResults.push({ key, status: "mismatched", expected: stored, actual: value });
+                throw new AssertionError(`expect(received).toMatchSnapshot()\n\nThe value does not match the stored snapshot "${key}"`, "toMatchSnapshot", stored, value);
+            }
+        };
+
+        return matchers;
+    };
+
+    /**
+     * Creates an expectation for a value.
+     * @param {*} actual - The actual value.
+     * @returns {object} The matchers of the value with the negated matchers in the `not` property.
+     */
+    const expect = (actual) => {
+        const matchers = createMatchers(actual, false);
+        matchers.not = createMatchers(actual, true);
+        return matchers;
+    };
+
+    /**
+     * Registers a test into the current describe block.
+     * @param {string} name - The name of the test.
+     * @param {Function} fn - The test function.
+     * @param {boolean} skip - Whether the test is skipped.
+     */
+    const registerTest = (name, fn, skip) => {
+        tests.push({ name: String(name), fn, suite: currentSuite, skip: skip || currentSuite.skip });
+    };
+
+    /**
+     * Runs the body of a describe block collecting its tests and hooks.
+     * @param {string} name - The name of the block.
+     * @param {Function} fn - The body of the block.
+     * @param {boolean} skip - Whether the tests of the block are skipped.
+     */
+    const registerSuite = (name, fn, skip) => {
+        const parent = currentSuite;
+        currentSuite = createSuite(String(name), parent, skip || parent.skip);
+        try {
+            fn();
+        } finally {
+            currentSuite = parent;
+        }
+    };
+
+    const describe = (name, fn) => registerSuite(name, fn, false);
+    describe.skip = (name, fn) => registerSuite(name, fn, true);
+
+    const it = (name, fn) => registerTest(name, fn, false);
+    it.skip = (name, fn) => registerTest(name, fn, true);
+
+    const beforeEach = (fn) => currentSuite.beforeEach.push(fn);
+    const afterEach = (fn) => currentSuite.afterEach.push(fn);
+
+    /**
+     * Gets the describe blocks of a test from the outermost to the innermost.
+     * @param {object} suite - The innermost describe block.
+     * @returns {Array} The describe blocks.
+     */
+    const suitePath = (suite) => {
+        const path = [];
+        for (let current = suite; current !== null; current = current.parent) {
+            path.unshift(current);
+        }
+        return path;
+    };
+
+    /**
+     * Converts a thrown value into the failure details of the test report.
+     * @param {*} error - The thrown value.
+     * @returns {object} The failure details.
+     */
+    const failure = (error) => ({
+        message: error instanceof Error ? error.message : format(error),
+        matcher: error instanceof AssertionError ? error.matcher : null,
+        expected: error instanceof AssertionError ? error.expected : null,
+        actual: error instanceof AssertionError ? error.actual : null,
+        stack: error instanceof Error && error.stack ? String(error.stack) : null,
+    });
+
+    /**
+     * Runs the collected tests with their hooks.
+     * @param {object} snapshots - The stored snapshots by their keys.
+     * @param {boolean} update - Whether the mismatched snapshots are updated instead of failing the tests.
+     * @returns {string} The test results and the snapshot results as JSON.
+     */
+    const run = (snapshots = {}, update = false) => {
+        const results = [];
+        storedSnapshots = snapshots;
+        updateSnapshots = update;
+        snapshotResults = [];
+
+        for (const test of tests) {
+            const suites = suitePath(test.suite);
+            const suiteNames = suites.filter((suite) => suite.parent !== null).map((suite) => suite.name);
+            const result = { name: test.name, suite: suiteNames, status: "skipped", duration_ms: 0, failure: null };
+
+            if (!test.skip) {
+                const started = Date.now();
+                let error = null;
+                // A test may throw any value including null or undefined.
+                let failed = false;
+                currentTest = { fullName: [...suiteNames, test.name].join(" "), snapshotCount: 0 };
+                try {
+                    for (const suite of suites) {
+                        suite.beforeEach.forEach((hook) => hook());
+                    }
+                    const returned = test.fn();
+                    if (returned !== null && typeof returned === "object" && typeof returned.then === "function") {
+                        throw new Error("Asynchronous tests are not supported.");
+                    }
+                } catch (e) {
+                    failed = true;
+                    error = e;
+                }
+
+                // The after hooks run from the innermost block outwards even if the test failed.
+                for (const suite of [...suites].reverse()) {
+                    for (const hook of suite.afterEach) {
+                        try {
+                            hook();
+                        } catch (e) {
+                            if (!failed) {
+                                failed = true;
+                                error = e;
+                            }
+                        }
+                    }
+                }
+
+                currentTest = null;
+                result.duration_ms = Date.now() - started;
+                result.status = failed ? "failed" : "passed";
+                result.failure = failed ? failure(error) : null;
+            }
+
+            results.push(result);
+        }
+
+        return JSON.stringify({ tests: results, snapshots: snapshotResults });
+    };
+
+    /**
+     * Clears the collected tests and hooks before a new script run.
+     */
+    const reset = () => {
+        rootSuite = createSuite("", null, false);
+        currentSuite = rootSuite;
+        tests = [];
+    };
+
+    global.describe = describe;
+    global.it = it;
+    global.test = it;
+    global.beforeEach = beforeEach;
+    global.afterEach = afterEach;
+    global.expect = expect;
+
+    Object.defineProperty(global, "__jsTsRunnerTests", { value: { run, reset, format }, enumerable: false });
+})(globalThis);
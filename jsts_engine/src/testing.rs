@@ -0,0 +1,138 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use similar::TextDiff;
+
+use crate::{
+    snapshot::SnapshotResult,
+    types::{TestCaseResult, TestReport, TestStatus},
+};
+
+/// The built-in test framework defining `describe`, `it`/`test`, `beforeEach`, `afterEach` and `expect`.
+const TEST_FRAMEWORK: &str = include_str!("test_framework.js");
+
+/// The script clearing the collected tests before the script body runs.
+pub(crate) const RESET_TESTS_SCRIPT: &str = "__jsTsRunnerTests.reset()";
+
+/// The results of a test run returned by the test framework as JSON.
+#[derive(Deserialize)]
+struct TestRunResults {
+    /// The results of the tests in the registration order.
+    tests: Vec<TestCaseResult>,
+    /// The results of the snapshot assertions in the order they were made.
+    snapshots: Vec<SnapshotResult>,
+}
+
+/// Creates the script running the collected tests and returning the results as JSON.
+///
+/// # Arguments
+/// * `snapshots` - The stored snapshots by their keys.
+/// * `update` - A flag indicating whether the mismatched snapshots are updated instead of failing the tests.
+///
+/// # Returns
+/// The script code.
+pub(crate) fn run_tests_script(snapshots: &BTreeMap<String, String>, update: bool) -> String {
+    // JSON is a valid JavaScript object literal.
+    let snapshots = serde_json::to_string(snapshots).unwrap_or_else(|_| "{}".to_string());
+    format!("__jsTsRunnerTests.run({}, {})", snapshots, update)
+}
+
+/// Defines the test framework functions into the global object of the current context.
+///
+/// # Arguments
+/// * `scope` - The v8 scope of the context.
+pub(crate) fn install_test_framework(scope: &mut v8::HandleScope) {
+    let source = match v8::String::new(scope, TEST_FRAMEWORK) {
+        Some(source) => source,
+        None => return,
+    };
+
+    if let Some(script) = v8::Script::compile(scope, source, None) {
+        script.run(scope);
+    }
+}
+
+/// Parses the JSON results of the test framework into a test report and adds the diffs of the failed assertions.
+///
+/// # Arguments
+/// * `json` - The test results as JSON.
+///
+/// # Returns
+/// The test report without the script and snapshot details with the snapshot assertion results;
+/// Error if the results could not be parsed.
+pub(crate) fn parse_test_results(json: &str) -> Result<(TestReport, Vec<SnapshotResult>), String> {
+    let TestRunResults {
+        mut tests,
+        snapshots,
+    } = match serde_json::from_str(json) {
+        Ok(results) => results,
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    for test in &mut tests {
+        if let Some(failure) = &mut test.failure {
+            if let (Some(expected), Some(actual)) = (&failure.expected, &failure.actual) {
+                if expected != actual {
+                    failure.diff = Some(assertion_diff(expected, actual));
+                }
+            }
+        }
+    }
+
+    let count = |status: TestStatus| tests.iter().filter(|test| test.status == status).count();
+
+    let report = TestReport {
+        passed: count(TestStatus::Passed),
+        failed: count(TestStatus::Failed),
+        skipped: count(TestStatus::Skipped),
+        tests,
+        ..Default::default()
+    };
+
+    Ok((report, snapshots))
+}
+
+/// Creates a unified line diff between the formatted expected and actual values.
+///
+/// # Arguments
+/// * `expected` - The formatted expected value.
+/// * `actual` - The formatted actual value.
+///
+/// # Returns
+/// The unified diff.
+pub(crate) fn assertion_diff(expected: &str, actual: &str) -> String {
+    // A trailing line break keeps the last lines from being reported as changed "no newline" lines.
+    let expected = format!("{}\n", expected);
+    let actual = format!("{}\n", actual);
+
+    TextDiff::from_lines(&expected, &actual)
+        .unified_diff()
+        .header("expected", "actual")
+        .to_string()
+}
@@ -0,0 +1,106 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use deno_ast::{
+    EmitOptions, MediaType, ModuleSpecifier, ParseParams, SourceMapOption, TranspileOptions,
+};
+
+use crate::engine::ScriptLanguage;
+
+/// Transpiles TypeScript code to JavaScript. The types are stripped and the code is otherwise
+/// left as is, the same way the frontend transpiles the TypeScript code before the script run.
+///
+/// # Arguments
+/// * `code` - The TypeScript code.
+///
+/// # Returns
+/// The transpiled JavaScript code; Error if the code failed to parse or transpile.
+pub fn transpile_typescript(code: &str) -> Result<String, String> {
+    transpile(code, MediaType::TypeScript, "file:///script.ts")
+}
+
+/// Transpiles script code of any language to JavaScript. The JSX elements are transpiled into
+/// `React.createElement` calls, so the script must define the `React` object to create them with.
+///
+/// # Arguments
+/// * `code` - The script code.
+/// * `language` - The language of the script code.
+///
+/// # Returns
+/// The JavaScript code; Error if the code failed to parse or transpile.
+pub fn transpile_script(code: &str, language: ScriptLanguage) -> Result<String, String> {
+    match language {
+        ScriptLanguage::JavaScript => Ok(code.to_string()),
+        ScriptLanguage::TypeScript => transpile_typescript(code),
+        ScriptLanguage::Jsx => transpile(code, MediaType::Jsx, "file:///script.jsx"),
+        ScriptLanguage::Tsx => transpile(code, MediaType::Tsx, "file:///script.tsx"),
+    }
+}
+
+/// Transpiles code of a media type to JavaScript.
+///
+/// # Arguments
+/// * `code` - The code.
+/// * `media_type` - The media type of the code.
+/// * `specifier` - The module specifier with the file extension of the media type.
+///
+/// # Returns
+/// The transpiled JavaScript code; Error if the code failed to parse or transpile.
+fn transpile(code: &str, media_type: MediaType, specifier: &str) -> Result<String, String> {
+    let specifier = match ModuleSpecifier::parse(specifier) {
+        Ok(specifier) => specifier,
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    let parsed = match deno_ast::parse_module(ParseParams {
+        specifier,
+        text: code.into(),
+        media_type,
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+    }) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    let transpiled = match parsed.transpile(
+        &TranspileOptions::default(),
+        &EmitOptions {
+            source_map: SourceMapOption::None,
+            ..Default::default()
+        },
+    ) {
+        Ok(transpiled) => transpiled,
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    Ok(transpiled.into_source().text)
+}
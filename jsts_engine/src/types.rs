@@ -0,0 +1,278 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The captured log calls of a single line evaluated in the line-by-line mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LineByLineLog {
+    /// The line number the log calls were made on.
+    pub line_number: i32,
+    /// The formatted log calls, see [LogEntry].
+    pub lines: Vec<String>,
+}
+
+/// The level of a captured console call.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// A `console.log()` call.
+    Log,
+    /// A `console.warn()` call.
+    Warn,
+    /// A `console.error()` call.
+    Error,
+}
+
+/// A single captured console call of a script run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LogEntry {
+    /// The level of the console call.
+    pub level: LogLevel,
+    /// The console call arguments joined with a space.
+    pub message: String,
+    /// The line being evaluated in the line-by-line mode; `None` if the entire code was evaluated at once.
+    pub line_number: Option<i32>,
+}
+
+impl fmt::Display for LogEntry {
+    /// Formats the entry with the level prefix, e.g. `WARN: message`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.level {
+            LogLevel::Log => "LOG",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+
+        write!(f, "{}: {}", prefix, self.message)
+    }
+}
+
+/// A variable defined by the script in the context global object or a child value of one.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GlobalVariable {
+    /// The name of the variable or the property key of the child value.
+    pub name: String,
+    /// The type of the value, e.g. `number`, `string`, `array` or `object`.
+    pub value_type: String,
+    /// A short human readable preview of the value.
+    pub preview: String,
+    /// The handle used to expand the child values of the value; `None` if the value is not expandable.
+    pub handle: Option<u32>,
+}
+
+/// A structured snapshot of a script value.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ValueSnapshot {
+    /// The type of the value, e.g. `number`, `string`, `array` or `object`.
+    pub value_type: String,
+    /// A short human readable preview of the value.
+    pub preview: String,
+    /// The value serialized as JSON; `None` if the value is not serializable or the JSON is too long.
+    pub json: Option<String>,
+}
+
+/// A snapshot of a named value, e.g. a variable assigned on a line.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NamedValueSnapshot {
+    /// The name of the value.
+    pub name: String,
+    /// The snapshot of the value.
+    pub value: ValueSnapshot,
+}
+
+/// The value snapshots of a single line evaluated in the line-by-line mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LineValueSnapshot {
+    /// The line number the snapshots were captured for.
+    pub line_number: i32,
+    /// The completion value of the line; `None` if the line failed to evaluate.
+    pub value: Option<ValueSnapshot>,
+    /// The variables assigned on the line.
+    pub assigned: Vec<NamedValueSnapshot>,
+}
+
+/// The completion value of a script run or a single line.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScriptValue {
+    /// The value converted to a string the same way as the JavaScript `String()` function does.
+    pub text: String,
+    /// The structured snapshot of the value.
+    pub snapshot: ValueSnapshot,
+}
+
+/// The details of an exception thrown by a script.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScriptException {
+    /// The exception message, e.g. `Error: Something went wrong`.
+    pub message: String,
+    /// The line number where the exception was thrown.
+    pub line_number: Option<usize>,
+    /// The column number where the exception was thrown.
+    pub column: Option<usize>,
+    /// The source code line where the exception was thrown.
+    pub source_line: Option<String>,
+    /// The stack trace of the exception.
+    pub stack: Option<String>,
+}
+
+impl ScriptException {
+    /// Creates the details of an exception which has only a message, e.g. a transpilation error.
+    ///
+    /// # Arguments
+    /// * `message` - The exception message.
+    ///
+    /// # Returns
+    /// The details of the exception.
+    pub fn from_message(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line_number: None,
+            column: None,
+            source_line: None,
+            stack: None,
+        }
+    }
+}
+
+/// The status of a test of the built-in test framework.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStatus {
+    /// The test passed.
+    Passed,
+    /// The test or one of its hooks threw an exception or an assertion failed.
+    Failed,
+    /// The test was skipped with `it.skip`, `test.skip` or `describe.skip`.
+    Skipped,
+}
+
+/// The details of a failed test.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TestFailure {
+    /// The failure message.
+    pub message: String,
+    /// The name of the failed matcher, e.g. `toEqual` or `not.toBe`; `None` if the test threw an exception.
+    pub matcher: Option<String>,
+    /// The formatted expected value of the failed matcher.
+    pub expected: Option<String>,
+    /// The formatted actual value of the failed matcher.
+    pub actual: Option<String>,
+    /// The unified line diff between the expected and the actual value.
+    #[serde(default)]
+    pub diff: Option<String>,
+    /// The stack trace of the failure.
+    pub stack: Option<String>,
+}
+
+/// The result of a single test of the built-in test framework.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TestCaseResult {
+    /// The name of the test.
+    pub name: String,
+    /// The names of the `describe` blocks of the test from the outermost to the innermost.
+    pub suite: Vec<String>,
+    /// The status of the test.
+    pub status: TestStatus,
+    /// The duration of the test with its hooks in milliseconds.
+    pub duration_ms: f64,
+    /// The details of the failure if the test failed.
+    pub failure: Option<TestFailure>,
+}
+
+/// The result of a single `toMatchSnapshot` assertion.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotStatus {
+    /// The value matched the stored snapshot.
+    Matched,
+    /// The test had no stored snapshot so the value was stored as a new snapshot.
+    Written,
+    /// The value did not match the stored snapshot and the snapshot was replaced in the update mode.
+    Updated,
+    /// The value did not match the stored snapshot and the test failed.
+    Mismatched,
+}
+
+/// A snapshot assertion whose value did not match the stored snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SnapshotMismatch {
+    /// The key of the snapshot, i.e. the full name of the test with the snapshot number.
+    pub key: String,
+    /// The stored snapshot.
+    pub expected: String,
+    /// The formatted value the test produced.
+    pub actual: String,
+    /// The unified line diff between the stored snapshot and the value.
+    pub diff: String,
+}
+
+/// The summary of the snapshot assertions of a test run.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SnapshotSummary {
+    /// The number of the values matching their stored snapshots.
+    pub matched: usize,
+    /// The number of the new snapshots stored.
+    pub written: usize,
+    /// The number of the stored snapshots replaced in the update mode.
+    pub updated: usize,
+    /// The values which did not match their stored snapshots.
+    pub mismatched: Vec<SnapshotMismatch>,
+    /// The keys of the stored snapshots no test uses anymore; removed in the update mode.
+    pub obsolete: Vec<String>,
+}
+
+/// The report of a test run of the built-in test framework.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TestReport {
+    /// The results of the tests in the registration order.
+    pub tests: Vec<TestCaseResult>,
+    /// The number of the passed tests.
+    pub passed: usize,
+    /// The number of the failed tests.
+    pub failed: usize,
+    /// The number of the skipped tests.
+    pub skipped: usize,
+    /// The duration of the script body and the test run in milliseconds.
+    pub duration_ms: f64,
+    /// The exception thrown by the script body outside of the tests.
+    pub script_exception: Option<ScriptException>,
+    /// The captured console calls of the script body and the tests.
+    pub logs: Vec<LogEntry>,
+    /// The results of the snapshot assertions of the tests.
+    #[serde(default)]
+    pub snapshots: SnapshotSummary,
+}
+
+impl TestReport {
+    /// Checks if all the tests passed and the script body ran without an exception.
+    ///
+    /// # Returns
+    /// `true` if the test run succeeded; `false` otherwise.
+    pub fn success(&self) -> bool {
+        self.failed == 0 && self.script_exception.is_none()
+    }
+}
@@ -0,0 +1,505 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use crate::{
+    assignments::{assigned_identifiers, lexical_declarations},
+    types::{GlobalVariable, LineValueSnapshot, NamedValueSnapshot, ValueSnapshot},
+};
+
+/// The maximum number of child values described for a single expanded value.
+const VARIABLE_MAX_CHILDREN: usize = 100;
+/// The maximum length of the JSON of a value snapshot; longer JSON is not captured.
+const SNAPSHOT_MAX_JSON_LENGTH: usize = 100_000;
+
+/// Gets the type name of a v8 value. Some object types are named more specifically than the
+/// JavaScript `typeof` operator does, e.g. `null`, `array`, `date` or `map`.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `value` - The v8 value.
+///
+/// # Returns
+/// The type name of the value.
+pub(crate) fn value_type_name(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> String {
+    if value.is_null() {
+        "null".to_string()
+    } else if value.is_array() {
+        "array".to_string()
+    } else if value.is_date() {
+        "date".to_string()
+    } else if value.is_reg_exp() {
+        "regexp".to_string()
+    } else if value.is_map() {
+        "map".to_string()
+    } else if value.is_set() {
+        "set".to_string()
+    } else if value.is_promise() {
+        "promise".to_string()
+    } else if value.is_native_error() {
+        "error".to_string()
+    } else {
+        value.type_of(scope).to_rust_string_lossy(scope)
+    }
+}
+
+/// Formats a short human readable preview of a v8 value.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `value` - The v8 value.
+///
+/// # Returns
+/// The preview of the value.
+pub(crate) fn preview_value(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> String {
+    if value.is_string() {
+        return format!("\"{}\"", value_to_string(scope, value));
+    }
+
+    if value.is_big_int() {
+        return format!("{}n", value_to_string(scope, value));
+    }
+
+    if let Ok(symbol) = v8::Local::<v8::Symbol>::try_from(value) {
+        let description = symbol.description(scope);
+        if description.is_undefined() {
+            return "Symbol()".to_string();
+        }
+        return format!("Symbol({})", value_to_string(scope, description));
+    }
+
+    if let Ok(array) = v8::Local::<v8::Array>::try_from(value) {
+        return format!("Array({})", array.length());
+    }
+
+    if let Ok(function) = v8::Local::<v8::Function>::try_from(value) {
+        let name = function.get_name(scope).to_rust_string_lossy(scope);
+        return format!("ƒ {}()", name);
+    }
+
+    if let Ok(map) = v8::Local::<v8::Map>::try_from(value) {
+        return format!("Map({})", map.size());
+    }
+
+    if let Ok(set) = v8::Local::<v8::Set>::try_from(value) {
+        return format!("Set({})", set.size());
+    }
+
+    if value.is_date() || value.is_reg_exp() || value.is_native_error() {
+        return value_to_string(scope, value);
+    }
+
+    if let Ok(object) = v8::Local::<v8::Object>::try_from(value) {
+        let constructor_name = object.get_constructor_name().to_rust_string_lossy(scope);
+        return format!("{} {{…}}", constructor_name);
+    }
+
+    value_to_string(scope, value)
+}
+
+/// Converts a v8 value to a string, returning an empty string if the conversion fails.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `value` - The v8 value.
+///
+/// # Returns
+/// The value as a string.
+pub(crate) fn value_to_string(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> String {
+    match value.to_string(scope) {
+        Some(value) => value.to_rust_string_lossy(scope),
+        None => "".to_string(),
+    }
+}
+
+/// Gets the own enumerable property names and values of a v8 object.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `object` - The v8 object.
+/// * `limit` - The maximum number of properties to get.
+///
+/// # Returns
+/// The property names and values of the object.
+fn own_properties<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    object: v8::Local<'s, v8::Object>,
+    limit: usize,
+) -> Vec<(String, v8::Local<'s, v8::Value>)> {
+    let names = match object.get_own_property_names(scope, v8::GetPropertyNamesArgs::default()) {
+        Some(names) => names,
+        None => return vec![],
+    };
+
+    let mut result = Vec::new();
+    for i in 0..names.length() {
+        if result.len() >= limit {
+            break;
+        }
+
+        let key = match names.get_index(scope, i) {
+            Some(key) => key,
+            None => continue,
+        };
+
+        let name = value_to_string(scope, key);
+
+        if let Some(value) = object.get(scope, key) {
+            result.push((name, value));
+        }
+    }
+
+    result
+}
+
+/// Gets the names of the own enumerable properties of the context global object.
+/// The names are used to exclude the built-in and injected globals from the global variable explorer.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `context` - The v8 context.
+///
+/// # Returns
+/// The names of the global object properties.
+pub(crate) fn global_property_names(
+    scope: &mut v8::HandleScope,
+    context: v8::Local<v8::Context>,
+) -> Vec<String> {
+    let global = context.global(scope);
+    own_properties(scope, global, usize::MAX)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Collects the variables a script defined into the context global object for the global variable explorer.
+/// The objects are kept with their handles in the session so that their child values are described on
+/// demand when expanded, however deep in the object graph they are.
+#[derive(Default)]
+pub(crate) struct GlobalVariableCollector {
+    /// The last handle given to an expandable value.
+    last_handle: u32,
+    /// The context global object, which is not expandable as it holds the built-in globals.
+    global: Option<v8::Global<v8::Object>>,
+    /// The expandable objects with their handles.
+    objects: Vec<(u32, v8::Global<v8::Object>)>,
+}
+
+impl GlobalVariableCollector {
+    /// Collects the global variables of the context excluding the specified names. The handles of the
+    /// previously collected variables are no longer valid.
+    ///
+    /// # Arguments
+    /// * `scope` - The v8 scope.
+    /// * `context` - The v8 context.
+    /// * `excluded_names` - The names of the global properties to exclude, e.g. the built-in and injected globals.
+    ///
+    /// # Returns
+    /// The global variables of the context.
+    pub(crate) fn collect_globals<'s>(
+        &mut self,
+        scope: &mut v8::HandleScope<'s>,
+        context: v8::Local<'s, v8::Context>,
+        excluded_names: &[String],
+    ) -> Vec<GlobalVariable> {
+        let global = context.global(scope);
+        self.global = Some(v8::Global::new(scope, global));
+        self.objects.clear();
+
+        let mut result = Vec::new();
+        for (name, value) in own_properties(scope, global, usize::MAX) {
+            if excluded_names.contains(&name) {
+                continue;
+            }
+
+            result.push(self.describe(scope, name, value));
+        }
+
+        result
+    }
+
+    /// Describes the child values of an expandable value.
+    ///
+    /// # Arguments
+    /// * `scope` - The v8 scope.
+    /// * `handle` - The handle of the value to expand.
+    ///
+    /// # Returns
+    /// The child values of the value; `None` if the handle was not found.
+    pub(crate) fn expand(
+        &mut self,
+        scope: &mut v8::HandleScope,
+        handle: u32,
+    ) -> Option<Vec<GlobalVariable>> {
+        let object = self
+            .objects
+            .iter()
+            .find(|(object_handle, _)| *object_handle == handle)
+            .map(|(_, object)| v8::Local::new(scope, object))?;
+
+        let mut children = Vec::new();
+        for (child_name, child_value) in child_values(scope, object.into(), object) {
+            children.push(self.describe(scope, child_name, child_value));
+        }
+
+        Some(children)
+    }
+
+    /// Describes a single value giving a handle to it if it has child values.
+    ///
+    /// # Arguments
+    /// * `scope` - The v8 scope.
+    /// * `name` - The name of the value.
+    /// * `value` - The v8 value.
+    ///
+    /// # Returns
+    /// The description of the value.
+    fn describe<'s>(
+        &mut self,
+        scope: &mut v8::HandleScope<'s>,
+        name: String,
+        value: v8::Local<'s, v8::Value>,
+    ) -> GlobalVariable {
+        let value_type = value_type_name(scope, value);
+        let preview = preview_value(scope, value);
+
+        let object = match v8::Local::<v8::Object>::try_from(value) {
+            Ok(object) if !value.is_function() && !self.is_global(scope, value) => object,
+            _ => {
+                return GlobalVariable {
+                    name,
+                    value_type,
+                    preview,
+                    handle: None,
+                };
+            }
+        };
+
+        // Reuse the handle of an already described object, e.g. with circular references.
+        let existing = self
+            .objects
+            .iter()
+            .find(|(_, existing)| v8::Local::new(scope, existing).strict_equals(value))
+            .map(|(handle, _)| *handle);
+
+        let handle = match existing {
+            Some(handle) => handle,
+            None => {
+                self.last_handle += 1;
+                self.objects
+                    .push((self.last_handle, v8::Global::new(scope, object)));
+                self.last_handle
+            }
+        };
+
+        GlobalVariable {
+            name,
+            value_type,
+            preview,
+            handle: Some(handle),
+        }
+    }
+
+    /// Checks whether a value is the context global object.
+    ///
+    /// # Arguments
+    /// * `scope` - The v8 scope.
+    /// * `value` - The v8 value.
+    ///
+    /// # Returns
+    /// `true` if the value is the context global object; `false` otherwise.
+    fn is_global(&self, scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> bool {
+        match &self.global {
+            Some(global) => v8::Local::new(scope, global).strict_equals(value),
+            None => false,
+        }
+    }
+}
+
+/// Gets the child values of an object for the global variable explorer.
+/// The entries of maps and sets are listed instead of their properties.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `value` - The v8 value of the object.
+/// * `object` - The v8 object.
+///
+/// # Returns
+/// The names and values of the child values.
+fn child_values<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    value: v8::Local<'s, v8::Value>,
+    object: v8::Local<'s, v8::Object>,
+) -> Vec<(String, v8::Local<'s, v8::Value>)> {
+    if let Ok(map) = v8::Local::<v8::Map>::try_from(value) {
+        // The map entries are returned as a flat array of keys and values.
+        let entries = map.as_array(scope);
+        let mut result = Vec::new();
+        let mut i = 0;
+        while i + 1 < entries.length() && result.len() < VARIABLE_MAX_CHILDREN {
+            if let (Some(key), Some(value)) =
+                (entries.get_index(scope, i), entries.get_index(scope, i + 1))
+            {
+                result.push((preview_value(scope, key), value));
+            }
+            i += 2;
+        }
+        return result;
+    }
+
+    if let Ok(set) = v8::Local::<v8::Set>::try_from(value) {
+        let entries = set.as_array(scope);
+        let mut result = Vec::new();
+        for i in 0..entries.length().min(VARIABLE_MAX_CHILDREN as u32) {
+            if let Some(value) = entries.get_index(scope, i) {
+                result.push((i.to_string(), value));
+            }
+        }
+        return result;
+    }
+
+    own_properties(scope, object, VARIABLE_MAX_CHILDREN)
+}
+
+/// Captures a structured snapshot of a v8 value.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `value` - The v8 value.
+///
+/// # Returns
+/// The snapshot of the value.
+pub(crate) fn snapshot_value(
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+) -> ValueSnapshot {
+    let value_type = value_type_name(scope, value);
+    let preview = preview_value(scope, value);
+
+    // Values without a JSON representation stringify to "undefined", circular structures and BigInts throw.
+    let json = if value.is_undefined() || value.is_function() || value.is_symbol() {
+        None
+    } else {
+        let scope = &mut v8::TryCatch::new(scope);
+        match v8::json::stringify(scope, value) {
+            Some(json) if json.length() <= SNAPSHOT_MAX_JSON_LENGTH => {
+                Some(json.to_rust_string_lossy(scope))
+            }
+            _ => None,
+        }
+    };
+
+    ValueSnapshot {
+        value_type,
+        preview,
+        json,
+    }
+}
+
+/// Captures the value snapshots of a single line evaluated in the line-by-line mode.
+/// The variables assigned at the top level of the line are read from the context after the line run.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `line_number` - The number of the line.
+/// * `line` - The code of the line.
+/// * `value` - The completion value of the line; `None` if the line failed to evaluate.
+/// * `lexical_names` - The names declared with `let`, `const` or `class` on the previous lines of the run.
+///   The names declared on the line are added to the list.
+///
+/// # Returns
+/// The value snapshots of the line.
+pub(crate) fn snapshot_line(
+    scope: &mut v8::HandleScope,
+    line_number: i32,
+    line: &str,
+    value: Option<v8::Local<v8::Value>>,
+    lexical_names: &mut Vec<String>,
+) -> LineValueSnapshot {
+    let value = value.map(|value| snapshot_value(scope, value));
+
+    for name in lexical_declarations(line) {
+        if !lexical_names.contains(&name) {
+            lexical_names.push(name);
+        }
+    }
+
+    let mut assigned = Vec::new();
+    for name in assigned_identifiers(line) {
+        let scope = &mut v8::TryCatch::new(scope);
+
+        if let Some(variable) = read_top_level_variable(scope, &name, lexical_names) {
+            assigned.push(NamedValueSnapshot {
+                name,
+                value: snapshot_value(scope, variable),
+            });
+        }
+    }
+
+    LineValueSnapshot {
+        line_number,
+        value,
+        assigned,
+    }
+}
+
+/// Reads a variable declared at the top level of the script without running a getter or a proxy trap.
+/// A lexical binding is read by its name and a `var` or an implicit global through the own data property
+/// of the global object; the accessor properties are skipped.
+///
+/// # Arguments
+/// * `scope` - The v8 scope.
+/// * `name` - The name of the variable.
+/// * `lexical_names` - The names declared with `let`, `const` or `class` at the top level of the script.
+///
+/// # Returns
+/// The value of the variable; `None` if the variable is not a top-level variable or is not readable.
+fn read_top_level_variable<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    name: &str,
+    lexical_names: &[String],
+) -> Option<v8::Local<'s, v8::Value>> {
+    let key = v8::String::new(scope, name)?;
+
+    // The lexical bindings shadow the properties of the global object. Reading a binding runs no other
+    // code; a binding not yet initialized throws.
+    if lexical_names
+        .iter()
+        .any(|lexical_name| lexical_name == name)
+    {
+        let script = v8::Script::compile(scope, key, None)?;
+        return script.run(scope);
+    }
+
+    let global = scope.get_current_context().global(scope);
+    let descriptor = global.get_own_property_descriptor(scope, key.into())?;
+    // The descriptor is undefined if the global object has no such own property.
+    let descriptor = v8::Local::<v8::Object>::try_from(descriptor).ok()?;
+
+    let value_key = v8::String::new(scope, "value")?;
+    if !descriptor.has_own_property(scope, value_key.into())? {
+        return None;
+    }
+
+    descriptor.get(scope, value_key.into())
+}
@@ -0,0 +1,301 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use jsts_engine::{Engine, LogLevel, RunOptions, ScriptLanguage};
+
+/// Gets the text results of a script run, the exception messages for the failed lines.
+fn result_texts(outcome: &jsts_engine::RunOutcome) -> Vec<String> {
+    outcome
+        .results
+        .iter()
+        .map(|result| match result {
+            Ok(value) => value.text.clone(),
+            Err(exception) => exception.message.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn runs_entire_code() {
+    let engine = Engine::new();
+    let outcome = engine.run("const a = 2;\na * 21", &RunOptions::default());
+
+    assert_eq!(result_texts(&outcome), vec!["42"]);
+    assert!(outcome.exception().is_none());
+}
+
+#[test]
+fn captures_console_calls() {
+    let engine = Engine::new();
+    let outcome = engine.run(
+        "console.log('a', 1);\nconsole.warn('b');\nconsole.error('c');",
+        &RunOptions::default(),
+    );
+
+    let levels: Vec<LogLevel> = outcome.logs.iter().map(|entry| entry.level).collect();
+    assert_eq!(levels, vec![LogLevel::Log, LogLevel::Warn, LogLevel::Error]);
+    assert_eq!(outcome.log_stack(), vec!["LOG: a 1", "WARN: b", "ERROR: c"]);
+}
+
+#[test]
+fn evaluates_line_by_line() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        evaluate_per_line: true,
+        capture_line_values: true,
+        ..Default::default()
+    };
+    let outcome = engine.run("let a = 1;\n\na += 2\nconsole.log(a)", &options);
+
+    assert_eq!(
+        result_texts(&outcome),
+        vec!["undefined", "", "3", "undefined"]
+    );
+
+    let log_stack_lines = outcome.log_stack_lines();
+    assert_eq!(log_stack_lines.len(), 1);
+    assert_eq!(log_stack_lines[0].line_number, 3);
+    assert_eq!(log_stack_lines[0].lines, vec!["LOG: 3"]);
+
+    let assigned: Vec<String> = outcome.line_value_snapshots[1]
+        .assigned
+        .iter()
+        .map(|value| format!("{}={}", value.name, value.value.preview))
+        .collect();
+    assert_eq!(outcome.line_value_snapshots[1].line_number, 2);
+    assert_eq!(assigned, vec!["a=3"]);
+}
+
+#[test]
+fn snapshots_only_top_level_variables() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        evaluate_per_line: true,
+        capture_line_values: true,
+        ..Default::default()
+    };
+    let outcome = engine.run(
+        "var count = 0; Object.defineProperty(globalThis, 'hits', { get() { return ++count; } });\n\
+         function f(x) { x = 5; return x; }\n\
+         hits = 1; var total = f(1);\n\
+         count",
+        &options,
+    );
+
+    let assigned = |line: usize| -> Vec<String> {
+        outcome.line_value_snapshots[line]
+            .assigned
+            .iter()
+            .map(|value| format!("{}={}", value.name, value.value.preview))
+            .collect()
+    };
+
+    assert!(assigned(1).is_empty());
+    assert_eq!(assigned(2), vec!["total=5"]);
+    // The getter of the accessor property is not run by the snapshots.
+    assert_eq!(result_texts(&outcome)[3], "0");
+}
+
+#[test]
+fn continues_after_failed_line() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        evaluate_per_line: true,
+        ..Default::default()
+    };
+    let outcome = engine.run("missing\n1 + 1", &options);
+
+    assert!(outcome.results[0].is_err());
+    assert_eq!(outcome.results[1].as_ref().unwrap().text, "2");
+}
+
+#[test]
+fn reports_exception_details() {
+    let engine = Engine::new();
+    let outcome = engine.run(
+        "let a = 1;\nthrow new Error('failed');",
+        &RunOptions::default(),
+    );
+
+    let exception = outcome.exception().expect("the script should throw");
+    assert_eq!(exception.message, "Error: failed");
+    assert_eq!(exception.line_number, Some(2));
+    assert_eq!(exception.column, Some(1));
+    assert_eq!(
+        exception.source_line.as_deref(),
+        Some("throw new Error('failed');")
+    );
+}
+
+#[test]
+fn reports_syntax_errors() {
+    let engine = Engine::new();
+    let outcome = engine.run("let = ;", &RunOptions::default());
+
+    let exception = outcome.exception().expect("the script should not compile");
+    assert!(exception.message.starts_with("SyntaxError"));
+}
+
+#[test]
+fn runs_typescript() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        language: ScriptLanguage::TypeScript,
+        ..Default::default()
+    };
+    let outcome = engine.run(
+        "interface Point { x: number }\nconst p: Point = { x: 5 };\np.x * 2",
+        &options,
+    );
+
+    assert_eq!(result_texts(&outcome), vec!["10"]);
+}
+
+#[test]
+fn runs_tsx() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        language: ScriptLanguage::Tsx,
+        ..Default::default()
+    };
+    let outcome = engine.run(
+        "const React = { createElement: (tag: string, _props: unknown, ...children: string[]) => `${tag}:${children.join('')}` };\n<b>{'bold'}</b>",
+        &options,
+    );
+
+    assert_eq!(result_texts(&outcome), vec!["b:bold"]);
+}
+
+#[test]
+fn session_keeps_variables_until_reset() {
+    let engine = Engine::new();
+    let mut session = engine.create_session();
+    let options = RunOptions::default();
+
+    session.run("let counter = 1;", &options);
+    let outcome = session.run("counter + 1", &options);
+    assert_eq!(result_texts(&outcome), vec!["2"]);
+
+    session.reset();
+    let outcome = session.run("typeof counter", &options);
+    assert_eq!(result_texts(&outcome), vec!["undefined"]);
+}
+
+#[test]
+fn collects_global_variables() {
+    let engine = Engine::new();
+    let mut session = engine.create_session();
+    let options = RunOptions {
+        collect_globals: true,
+        ..Default::default()
+    };
+    let outcome = session.run("var config = { name: 'test', items: [1, 2] };", &options);
+
+    assert_eq!(outcome.global_variables.len(), 1);
+    let config = &outcome.global_variables[0];
+    assert_eq!(config.name, "config");
+    assert_eq!(config.value_type, "object");
+
+    let handle = config.handle.expect("the object should be expandable");
+    let children: Vec<String> = session
+        .expand_global_variable(handle)
+        .expect("the handle should be valid")
+        .iter()
+        .map(|child| format!("{}={}", child.name, child.preview))
+        .collect();
+    assert_eq!(children, vec!["name=\"test\"", "items=Array(2)"]);
+}
+
+#[test]
+fn expands_deeply_nested_global_variables() {
+    let engine = Engine::new();
+    let mut session = engine.create_session();
+    let options = RunOptions {
+        collect_globals: true,
+        ..Default::default()
+    };
+    let outcome = session.run(
+        "var deep = {}; let node = deep; for (let i = 0; i < 20; i++) { node.next = {}; node = node.next; } node.value = 42;",
+        &options,
+    );
+
+    let deep = outcome
+        .global_variables
+        .iter()
+        .find(|variable| variable.name == "deep")
+        .expect("the variable should be collected");
+
+    let mut handle = deep.handle.expect("the object should be expandable");
+    for _ in 0..20 {
+        let children = session.expand_global_variable(handle).unwrap();
+        handle = children[0].handle.expect("the child should be expandable");
+    }
+
+    let children = session.expand_global_variable(handle).unwrap();
+    assert_eq!(children[0].name, "value");
+    assert_eq!(children[0].preview, "42");
+
+    // The handles of the previous run are no longer valid.
+    session.run("1", &options);
+    assert!(session.expand_global_variable(handle).is_none());
+}
+
+#[test]
+fn terminates_running_script() {
+    let engine = Engine::new();
+    let mut session = engine.create_session();
+    let handle = session.terminate_handle();
+
+    let terminator = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        handle.terminate();
+    });
+
+    let outcome = session.run("while (true) {}", &RunOptions::default());
+    terminator.join().unwrap();
+
+    assert!(outcome.terminated);
+
+    // The session is usable again after the termination.
+    let outcome = session.run("1 + 1", &RunOptions::default());
+    assert_eq!(result_texts(&outcome), vec!["2"]);
+}
+
+#[test]
+fn ignores_termination_after_completed_run_on_reset() {
+    let engine = Engine::new();
+    let mut session = engine.create_session();
+    let handle = session.terminate_handle();
+
+    let outcome = session.run("1 + 1", &RunOptions::default());
+    assert!(!outcome.terminated);
+
+    // The termination of a superseded run arrives after the run already completed.
+    handle.terminate();
+    session.reset();
+
+    let outcome = session.run("typeof describe", &RunOptions::default());
+    assert!(!outcome.terminated);
+    assert_eq!(result_texts(&outcome), vec!["function"]);
+}
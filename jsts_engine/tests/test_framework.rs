@@ -0,0 +1,212 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use jsts_engine::{Engine, RunOptions, Snapshots, TestStatus};
+
+#[test]
+fn runs_registered_tests() {
+    let engine = Engine::new();
+    let report = engine.run_tests(
+        r#"
+        const add = (a, b) => a + b;
+
+        describe("add", () => {
+            let calls = 0;
+            beforeEach(() => calls++);
+            afterEach(() => console.log("calls", calls));
+
+            it("adds numbers", () => {
+                expect(add(1, 2)).toBe(3);
+            });
+
+            test("adds objects", () => {
+                expect({ sum: add(1, 1) }).toEqual({ sum: 3 });
+            });
+
+            it.skip("is skipped", () => {});
+        });
+        "#,
+        &RunOptions::default(),
+    );
+
+    assert_eq!(report.passed, 1);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.skipped, 1);
+    assert!(!report.success());
+
+    let passed = &report.tests[0];
+    assert_eq!(passed.name, "adds numbers");
+    assert_eq!(passed.suite, vec!["add"]);
+    assert_eq!(passed.status, TestStatus::Passed);
+
+    let failure = report.tests[1]
+        .failure
+        .as_ref()
+        .expect("the test should fail");
+    assert_eq!(failure.matcher.as_deref(), Some("toEqual"));
+    let diff = failure
+        .diff
+        .as_deref()
+        .expect("the failure should have a diff");
+    assert!(diff.contains("-  sum: 3,"));
+    assert!(diff.contains("+  sum: 2,"));
+
+    let logs: Vec<String> = report
+        .logs
+        .iter()
+        .map(|entry| entry.message.clone())
+        .collect();
+    assert_eq!(logs, vec!["calls 1", "calls 2"]);
+}
+
+#[test]
+fn supports_negated_and_throwing_matchers() {
+    let engine = Engine::new();
+    let report = engine.run_tests(
+        r#"
+        test("matchers", () => {
+            expect(1).not.toBe(2);
+            expect([1, 2, 3]).toContain(2);
+            expect(() => { throw new TypeError("invalid"); }).toThrow(TypeError);
+            expect({ a: { b: 1 } }).toHaveProperty("a.b", 1);
+        });
+        "#,
+        &RunOptions::default(),
+    );
+
+    assert!(report.success());
+    assert_eq!(report.passed, 1);
+}
+
+#[test]
+fn fails_tests_throwing_null_or_undefined() {
+    let engine = Engine::new();
+    let report = engine.run_tests(
+        r#"
+        test("throws null", () => { throw null; });
+        test("throws undefined", () => { throw undefined; });
+        describe("hook", () => {
+            afterEach(() => { throw null; });
+            test("passes before the hook", () => {});
+        });
+        "#,
+        &RunOptions::default(),
+    );
+
+    assert_eq!(report.passed, 0);
+    assert_eq!(report.failed, 3);
+    assert!(report
+        .tests
+        .iter()
+        .all(|test| test.status == TestStatus::Failed && test.failure.is_some()));
+}
+
+#[test]
+fn reports_script_body_exception() {
+    let engine = Engine::new();
+    let report = engine.run_tests(
+        "test('before', () => {});\nthrow new Error('body failed');",
+        &RunOptions::default(),
+    );
+
+    assert_eq!(report.passed, 1);
+    let exception = report
+        .script_exception
+        .expect("the script body should throw");
+    assert_eq!(exception.message, "Error: body failed");
+}
+
+#[test]
+fn excludes_test_functions_from_globals() {
+    let engine = Engine::new();
+    let options = RunOptions {
+        collect_globals: true,
+        ..Default::default()
+    };
+    let outcome = engine.run("var value = 1;", &options);
+
+    let names: Vec<String> = outcome
+        .global_variables
+        .iter()
+        .map(|variable| variable.name.clone())
+        .collect();
+    assert_eq!(names, vec!["value"]);
+}
+
+#[test]
+fn matches_and_updates_snapshots() {
+    let engine = Engine::new();
+    let mut snapshots = Snapshots::default();
+    let code = |value: i32| {
+        format!(
+            "describe('transform', () => {{ it('maps', () => {{ expect({{ value: {} }}).toMatchSnapshot(); }}); }});",
+            value
+        )
+    };
+
+    let written =
+        engine.run_tests_with_snapshots(&code(1), &RunOptions::default(), &mut snapshots, false);
+    assert!(written.success());
+    assert_eq!(written.snapshots.written, 1);
+    assert_eq!(
+        snapshots
+            .snapshots
+            .get("transform maps 1")
+            .map(String::as_str),
+        Some("{\n  value: 1,\n}")
+    );
+
+    let matched =
+        engine.run_tests_with_snapshots(&code(1), &RunOptions::default(), &mut snapshots, false);
+    assert!(matched.success());
+    assert_eq!(matched.snapshots.matched, 1);
+
+    let mismatched =
+        engine.run_tests_with_snapshots(&code(2), &RunOptions::default(), &mut snapshots, false);
+    assert_eq!(mismatched.failed, 1);
+    let mismatch = &mismatched.snapshots.mismatched[0];
+    assert_eq!(mismatch.key, "transform maps 1");
+    assert!(mismatch.diff.contains("+  value: 2,"));
+
+    let updated =
+        engine.run_tests_with_snapshots(&code(2), &RunOptions::default(), &mut snapshots, true);
+    assert!(updated.success());
+    assert_eq!(updated.snapshots.updated, 1);
+    assert_eq!(
+        snapshots
+            .snapshots
+            .get("transform maps 1")
+            .map(String::as_str),
+        Some("{\n  value: 2,\n}")
+    );
+
+    let obsolete = engine.run_tests_with_snapshots(
+        "test('other', () => {});",
+        &RunOptions::default(),
+        &mut snapshots,
+        true,
+    );
+    assert_eq!(obsolete.snapshots.obsolete, vec!["transform maps 1"]);
+    assert!(snapshots.snapshots.is_empty());
+}
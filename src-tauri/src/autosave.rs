@@ -0,0 +1,367 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{collections::HashMap, fs, io, path::PathBuf, process, thread, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    config::{get_app_config, get_settings_dir},
+    file_save::save_file_atomically,
+    types::{AppState, FileTabData},
+    utils::hash_content,
+};
+
+/// The name of the file marking a running application in the recovery directory. The file is left
+/// behind by a crash or another unclean shutdown.
+const SESSION_FILE_NAME: &str = "session.lock";
+
+/// The name of the file of the unsaved tab contents in the recovery directory.
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+
+/// The time to wait before checking the autosave interval setting again while the autosave is disabled.
+const AUTOSAVE_DISABLED_POLL: Duration = Duration::from_secs(5);
+
+/// The unsaved tab contents snapshotted by the autosave.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecoverySnapshot {
+    /// The time the snapshot was taken.
+    pub saved_at: DateTime<Utc>,
+    /// The tabs with unsaved contents.
+    pub tabs: Vec<FileTabData>,
+}
+
+/// The editor content of a tab pushed by the frontend on each edit. The content is newer than the content
+/// of the open tabs until the frontend updates the open tabs.
+#[derive(Clone)]
+pub struct EditedContent {
+    /// The content in the editor.
+    pub content: String,
+    /// The time the content was edited.
+    pub edited_at: DateTime<Utc>,
+}
+
+/// Gets the recovery directory located next to the application settings.
+///
+/// # Returns
+/// The recovery directory; Error if the settings location is not available.
+pub fn recovery_dir() -> io::Result<PathBuf> {
+    match get_settings_dir() {
+        Ok(directory) => Ok(directory.join("recovery")),
+        Err(e) => Err(io::Error::new(io::ErrorKind::NotFound, e)),
+    }
+}
+
+/// Marks the application as running. The previous session ended uncleanly if its mark still exists.
+///
+/// # Returns
+/// `true` if the previous session ended uncleanly; Error if the mark could not be written.
+pub fn start_session() -> io::Result<bool> {
+    let directory = recovery_dir()?;
+    fs::create_dir_all(&directory)?;
+
+    let session_file = directory.join(SESSION_FILE_NAME);
+    let unclean_shutdown = session_file.exists();
+    fs::write(&session_file, process::id().to_string())?;
+
+    Ok(unclean_shutdown)
+}
+
+/// Marks the application as shut down cleanly and removes the snapshot of the unsaved tab contents,
+/// as the open tabs were saved into the open files state. The mark of another running instance is kept.
+pub fn end_session() {
+    let Ok(directory) = recovery_dir() else {
+        return;
+    };
+
+    let session_file = directory.join(SESSION_FILE_NAME);
+    if fs::read_to_string(&session_file).ok() == Some(process::id().to_string()) {
+        let _ = fs::remove_file(directory.join(SNAPSHOT_FILE_NAME));
+        let _ = fs::remove_file(session_file);
+    }
+}
+
+/// Reads the snapshot of the unsaved tab contents.
+///
+/// # Returns
+/// The snapshot; `None` if there is no snapshot or it could not be read.
+pub fn read_snapshot() -> Option<RecoverySnapshot> {
+    let contents = fs::read(recovery_dir().ok()?.join(SNAPSHOT_FILE_NAME)).ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+/// Removes the snapshot of the unsaved tab contents.
+///
+/// # Returns
+/// Ok if the snapshot was removed or did not exist; Error otherwise.
+pub fn remove_snapshot() -> io::Result<()> {
+    match fs::remove_file(recovery_dir()?.join(SNAPSHOT_FILE_NAME)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Checks whether a tab has contents not saved into its file, i.e. the tab is a non-empty temporary
+/// tab or the tab has been modified in the editor.
+///
+/// # Arguments
+/// * `tab` - The tab to check.
+///
+/// # Returns
+/// `true` if the tab has unsaved contents; `false` otherwise.
+pub fn has_unsaved_contents(tab: &FileTabData) -> bool {
+    match &tab.content {
+        Some(content) if !tab.read_only => {
+            if tab.is_temporary {
+                !content.is_empty()
+            } else {
+                tab.modified_at_state != tab.modified_at
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Gets the tabs with unsaved contents using the editor contents edited since the open tabs were last updated.
+///
+/// # Arguments
+/// * `tabs` - The open tabs.
+/// * `edited_contents` - The editor contents by the tab identifier.
+///
+/// # Returns
+/// The tabs with unsaved contents with their latest editor contents.
+pub fn unsaved_tabs(
+    tabs: &[FileTabData],
+    edited_contents: &HashMap<i32, EditedContent>,
+) -> Vec<FileTabData> {
+    tabs.iter()
+        .filter_map(|tab| {
+            let mut tab = tab.clone();
+            if let Some(edited) = edited_contents.get(&tab.uid) {
+                if tab.content.as_ref() != Some(&edited.content) {
+                    tab.content = Some(edited.content.clone());
+                    tab.modified_at_state = Some(edited.edited_at);
+                }
+            }
+
+            has_unsaved_contents(&tab).then_some(tab)
+        })
+        .collect()
+}
+
+/// Removes the tabs identical to the restored open tabs from a snapshot, as they have nothing to recover.
+///
+/// # Arguments
+/// * `snapshot` - The snapshot of the unsaved tab contents.
+/// * `tabs` - The restored open tabs.
+pub fn remove_restored_tabs(snapshot: &mut RecoverySnapshot, tabs: &[FileTabData]) {
+    snapshot.tabs.retain(|recovered| {
+        !tabs
+            .iter()
+            .any(|tab| tab.uid == recovered.uid && tab.content == recovered.content)
+    });
+}
+
+impl AppState {
+    /// Discards the editor content of a tab pushed by the frontend, e.g. when the content of the tab
+    /// is replaced by the backend.
+    ///
+    /// # Arguments
+    /// * `uid` - The identifier of the tab.
+    pub fn discard_edited_content(&self, uid: i32) {
+        self.lock_or_reset("edited_contents", &self.edited_contents)
+            .remove(&uid);
+    }
+}
+
+/// Starts the autosave snapshotting the unsaved tab contents into the recovery directory in the
+/// interval of the autosave interval setting. The snapshot is written only if the contents changed.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle to access the application state with.
+pub fn start_autosave(app_handle: AppHandle) {
+    thread::spawn(move || {
+        let mut snapshot_hash: Option<String> = None;
+
+        loop {
+            let interval = get_app_config().autosave_interval_seconds;
+            if interval == 0 {
+                thread::sleep(AUTOSAVE_DISABLED_POLL);
+                continue;
+            }
+
+            thread::sleep(Duration::from_secs(interval));
+
+            let app_state = app_handle.state::<AppState>();
+            if let Err(e) = snapshot_unsaved_tabs(&app_state, &mut snapshot_hash) {
+                eprintln!("{}", e);
+            }
+        }
+    });
+}
+
+/// Writes the snapshot of the unsaved tab contents into the recovery directory. The snapshot is removed
+/// if no tab has unsaved contents.
+///
+/// # Arguments
+/// * `app_state` - The application state.
+/// * `snapshot_hash` - The hash of the tabs of the last written snapshot, updated by the written snapshot.
+///
+/// # Returns
+/// Ok if the snapshot was written or was up to date; Error otherwise.
+fn snapshot_unsaved_tabs(
+    app_state: &AppState,
+    snapshot_hash: &mut Option<String>,
+) -> io::Result<()> {
+    // A recovery not yet restored or discarded is not overwritten.
+    if app_state
+        .lock_or_reset("recovery", &app_state.recovery)
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    // The open tabs are updated by the frontend only after a pause in the editing, so the editor contents
+    // pushed on each edit are newer.
+    let tabs = app_state.lock_file_tabs().clone();
+    let tabs = unsaved_tabs(
+        &tabs,
+        &app_state.lock_or_reset("edited_contents", &app_state.edited_contents),
+    );
+
+    if tabs.is_empty() {
+        if snapshot_hash.take().is_some() {
+            remove_snapshot()?;
+        }
+        return Ok(());
+    }
+
+    let hash = hash_content(&serde_json::to_vec(&tabs)?);
+    if snapshot_hash.as_ref() == Some(&hash) {
+        return Ok(());
+    }
+
+    let snapshot = RecoverySnapshot {
+        saved_at: Utc::now(),
+        tabs,
+    };
+
+    let directory = recovery_dir()?;
+    fs::create_dir_all(&directory)?;
+    save_file_atomically(
+        &directory.join(SNAPSHOT_FILE_NAME),
+        &serde_json::to_vec(&snapshot)?,
+        0,
+    )?;
+
+    *snapshot_hash = Some(hash);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn file_tab(uid: i32, content: &str, modified_at: DateTime<Utc>) -> FileTabData {
+        FileTabData {
+            uid,
+            path: Some("/scripts".to_string()),
+            file_name: format!("script{}.ts", uid),
+            file_name_path: Some(format!("/scripts/script{}.ts", uid)),
+            is_temporary: false,
+            script_language: "typescript".to_string(),
+            jsx: false,
+            module_kind: Default::default(),
+            content: Some(content.to_string()),
+            modified_at: Some(modified_at),
+            modified_at_state: Some(modified_at),
+            content_hash: None,
+            content_size: None,
+            history_id: None,
+            encoding: Default::default(),
+            line_ending: Default::default(),
+            read_only: false,
+            page: 0,
+            page_count: 0,
+            evalueate_per_line: false,
+        }
+    }
+
+    fn edited(content: &str, edited_at: DateTime<Utc>) -> EditedContent {
+        EditedContent {
+            content: content.to_string(),
+            edited_at,
+        }
+    }
+
+    #[test]
+    fn recovers_content_edited_after_the_saved_state() {
+        let modified_at = Utc::now() - TimeDelta::minutes(5);
+        let saved_tabs = vec![file_tab(1, "let a = 1;", modified_at)];
+        let edited_contents = HashMap::from([(1, edited("let a = 2;", Utc::now()))]);
+
+        let mut snapshot = RecoverySnapshot {
+            saved_at: Utc::now(),
+            tabs: unsaved_tabs(&saved_tabs, &edited_contents),
+        };
+
+        // The saved state restored at the start has the older content, so the snapshot is kept.
+        remove_restored_tabs(&mut snapshot, &saved_tabs);
+
+        assert_eq!(snapshot.tabs.len(), 1);
+        assert_eq!(snapshot.tabs[0].content.as_deref(), Some("let a = 2;"));
+        assert_ne!(snapshot.tabs[0].modified_at_state, Some(modified_at));
+    }
+
+    #[test]
+    fn skips_contents_already_in_the_saved_state() {
+        let modified_at = Utc::now() - TimeDelta::minutes(5);
+        let saved_tabs = vec![
+            file_tab(1, "let a = 1;", modified_at),
+            file_tab(2, "let b = 1;", modified_at),
+        ];
+        let edited_contents = HashMap::from([(1, edited("let a = 1;", Utc::now()))]);
+
+        assert!(unsaved_tabs(&saved_tabs, &edited_contents).is_empty());
+    }
+
+    #[test]
+    fn removes_tabs_identical_to_the_restored_tabs() {
+        let modified_at = Utc::now() - TimeDelta::minutes(5);
+        let mut unsaved = file_tab(1, "let a = 2;", modified_at);
+        unsaved.modified_at_state = Some(Utc::now());
+
+        let mut snapshot = RecoverySnapshot {
+            saved_at: Utc::now(),
+            tabs: vec![unsaved.clone()],
+        };
+        remove_restored_tabs(&mut snapshot, &[unsaved]);
+
+        assert!(snapshot.tabs.is_empty());
+    }
+}
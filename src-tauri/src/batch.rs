@@ -0,0 +1,566 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    language::language_by_extension,
+    report::{RunReport, RunStatus},
+};
+
+/// The format of an exported batch run report.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchReportFormat {
+    /// A JUnit XML report for the CI systems.
+    JUnit,
+    /// A Markdown table.
+    Markdown,
+}
+
+/// The result of a single script file of a batch run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BatchFileResult {
+    /// The name of the script file.
+    pub name: String,
+    /// The path of the script file.
+    pub file: String,
+    /// The status of the script run.
+    pub status: RunStatus,
+    /// The duration of the script run in milliseconds.
+    pub duration_ms: f64,
+    /// The exception message or the file error message if the script run failed.
+    pub error: Option<String>,
+    /// The stack trace of the exception if the script threw one.
+    pub stack: Option<String>,
+}
+
+impl BatchFileResult {
+    /// Creates the batch result of a script file from its script run report.
+    ///
+    /// # Arguments
+    /// * `file` - The script file.
+    /// * `report` - The script run report.
+    ///
+    /// # Returns
+    /// The batch result of the script file.
+    fn from_report(file: &Path, report: RunReport) -> Self {
+        let (error, stack) = match (report.error, report.exception) {
+            (Some(error), _) => (Some(error), None),
+            (None, Some(exception)) => (Some(exception.message), exception.stack),
+            (None, None) => (None, None),
+        };
+
+        Self {
+            name: file_name(file),
+            file: report.file,
+            status: report.status,
+            duration_ms: report.timings.total_ms,
+            error,
+            stack,
+        }
+    }
+
+    /// Checks if the script file ran successfully.
+    ///
+    /// # Returns
+    /// `true` if the script ran without an uncaught exception; `false` otherwise.
+    pub fn passed(&self) -> bool {
+        self.status == RunStatus::Success
+    }
+}
+
+/// The summary of a batch run of the script files in a directory.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BatchSummary {
+    /// The directory of the script files.
+    pub directory: String,
+    /// The time the batch run started.
+    pub started_at: DateTime<Utc>,
+    /// The duration of the batch run in milliseconds.
+    pub duration_ms: f64,
+    /// The number of the script files.
+    pub total: usize,
+    /// The number of the script files which ran successfully.
+    pub passed: usize,
+    /// The number of the script files which failed.
+    pub failed: usize,
+    /// The results of the script files in the file name order.
+    pub files: Vec<BatchFileResult>,
+}
+
+impl BatchSummary {
+    /// Formats the summary as a JUnit XML report with a test case for each script file.
+    ///
+    /// # Returns
+    /// The JUnit XML report.
+    pub fn to_junit_xml(&self) -> String {
+        let errors = self
+            .files
+            .iter()
+            .filter(|file| file.status == RunStatus::FileError)
+            .count();
+        let failures = self.failed - errors;
+        let name = xml_escape(&self.directory);
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"js_ts_runner\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+            self.total,
+            failures,
+            errors,
+            self.duration_ms / 1000.0
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\" timestamp=\"{}\">\n",
+            name,
+            self.total,
+            failures,
+            errors,
+            self.duration_ms / 1000.0,
+            self.started_at.format("%Y-%m-%dT%H:%M:%S")
+        ));
+
+        for file in &self.files {
+            let test_case = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" time=\"{:.3}\"",
+                xml_escape(&file.name),
+                name,
+                xml_escape(&file.file),
+                file.duration_ms / 1000.0
+            );
+
+            let element = match file.status {
+                RunStatus::FileError => "error",
+                RunStatus::Exception => "failure",
+                _ => {
+                    xml.push_str(&test_case);
+                    xml.push_str("/>\n");
+                    continue;
+                }
+            };
+
+            let message = file.error.clone().unwrap_or_default();
+            let details = file.stack.clone().unwrap_or_else(|| message.clone());
+
+            xml.push_str(&test_case);
+            xml.push_str(">\n");
+            xml.push_str(&format!(
+                "      <{} message=\"{}\">{}</{}>\n",
+                element,
+                xml_escape(&message),
+                xml_escape(&details),
+                element
+            ));
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+
+        xml
+    }
+
+    /// Formats the summary as a Markdown report with a table row for each script file.
+    ///
+    /// # Returns
+    /// The Markdown report.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str(&format!("# Batch run: {}\n\n", self.directory));
+        markdown.push_str(&format!(
+            "**{}** passed, **{}** failed, **{}** total in {:.1} ms ({}).\n\n",
+            self.passed,
+            self.failed,
+            self.total,
+            self.duration_ms,
+            self.started_at.to_rfc3339()
+        ));
+        markdown.push_str("| Status | File | Duration (ms) | Error |\n");
+        markdown.push_str("| --- | --- | ---: | --- |\n");
+
+        for file in &self.files {
+            let status = match file.status {
+                RunStatus::Success => "✅ Pass",
+                RunStatus::FileError => "⚠️ Error",
+                _ => "❌ Fail",
+            };
+
+            markdown.push_str(&format!(
+                "| {} | {} | {:.1} | {} |\n",
+                status,
+                markdown_escape(&file.name),
+                file.duration_ms,
+                markdown_escape(file.error.as_deref().unwrap_or(""))
+            ));
+        }
+
+        markdown
+    }
+
+    /// Formats the summary into the specified report format.
+    ///
+    /// # Arguments
+    /// * `format` - The report format.
+    ///
+    /// # Returns
+    /// The formatted report.
+    pub fn format(&self, format: BatchReportFormat) -> String {
+        match format {
+            BatchReportFormat::JUnit => self.to_junit_xml(),
+            BatchReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+}
+
+/// Gets the JavaScript and TypeScript files of a directory, e.g. `.js`, `.mjs`, `.ts` and `.tsx` files, in the file name order.
+/// The subdirectories are not included.
+///
+/// # Arguments
+/// * `directory` - The directory.
+///
+/// # Returns
+/// The script files of the directory; Error if the directory could not be read.
+pub fn batch_script_files(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| language_by_extension(path).is_some())
+        .collect();
+
+    files.sort();
+
+    Ok(files)
+}
+
+/// Runs the script files of a directory in parallel, each file in its own isolate.
+///
+/// # Arguments
+/// * `directory` - The directory of the script files.
+/// * `jobs` - The number of the worker threads; `None` to use the number of the available CPU cores.
+/// * `timeout` - The time after which the run of a single file is terminated; `None` for no limit.
+///
+/// # Returns
+/// The summary of the batch run; Error if the directory could not be read.
+pub fn run_batch(
+    directory: &Path,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<BatchSummary, String> {
+    let files = batch_script_files(directory)?;
+
+    let jobs = match jobs {
+        Some(jobs) => jobs,
+        None => thread::available_parallelism()
+            .map(|jobs| jobs.get())
+            .unwrap_or(1),
+    };
+
+    let started_at = Utc::now();
+    let started = Instant::now();
+
+    let next_index = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BatchFileResult>>> = Mutex::new(vec![None; files.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, files.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let file = match files.get(index) {
+                    Some(file) => file,
+                    None => break,
+                };
+
+                // The report run creates a new isolate on this worker thread for each file.
+                let result =
+                    BatchFileResult::from_report(file, RunReport::run_file(file, false, timeout));
+
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    let results = match results.into_inner() {
+        Ok(results) => results,
+        Err(e) => e.into_inner(),
+    };
+
+    let files: Vec<BatchFileResult> = results.into_iter().flatten().collect();
+    let passed = files.iter().filter(|file| file.passed()).count();
+
+    Ok(BatchSummary {
+        directory: directory.display().to_string(),
+        started_at,
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        total: files.len(),
+        passed,
+        failed: files.len() - passed,
+        files,
+    })
+}
+
+/// Gets the file name of a path.
+///
+/// # Arguments
+/// * `file` - The file path.
+///
+/// # Returns
+/// The file name; the entire path if the path has no file name.
+fn file_name(file: &Path) -> String {
+    match file.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => file.display().to_string(),
+    }
+}
+
+/// Escapes the XML special characters of a text.
+/// The whitespace control characters are written as character references to keep them in the attribute values,
+/// and the other control characters, e.g. the ANSI escapes of an exception message, are removed as they are not allowed in XML.
+///
+/// # Arguments
+/// * `text` - The text to escape.
+///
+/// # Returns
+/// The escaped text.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\u{FFFE}' | '\u{FFFF}' => {}
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escapes the text of a Markdown table cell.
+///
+/// # Arguments
+/// * `text` - The text to escape.
+///
+/// # Returns
+/// The escaped text on a single line.
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_result(
+        name: &str,
+        status: RunStatus,
+        error: Option<&str>,
+        stack: Option<&str>,
+    ) -> BatchFileResult {
+        BatchFileResult {
+            name: name.to_string(),
+            file: format!("/scripts/{}", name),
+            status,
+            duration_ms: 12.5,
+            error: error.map(|error| error.to_string()),
+            stack: stack.map(|stack| stack.to_string()),
+        }
+    }
+
+    fn summary(files: Vec<BatchFileResult>) -> BatchSummary {
+        let passed = files.iter().filter(|file| file.passed()).count();
+
+        BatchSummary {
+            directory: "/scripts/<a & b>".to_string(),
+            started_at: Utc::now(),
+            duration_ms: 100.0,
+            total: files.len(),
+            passed,
+            failed: files.len() - passed,
+            files,
+        }
+    }
+
+    /// Splits a Markdown table row into its cells at the unescaped pipes.
+    fn table_cells(row: &str) -> Vec<String> {
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut escaped = false;
+
+        for c in row
+            .trim()
+            .trim_start_matches('|')
+            .trim_end_matches('|')
+            .chars()
+        {
+            match c {
+                '|' if !escaped => cells.push(std::mem::take(&mut cell).trim().to_string()),
+                c => cell.push(c),
+            }
+            escaped = c == '\\';
+        }
+        cells.push(cell.trim().to_string());
+
+        cells
+    }
+
+    #[test]
+    fn junit_xml_round_trips_special_characters() {
+        let message = "Expected <b> & \"c\" to be 'd'\nat line 2";
+        let stack = "Error: <b> & c\r\n    at main (a&b.js:1:1)\tend";
+        let summary = summary(vec![
+            file_result("ok & <fine>.js", RunStatus::Success, None, None),
+            file_result(
+                "fail\"quoted\".ts",
+                RunStatus::Exception,
+                Some(message),
+                Some(stack),
+            ),
+            file_result(
+                "missing'.js",
+                RunStatus::FileError,
+                Some("No such file"),
+                None,
+            ),
+        ]);
+
+        let xml = summary.to_junit_xml();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+
+        let suite = document
+            .descendants()
+            .find(|node| node.has_tag_name("testsuite"))
+            .unwrap();
+        assert_eq!(suite.attribute("name"), Some("/scripts/<a & b>"));
+        assert_eq!(suite.attribute("failures"), Some("1"));
+        assert_eq!(suite.attribute("errors"), Some("1"));
+
+        let cases: Vec<_> = suite
+            .children()
+            .filter(|node| node.has_tag_name("testcase"))
+            .collect();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].attribute("name"), Some("ok & <fine>.js"));
+        assert_eq!(
+            cases[0].children().filter(|node| node.is_element()).count(),
+            0
+        );
+        assert_eq!(cases[1].attribute("name"), Some("fail\"quoted\".ts"));
+        assert_eq!(cases[2].attribute("file"), Some("/scripts/missing'.js"));
+
+        let failure = cases[1]
+            .children()
+            .find(|node| node.has_tag_name("failure"))
+            .unwrap();
+        assert_eq!(failure.attribute("message"), Some(message));
+        assert_eq!(failure.text(), Some(stack));
+
+        let error = cases[2]
+            .children()
+            .find(|node| node.has_tag_name("error"))
+            .unwrap();
+        assert_eq!(error.attribute("message"), Some("No such file"));
+        assert_eq!(error.text(), Some("No such file"));
+    }
+
+    #[test]
+    fn junit_xml_removes_invalid_control_characters() {
+        let summary = summary(vec![file_result(
+            "color.js",
+            RunStatus::Exception,
+            Some("\u{1b}[31mred\u{1b}[0m\u{0}"),
+            None,
+        )]);
+
+        let xml = summary.to_junit_xml();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+
+        let failure = document
+            .descendants()
+            .find(|node| node.has_tag_name("failure"))
+            .unwrap();
+        assert_eq!(failure.attribute("message"), Some("[31mred[0m"));
+    }
+
+    #[test]
+    fn markdown_escapes_table_cells() {
+        let summary = summary(vec![
+            file_result("a|b.js", RunStatus::Success, None, None),
+            file_result(
+                "c.ts",
+                RunStatus::Exception,
+                Some("x || y\r\nfailed | here"),
+                None,
+            ),
+        ]);
+
+        let markdown = summary.to_markdown();
+        let rows: Vec<&str> = markdown
+            .lines()
+            .skip_while(|line| !line.starts_with("| ---"))
+            .skip(1)
+            .collect();
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(
+            table_cells(rows[0]),
+            vec!["✅ Pass", "a\\|b.js", "12.5", ""]
+        );
+        assert_eq!(
+            table_cells(rows[1]),
+            vec!["❌ Fail", "c.ts", "12.5", "x \\|\\| y  failed \\| here"]
+        );
+    }
+}
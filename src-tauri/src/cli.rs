@@ -0,0 +1,428 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use jsts_engine::{LogEntry, LogLevel, ScriptException, ScriptLanguage};
+
+use crate::{
+    batch::{run_batch, BatchReportFormat, BatchSummary},
+    config::get_app_config,
+    language::{language_by_extension, language_by_shebang},
+    repl::run_repl,
+    report::{RunReport, RunStatus},
+    text_encoding::decode_text,
+};
+
+/// The exit code for a successful script run.
+pub(crate) const EXIT_SUCCESS: i32 = 0;
+/// The exit code for a script run which threw an exception.
+pub(crate) const EXIT_SCRIPT_EXCEPTION: i32 = 1;
+/// The exit code for a script file which could not be read or transpiled.
+pub(crate) const EXIT_FILE_ERROR: i32 = 2;
+
+/// The arguments which start the command line mode. Any other arguments start the GUI.
+const CLI_ARGUMENTS: [&str; 8] = [
+    "run",
+    "repl",
+    "batch",
+    "help",
+    "--help",
+    "-h",
+    "--version",
+    "-V",
+];
+
+/// Runs JavaScript or TypeScript files with the same engine as the GUI without opening a window.
+#[derive(Parser)]
+#[command(name = "js_ts_runner", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+/// The command line mode subcommands.
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Runs a JavaScript or TypeScript file and prints the logs and the result.
+    Run {
+        /// The JavaScript or TypeScript file to run, e.g. a `.js`, `.mjs`, `.ts` or `.tsx` file.
+        file: PathBuf,
+        /// The output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Evaluate each line separately instead of the entire file at once.
+        #[arg(long)]
+        per_line: bool,
+    },
+    /// Runs every JavaScript and TypeScript file of a directory in parallel and prints a summary.
+    Batch {
+        /// The directory of the script files.
+        directory: PathBuf,
+        /// The number of the files to run in parallel; defaults to the number of the CPU cores.
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Write a JUnit XML report into the specified file.
+        #[arg(long)]
+        junit: Option<PathBuf>,
+        /// Write a Markdown report into the specified file.
+        #[arg(long)]
+        markdown: Option<PathBuf>,
+        /// The output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// The time in seconds after which the run of a single file is terminated and the file fails.
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Starts an interactive REPL in the terminal.
+    Repl {
+        /// Treat the input as TypeScript.
+        #[arg(long)]
+        typescript: bool,
+    },
+}
+
+/// The output format of the script run.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// The logs and the result as plain text.
+    Text,
+    /// A versioned JSON report, see [RunReport].
+    Json,
+}
+
+/// Runs the command line mode if the process was started with command line mode arguments.
+///
+/// # Returns
+/// The process exit code; `None` if the GUI should be started instead.
+pub fn run_cli() -> Option<i32> {
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1) {
+        Some(argument) if CLI_ARGUMENTS.contains(&argument.as_str()) => {}
+        _ => return None,
+    }
+
+    attach_console();
+
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            // The help and the version are "errors" with a success exit code.
+            let _ = e.print();
+            return Some(e.exit_code());
+        }
+    };
+
+    rust_i18n::set_locale(&get_app_config().locale);
+
+    let exit_code = match cli.command {
+        CliCommand::Run {
+            file,
+            format,
+            per_line,
+        } => run_file(&file, format, per_line),
+        CliCommand::Batch {
+            directory,
+            jobs,
+            junit,
+            markdown,
+            format,
+            timeout,
+        } => run_batch_directory(
+            &directory,
+            jobs,
+            timeout.map(Duration::from_secs),
+            junit,
+            markdown,
+            format,
+        ),
+        CliCommand::Repl { typescript } => run_repl(typescript),
+    };
+
+    Some(exit_code)
+}
+
+/// Runs a script file printing the logs and the result to the standard output and
+/// the warnings, errors and the exception details to the standard error, or a JSON report
+/// to the standard output.
+///
+/// # Arguments
+/// * `file` - The JavaScript or TypeScript file to run.
+/// * `format` - The output format.
+/// * `per_line` - A flag indicating whether to evaluate each line separately.
+///
+/// # Returns
+/// The process exit code.
+fn run_file(file: &Path, format: OutputFormat, per_line: bool) -> i32 {
+    let report = RunReport::run_file(file, per_line, None);
+
+    match format {
+        OutputFormat::Text => print_text_report(file, &report),
+        OutputFormat::Json => print_report(&report),
+    }
+
+    report.exit_code
+}
+
+/// Prints a script run report as plain text, the logs and the results to the standard output and
+/// the warnings, errors and the exception details to the standard error.
+///
+/// # Arguments
+/// * `file` - The script file.
+/// * `report` - The script run report.
+fn print_text_report(file: &Path, report: &RunReport) {
+    if let Some(error) = &report.error {
+        eprintln!("{}: {}", file.display(), error);
+        return;
+    }
+
+    if !report.evaluate_per_line {
+        print_log_stack(&report.logs);
+
+        match (&report.result, &report.exception) {
+            // Skip the undefined result the same way as the GUI does by default.
+            (Some(value), _) if value.text != "undefined" => println!("{}", value.text),
+            (_, Some(exception)) => print_exception(Some(file), exception),
+            _ => {}
+        }
+        return;
+    }
+
+    for line in &report.lines {
+        let logs: Vec<LogEntry> = report
+            .logs
+            .iter()
+            .filter(|entry| entry.line_number == Some(line.line_number as i32))
+            .cloned()
+            .collect();
+        print_log_stack(&logs);
+
+        match (&line.result, &line.exception) {
+            // Skip the empty and undefined results the same way as the GUI does by default.
+            (Some(value), _) if !value.text.is_empty() && value.text != "undefined" => {
+                println!("{}: {}", line.line_number, value.text)
+            }
+            (_, Some(exception)) => print_exception(Some(file), exception),
+            _ => {}
+        }
+    }
+}
+
+/// Runs the script files of a directory in parallel and prints the summary, and optionally
+/// writes the summary into JUnit XML and Markdown report files.
+///
+/// # Arguments
+/// * `directory` - The directory of the script files.
+/// * `jobs` - The number of the files to run in parallel.
+/// * `timeout` - The time after which the run of a single file is terminated.
+/// * `junit` - The JUnit XML report file to write.
+/// * `markdown` - The Markdown report file to write.
+/// * `format` - The output format of the summary.
+///
+/// # Returns
+/// The process exit code; an error exit code if any of the files failed.
+fn run_batch_directory(
+    directory: &Path,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+    junit: Option<PathBuf>,
+    markdown: Option<PathBuf>,
+    format: OutputFormat,
+) -> i32 {
+    let summary = match run_batch(directory, jobs, timeout) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("{}: {}", directory.display(), e);
+            return EXIT_FILE_ERROR;
+        }
+    };
+
+    match format {
+        OutputFormat::Text => print_batch_summary(&summary),
+        OutputFormat::Json => match serde_json::to_string_pretty(&summary) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("{}", e),
+        },
+    }
+
+    let reports = [
+        (junit, BatchReportFormat::JUnit),
+        (markdown, BatchReportFormat::Markdown),
+    ];
+
+    for (file, report_format) in reports {
+        if let Some(file) = file {
+            if let Err(e) = fs::write(&file, summary.format(report_format)) {
+                eprintln!("{}: {}", file.display(), e);
+                return EXIT_FILE_ERROR;
+            }
+        }
+    }
+
+    if summary.failed > 0 {
+        EXIT_SCRIPT_EXCEPTION
+    } else {
+        EXIT_SUCCESS
+    }
+}
+
+/// Prints the summary of a batch run as plain text, a line for each file followed by the totals.
+///
+/// # Arguments
+/// * `summary` - The summary of the batch run.
+fn print_batch_summary(summary: &BatchSummary) {
+    for file in &summary.files {
+        let status = match file.status {
+            RunStatus::Success => "PASS",
+            RunStatus::FileError => "ERROR",
+            _ => "FAIL",
+        };
+
+        match &file.error {
+            Some(error) => println!(
+                "{} {} ({:.1} ms): {}",
+                status, file.name, file.duration_ms, error
+            ),
+            None => println!("{} {} ({:.1} ms)", status, file.name, file.duration_ms),
+        }
+    }
+
+    let passed = summary.passed.to_string();
+    let failed = summary.failed.to_string();
+    let total = summary.total.to_string();
+    let duration = format!("{:.1}", summary.duration_ms);
+
+    println!(
+        "{}",
+        t!(
+            "messages.batchRunSummary",
+            passed = passed,
+            failed = failed,
+            total = total,
+            duration = duration
+        )
+    );
+}
+
+/// Prints a script run report as JSON to the standard output.
+///
+/// # Arguments
+/// * `report` - The script run report.
+fn print_report(report: &RunReport) {
+    match serde_json::to_string_pretty(report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// Gets the language of a script file by its extension or, for the files without a known
+/// extension, by its shebang line; the files with neither are TypeScript.
+///
+/// # Arguments
+/// * `file` - The script file.
+///
+/// # Returns
+/// The language of the script file.
+pub(crate) fn script_language(file: &Path) -> ScriptLanguage {
+    language_by_extension(file)
+        .or_else(|| {
+            read_script_file(file)
+                .ok()
+                .and_then(|code| language_by_shebang(&code))
+        })
+        .unwrap_or_default()
+        .language
+}
+
+/// Reads a script file detecting its text encoding the same way as the editor does.
+///
+/// # Arguments
+/// * `file` - The script file.
+///
+/// # Returns
+/// The code of the file without a byte order mark and with `\n` line endings; Error if the file could not be read.
+pub(crate) fn read_script_file(file: &Path) -> Result<String, String> {
+    match fs::read(file) {
+        Ok(bytes) => Ok(decode_text(&bytes).text),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Prints the captured log calls, the logs to the standard output and the warnings and errors to the standard error.
+///
+/// # Arguments
+/// * `logs` - The captured log calls.
+pub(crate) fn print_log_stack(logs: &[LogEntry]) {
+    for entry in logs {
+        match entry.level {
+            LogLevel::Log => println!("{}", entry.message),
+            LogLevel::Warn | LogLevel::Error => eprintln!("{}", entry),
+        }
+    }
+}
+
+/// Prints the details of an uncaught script exception to the standard error.
+///
+/// # Arguments
+/// * `file` - The script file; `None` for the REPL input.
+/// * `exception` - The details of the exception.
+pub(crate) fn print_exception(file: Option<&Path>, exception: &ScriptException) {
+    if let (Some(file), Some(line_number), Some(column)) =
+        (file, exception.line_number, exception.column)
+    {
+        eprintln!("{}:{}:{}", file.display(), line_number, column);
+    }
+
+    if let (Some(source_line), Some(column)) = (&exception.source_line, exception.column) {
+        eprintln!("{}", source_line);
+        eprintln!("{}^", " ".repeat(column.saturating_sub(1)));
+    }
+
+    match &exception.stack {
+        Some(stack) => eprintln!("Uncaught {}", stack),
+        None => eprintln!("Uncaught {}", exception.message),
+    }
+}
+
+/// Attaches the console of the parent process as the release build uses the Windows subsystem
+/// without a console, which would discard the output of the command line mode.
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Attaches the console of the parent process; not needed outside of Windows.
+#[cfg(not(windows))]
+fn attach_console() {}
@@ -22,6 +22,9 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
 
 use crate::types::FileTabData;
@@ -43,6 +46,46 @@ pub struct AppConfig {
     skip_undefined_on_js: bool,
     /// A value indicating whether to skip empty result values on JavaScript / TypeScript evaluation.
     skip_empty_on_js: bool,
+    /// The time in milliseconds to wait for further content updates before evaluating the content in the live mode.
+    #[serde(default = "default_live_mode_debounce_ms")]
+    pub live_mode_debounce_ms: u64,
+    /// The number of the rotating backup copies of the previous contents to keep when saving a file; `0` to keep none.
+    #[serde(default = "default_save_backup_count")]
+    pub save_backup_count: usize,
+    /// The size limit in megabytes of the files opened for editing; the larger files can be opened read-only in pages.
+    #[serde(default = "default_file_size_limit_mb")]
+    pub file_size_limit_mb: u64,
+    /// The interval in seconds to snapshot the unsaved tab contents into the recovery directory; `0` to disable the autosave.
+    #[serde(default = "default_autosave_interval_seconds")]
+    pub autosave_interval_seconds: u64,
+    /// The size limit in megabytes of the local history of the saved and run file versions; the oldest versions are removed first.
+    #[serde(default = "default_history_size_limit_mb")]
+    pub history_size_limit_mb: u64,
+}
+
+/// The default value for the live mode debounce time in milliseconds.
+fn default_live_mode_debounce_ms() -> u64 {
+    300
+}
+
+/// The default value for the number of the backup copies of the saved files.
+fn default_save_backup_count() -> usize {
+    0
+}
+
+/// The default value for the size limit of the files opened for editing in megabytes.
+fn default_file_size_limit_mb() -> u64 {
+    10
+}
+
+/// The default value for the autosave interval of the unsaved tab contents in seconds.
+fn default_autosave_interval_seconds() -> u64 {
+    30
+}
+
+/// The default value for the size limit of the local history in megabytes.
+fn default_history_size_limit_mb() -> u64 {
+    50
 }
 
 // The default value for the application configuration.
@@ -56,6 +99,11 @@ impl ::std::default::Default for AppConfig {
             dark_mode: false,
             skip_undefined_on_js: true,
             skip_empty_on_js: true,
+            live_mode_debounce_ms: default_live_mode_debounce_ms(),
+            save_backup_count: default_save_backup_count(),
+            file_size_limit_mb: default_file_size_limit_mb(),
+            autosave_interval_seconds: default_autosave_interval_seconds(),
+            history_size_limit_mb: default_history_size_limit_mb(),
         }
     }
 }
@@ -80,6 +128,21 @@ pub struct FileState {
     pub file_ids: Vec<i32>,
     /// The identifier of the active tab.
     pub active_tab_id: Option<i32>,
+    /// The names and paths of the recently opened files, the most recent first.
+    #[serde(default)]
+    pub recent_files: Vec<String>,
+    /// The recently closed tabs, the most recently closed first.
+    #[serde(default)]
+    pub closed_tabs: Vec<FileTabData>,
+    /// The name of the workspace of the open tabs; `None` if the tabs are not saved as a workspace.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// The path of the open project folder; `None` if no folder is open.
+    #[serde(default)]
+    pub project_folder: Option<String>,
+    /// The layout of the project folder sidebar.
+    #[serde(default)]
+    pub sidebar: SidebarLayout,
 }
 
 // The default value for the file state.
@@ -89,10 +152,73 @@ impl ::std::default::Default for FileState {
             files: vec![],
             file_ids: vec![],
             active_tab_id: None,
+            recent_files: vec![],
+            closed_tabs: vec![],
+            workspace: None,
+            project_folder: None,
+            sidebar: SidebarLayout::default(),
+        }
+    }
+}
+
+/// The default width of the project folder sidebar in pixels.
+const DEFAULT_SIDEBAR_WIDTH: u32 = 250;
+
+/// The layout of the project folder sidebar.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct SidebarLayout {
+    /// A flag indicating whether the sidebar is visible while a project folder is open.
+    pub visible: bool,
+    /// The width of the sidebar in pixels.
+    pub width: u32,
+}
+
+// The default value for the project folder sidebar layout.
+impl ::std::default::Default for SidebarLayout {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            width: DEFAULT_SIDEBAR_WIDTH,
         }
     }
 }
 
+/// The layout of a named workspace restored when switching to the workspace.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorkspaceLayout {
+    /// The layout of the project folder sidebar.
+    pub sidebar: SidebarLayout,
+    /// The path of the project folder of the workspace; `None` if no folder was open.
+    pub project_folder: Option<String>,
+    /// The identifiers of the tabs in their order in the editor.
+    pub tab_order: Vec<i32>,
+}
+
+/// A named workspace of tabs with their run modes, order, the active tab and the layout.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Workspace {
+    /// The name of the workspace.
+    pub name: String,
+    /// The tabs of the workspace in their order.
+    pub files: Vec<FileTabData>,
+    /// The identifiers of the tabs of the workspace.
+    pub file_ids: Vec<i32>,
+    /// The identifier of the active tab of the workspace.
+    pub active_tab_id: Option<i32>,
+    /// The time the workspace was last saved.
+    pub saved_at: DateTime<Utc>,
+    /// The layout of the workspace; `None` if the workspace was saved without its layout.
+    #[serde(default)]
+    pub layout: Option<WorkspaceLayout>,
+}
+
+/// The named workspaces to be saved to a named settings file (`workspaces`).
+#[derive(Serialize, Deserialize, Default)]
+pub struct Workspaces {
+    /// The saved workspaces.
+    pub workspaces: Vec<Workspace>,
+}
+
 /// Gets the application config from a file or default if one doesn't exist.
 ///
 /// # Returns
@@ -108,6 +234,20 @@ pub fn get_app_config() -> AppConfig {
     result
 }
 
+/// Gets the directory of the settings files, e.g. for the application data stored next to the settings.
+///
+/// # Returns
+/// The settings directory; Error if the settings location is not available.
+pub fn get_settings_dir() -> Result<PathBuf, String> {
+    match confy::get_configuration_file_path("js_ts_runner", "state") {
+        Ok(path) => match path.parent() {
+            Some(directory) => Ok(directory.to_path_buf()),
+            None => Err(format!("{} has no parent directory", path.display())),
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 /// Gets the file state from a file or default if one doesn't exist.
 ///
 /// # Returns
@@ -139,6 +279,31 @@ pub fn save_file_state(file_state: FileState) -> Result<bool, String> {
     result
 }
 
+/// Gets the named workspaces from a file or default if one doesn't exist.
+///
+/// # Returns
+/// A Workspaces value
+pub fn get_workspaces() -> Result<Workspaces, String> {
+    match confy::load("js_ts_runner", "workspaces") {
+        Ok(v) => Ok(v),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Saves the named workspaces to a settings file using confy. The file format is TOML.
+/// # Arguments
+///
+/// * `workspaces` - the named workspaces value.
+///
+/// # Returns
+/// `true` if the workspaces were successfully saved; Error otherwise.
+pub fn save_workspaces(workspaces: Workspaces) -> Result<bool, String> {
+    match confy::store("js_ts_runner", "workspaces", workspaces) {
+        Ok(_) => Ok(true),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 /// Saves the application config to a settings file using confy. The file format is TOML.
 /// # Arguments
 ///
@@ -0,0 +1,512 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{collections::BTreeMap, fmt, io};
+
+use jsts_engine::ScriptException;
+use serde::{Deserialize, Serialize};
+
+use crate::text_encoding::TextEncoding;
+
+/// The machine-readable code of a command error for the frontend to react to.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The file does not exist.
+    FileNotFound,
+    /// The file is larger than the file size limit.
+    FileTooLarge,
+    /// The file could not be read.
+    FileReadFailed,
+    /// The file could not be written.
+    FileWriteFailed,
+    /// The directory could not be read.
+    DirectoryReadFailed,
+    /// The file name and path was not specified.
+    FilePathNotSpecified,
+    /// The file content was not specified.
+    FileContentNotSpecified,
+    /// The tab was not found in the application state.
+    TabNotFound,
+    /// The open files state could not be loaded.
+    StateLoadFailed,
+    /// The open files state could not be saved.
+    StateSaveFailed,
+    /// The script threw an uncaught exception.
+    ScriptException,
+    /// The script could not be run.
+    ScriptFailed,
+    /// The expandable global variable was not found from the last script run.
+    GlobalVariableNotFound,
+    /// The live mode could not be started.
+    LiveModeFailed,
+    /// The snapshot file could not be read.
+    SnapshotReadFailed,
+    /// The snapshot file could not be written.
+    SnapshotWriteFailed,
+    /// The background task of the command failed.
+    TaskFailed,
+    /// The merged content still has conflict markers.
+    MergeConflictsUnresolved,
+    /// The file changed in the file system after the merge was made.
+    FileChangedDuringMerge,
+    /// The file changed in the file system after it was loaded and saving would overwrite the changes.
+    SaveConflict,
+    /// The file contents could not be encoded with the text encoding of the file.
+    EncodingFailed,
+    /// The file is opened read-only in pages and cannot be modified.
+    FileReadOnly,
+    /// The workspace was not found from the saved workspaces.
+    WorkspaceNotFound,
+    /// A workspace with the name already exists.
+    WorkspaceExists,
+    /// The workspace name is empty.
+    WorkspaceNameEmpty,
+    /// The local history could not be read or written.
+    HistoryFailed,
+    /// The revision was not found from the local history.
+    RevisionNotFound,
+    /// The project folder was not found.
+    FolderNotFound,
+}
+
+/// The error of a Tauri command with a code, the parameters of the error and a localized message.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommandError {
+    /// The error code.
+    pub code: ErrorCode,
+    /// The parameters of the error, e.g. the file name.
+    pub params: BTreeMap<String, String>,
+    /// The localized error message.
+    pub message: String,
+}
+
+/// The result type of the Tauri commands.
+pub type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    /// Creates a new command error.
+    ///
+    /// # Arguments
+    /// * `code` - The error code.
+    /// * `message` - The localized error message.
+    /// * `params` - The parameters of the error.
+    ///
+    /// # Returns
+    /// The command error.
+    pub fn new(code: ErrorCode, message: impl Into<String>, params: &[(&str, String)]) -> Self {
+        Self {
+            code,
+            params: params
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error of a missing file.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    pub fn file_not_found(file: &str) -> Self {
+        Self::new(
+            ErrorCode::FileNotFound,
+            t!("messages.fileNotFound", file = file),
+            &[("file", file.to_string())],
+        )
+    }
+
+    /// Creates an error of a missing folder.
+    ///
+    /// # Arguments
+    /// * `folder` - The path of the folder.
+    pub fn folder_not_found(folder: &str) -> Self {
+        Self::new(
+            ErrorCode::FolderNotFound,
+            t!("messages.folderNotFound", folder = folder),
+            &[("folder", folder.to_string())],
+        )
+    }
+
+    /// Creates an error of a file exceeding the file size limit.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    /// * `size` - The size of the file in bytes.
+    /// * `limit` - The file size limit in bytes.
+    pub fn file_too_large(file: &str, size: u64, limit: u64) -> Self {
+        let limit_mb = (limit / 1_000_000).to_string();
+        Self::new(
+            ErrorCode::FileTooLarge,
+            t!("messages.fileTooLarge", file = file, limit = limit_mb),
+            &[
+                ("file", file.to_string()),
+                ("size", size.to_string()),
+                ("limit", limit.to_string()),
+            ],
+        )
+    }
+
+    /// Creates an error of a failed file read. A missing file is reported as [ErrorCode::FileNotFound].
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    /// * `error` - The IO error.
+    pub fn file_read_failed(file: &str, error: &io::Error) -> Self {
+        if error.kind() == io::ErrorKind::NotFound {
+            return Self::file_not_found(file);
+        }
+
+        let error = error.to_string();
+        Self::new(
+            ErrorCode::FileReadFailed,
+            t!("messages.fileReadFailed", file = file, error = error),
+            &[("file", file.to_string()), ("error", error)],
+        )
+    }
+
+    /// Creates an error of a failed file write.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    /// * `error` - The IO error.
+    pub fn file_write_failed(file: &str, error: &io::Error) -> Self {
+        let error = error.to_string();
+        Self::new(
+            ErrorCode::FileWriteFailed,
+            t!("messages.fileWriteFailed", file = file, error = error),
+            &[("file", file.to_string()), ("error", error)],
+        )
+    }
+
+    /// Creates an error of a failed directory read.
+    ///
+    /// # Arguments
+    /// * `directory` - The directory.
+    /// * `error` - The error message.
+    pub fn directory_read_failed(directory: &str, error: String) -> Self {
+        Self::new(
+            ErrorCode::DirectoryReadFailed,
+            t!(
+                "messages.directoryReadFailed",
+                directory = directory,
+                error = error
+            ),
+            &[("directory", directory.to_string()), ("error", error)],
+        )
+    }
+
+    /// Creates an error of an unspecified file name and path.
+    pub fn file_path_not_specified() -> Self {
+        Self::new(
+            ErrorCode::FilePathNotSpecified,
+            t!("messages.filePathNotSpecified"),
+            &[],
+        )
+    }
+
+    /// Creates an error of an unspecified file content.
+    pub fn file_content_not_specified() -> Self {
+        Self::new(
+            ErrorCode::FileContentNotSpecified,
+            t!("messages.fileContentNotSpecified"),
+            &[],
+        )
+    }
+
+    /// Creates an error of a tab missing from the application state.
+    ///
+    /// # Arguments
+    /// * `tab_id` - The identifier of the tab.
+    pub fn tab_not_found(tab_id: i32) -> Self {
+        Self::new(
+            ErrorCode::TabNotFound,
+            t!("messages.failedFindFileBackendState"),
+            &[("tab_id", tab_id.to_string())],
+        )
+    }
+
+    /// Creates an error of a failed open files state load.
+    ///
+    /// # Arguments
+    /// * `error` - The error message.
+    pub fn state_load_failed(error: String) -> Self {
+        Self::new(
+            ErrorCode::StateLoadFailed,
+            t!("messages.stateLoadFailed", error = error),
+            &[("error", error)],
+        )
+    }
+
+    /// Creates an error of a failed open files state save.
+    ///
+    /// # Arguments
+    /// * `error` - The error message.
+    pub fn state_save_failed(error: String) -> Self {
+        Self::new(
+            ErrorCode::StateSaveFailed,
+            t!("messages.stateSaveFailed", error = error),
+            &[("error", error)],
+        )
+    }
+
+    /// Creates an error of an uncaught script exception. The message is the exception message as is.
+    ///
+    /// # Arguments
+    /// * `exception` - The details of the exception.
+    pub fn script_exception(exception: &ScriptException) -> Self {
+        let mut params = vec![("exception", exception.message.clone())];
+        if let Some(line_number) = exception.line_number {
+            params.push(("line_number", line_number.to_string()));
+        }
+        if let Some(column) = exception.column {
+            params.push(("column", column.to_string()));
+        }
+
+        Self::new(
+            ErrorCode::ScriptException,
+            exception.message.clone(),
+            &params,
+        )
+    }
+
+    /// Creates an error of a script which could not be run.
+    pub fn script_failed() -> Self {
+        Self::new(ErrorCode::ScriptFailed, t!("messages.failedRunScript"), &[])
+    }
+
+    /// Creates an error of an expandable global variable missing from the last script run.
+    ///
+    /// # Arguments
+    /// * `handle` - The handle of the variable.
+    pub fn global_variable_not_found(handle: u32) -> Self {
+        Self::new(
+            ErrorCode::GlobalVariableNotFound,
+            t!("messages.globalVariableHandleNotFound"),
+            &[("handle", handle.to_string())],
+        )
+    }
+
+    /// Creates an error of a live mode which could not be started.
+    ///
+    /// # Arguments
+    /// * `tab_id` - The identifier of the tab.
+    pub fn live_mode_failed(tab_id: i32) -> Self {
+        Self::new(
+            ErrorCode::LiveModeFailed,
+            t!("messages.failedStartLiveMode"),
+            &[("tab_id", tab_id.to_string())],
+        )
+    }
+
+    /// Creates an error of a failed snapshot file read.
+    ///
+    /// # Arguments
+    /// * `file` - The snapshot file.
+    /// * `error` - The error message.
+    pub fn snapshot_read_failed(file: &str, error: String) -> Self {
+        Self::new(
+            ErrorCode::SnapshotReadFailed,
+            t!(
+                "messages.snapshotFileReadFailed",
+                file = file,
+                error = error
+            ),
+            &[("file", file.to_string()), ("error", error)],
+        )
+    }
+
+    /// Creates an error of a failed snapshot file write.
+    ///
+    /// # Arguments
+    /// * `file` - The snapshot file.
+    /// * `error` - The error message.
+    pub fn snapshot_write_failed(file: &str, error: String) -> Self {
+        Self::new(
+            ErrorCode::SnapshotWriteFailed,
+            t!(
+                "messages.snapshotFileWriteFailed",
+                file = file,
+                error = error
+            ),
+            &[("file", file.to_string()), ("error", error)],
+        )
+    }
+
+    /// Creates an error of a failed background task.
+    ///
+    /// # Arguments
+    /// * `error` - The error message.
+    pub fn task_failed(error: String) -> Self {
+        Self::new(
+            ErrorCode::TaskFailed,
+            t!("messages.taskFailed", error = error),
+            &[("error", error)],
+        )
+    }
+
+    /// Creates an error of a merged content with unresolved conflicts.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    /// * `count` - The number of the unresolved conflicts.
+    pub fn merge_conflicts_unresolved(file: &str, count: usize) -> Self {
+        let count = count.to_string();
+        Self::new(
+            ErrorCode::MergeConflictsUnresolved,
+            t!(
+                "messages.mergeConflictsUnresolved",
+                file = file,
+                count = count
+            ),
+            &[("file", file.to_string()), ("count", count)],
+        )
+    }
+
+    /// Creates an error of a file changed in the file system after the merge was made.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    pub fn file_changed_during_merge(file: &str) -> Self {
+        Self::new(
+            ErrorCode::FileChangedDuringMerge,
+            t!("messages.fileChangedDuringMerge", file = file),
+            &[("file", file.to_string())],
+        )
+    }
+
+    /// Creates an error of a file changed in the file system after it was loaded.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    pub fn save_conflict(file: &str) -> Self {
+        Self::new(
+            ErrorCode::SaveConflict,
+            t!("messages.saveConflict", file = file),
+            &[("file", file.to_string())],
+        )
+    }
+
+    /// Creates an error of file contents having a character the text encoding of the file cannot represent.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    /// * `encoding` - The text encoding of the file.
+    /// * `character` - The first character the encoding cannot represent.
+    pub fn encoding_failed(file: &str, encoding: TextEncoding, character: char) -> Self {
+        let encoding = encoding.to_string();
+        let character = character.to_string();
+        Self::new(
+            ErrorCode::EncodingFailed,
+            t!(
+                "messages.encodingFailed",
+                file = file,
+                encoding = encoding,
+                character = character
+            ),
+            &[
+                ("file", file.to_string()),
+                ("encoding", encoding),
+                ("character", character),
+            ],
+        )
+    }
+
+    /// Creates an error of modifying a file opened read-only in pages.
+    ///
+    /// # Arguments
+    /// * `file` - The file name and path.
+    pub fn file_read_only(file: &str) -> Self {
+        Self::new(
+            ErrorCode::FileReadOnly,
+            t!("messages.fileReadOnly", file = file),
+            &[("file", file.to_string())],
+        )
+    }
+
+    /// Creates an error of a workspace not found from the saved workspaces.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the workspace.
+    pub fn workspace_not_found(name: &str) -> Self {
+        Self::new(
+            ErrorCode::WorkspaceNotFound,
+            t!("messages.workspaceNotFound", name = name),
+            &[("name", name.to_string())],
+        )
+    }
+
+    /// Creates an error of a workspace name already in use.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the workspace.
+    pub fn workspace_exists(name: &str) -> Self {
+        Self::new(
+            ErrorCode::WorkspaceExists,
+            t!("messages.workspaceExists", name = name),
+            &[("name", name.to_string())],
+        )
+    }
+
+    /// Creates an error of a failed local history read or write.
+    ///
+    /// # Arguments
+    /// * `error` - The error message.
+    pub fn history_failed(error: String) -> Self {
+        Self::new(
+            ErrorCode::HistoryFailed,
+            t!("messages.historyFailed", error = error),
+            &[("error", error)],
+        )
+    }
+
+    /// Creates an error of a revision not found from the local history.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier of the revision.
+    pub fn revision_not_found(id: u64) -> Self {
+        Self::new(
+            ErrorCode::RevisionNotFound,
+            t!("messages.revisionNotFound", id = id),
+            &[("id", id.to_string())],
+        )
+    }
+
+    /// Creates an error of an empty workspace name.
+    pub fn workspace_name_empty() -> Self {
+        Self::new(
+            ErrorCode::WorkspaceNameEmpty,
+            t!("messages.workspaceNameEmpty"),
+            &[],
+        )
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
@@ -0,0 +1,146 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::text_encoding::{decode_text, decode_text_with, TextEncoding};
+
+/// The size of a page of a file opened read-only in pages in bytes. The pages are extended to
+/// the next line start, so the pages are about this size.
+pub const FILE_PAGE_SIZE: u64 = 1_000_000;
+
+/// A page of a file opened read-only in pages.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FilePage {
+    /// The decoded text of the page with the line feed line endings.
+    pub content: String,
+    /// The zero-based number of the page.
+    pub page: u64,
+    /// The number of the pages of the file.
+    pub page_count: u64,
+    /// The text encoding of the file.
+    pub encoding: TextEncoding,
+}
+
+/// Reads a page of a file. The pages start and end at the line starts unless a line is longer than
+/// a page, so the lines are not split between the pages.
+///
+/// # Arguments
+/// * `path` - The file to read.
+/// * `page` - The zero-based number of the page to read; the last page if out of range.
+/// * `encoding` - The text encoding of the file; `None` to detect it from the first page.
+///
+/// # Returns
+/// The page; Error if the file could not be read.
+pub fn read_file_page(
+    path: &Path,
+    page: u64,
+    encoding: Option<TextEncoding>,
+) -> io::Result<FilePage> {
+    let mut file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let page_count = file_size.div_ceil(FILE_PAGE_SIZE).max(1);
+    let page = page.min(page_count - 1);
+
+    let encoding = match encoding {
+        Some(encoding) => encoding,
+        None => {
+            let mut first_page = vec![];
+            (&mut file)
+                .take(FILE_PAGE_SIZE)
+                .read_to_end(&mut first_page)?;
+
+            // Detect from the complete lines so that a character split at the page end is not invalid UTF-8.
+            let lines_end = first_page
+                .iter()
+                .rposition(|byte| *byte == b'\n')
+                .map_or(first_page.len(), |position| position + 1);
+            decode_text(&first_page[..lines_end]).encoding
+        }
+    };
+
+    let start = line_start_at(&mut file, page * FILE_PAGE_SIZE, encoding)?;
+    let end = if page + 1 >= page_count {
+        file_size
+    } else {
+        line_start_at(&mut file, (page + 1) * FILE_PAGE_SIZE, encoding)?
+    };
+
+    let mut bytes = vec![];
+    if end > start {
+        file.seek(SeekFrom::Start(start))?;
+        (&mut file).take(end - start).read_to_end(&mut bytes)?;
+    }
+
+    Ok(FilePage {
+        content: decode_text_with(&bytes, encoding).text,
+        page,
+        page_count,
+        encoding,
+    })
+}
+
+/// Finds the start of the first line at or after a byte offset of a file.
+///
+/// # Arguments
+/// * `file` - The file.
+/// * `offset` - The byte offset.
+/// * `encoding` - The text encoding of the file.
+///
+/// # Returns
+/// The offset of the line start; the offset itself if it is the file start or no line starts within a page after it.
+fn line_start_at(file: &mut File, offset: u64, encoding: TextEncoding) -> io::Result<u64> {
+    let line_feed: &[u8] = match encoding {
+        TextEncoding::Utf16Le => &[0x0A, 0x00],
+        TextEncoding::Utf16Be => &[0x00, 0x0A],
+        _ => &[0x0A],
+    };
+
+    // The UTF-16 code units start at the even offsets.
+    let unit = line_feed.len() as u64;
+    let offset = offset - offset % unit;
+    if offset == 0 {
+        return Ok(0);
+    }
+
+    // The line feed just before the offset makes the offset a line start.
+    file.seek(SeekFrom::Start(offset - unit))?;
+    let mut buffer = vec![];
+    file.take(FILE_PAGE_SIZE + unit).read_to_end(&mut buffer)?;
+
+    let position = buffer
+        .chunks_exact(line_feed.len())
+        .position(|chunk| chunk == line_feed);
+
+    Ok(match position {
+        Some(position) => offset + position as u64 * unit,
+        None => offset,
+    })
+}
@@ -0,0 +1,310 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The file extension of the backup copies of the saved files.
+const BACKUP_FILE_EXTENSION: &str = "bak";
+
+/// Saves the contents into a file so that the file has either the old or the new contents even if the
+/// application crashes or the disk gets full mid-write. The contents are written into a temporary file
+/// in the same directory, flushed to the disk and then renamed over the file. The permissions of an
+/// existing file are preserved and a symbolic link is followed to the file it points to.
+///
+/// # Arguments
+/// * `path` - The file to save.
+/// * `contents` - The contents to save.
+/// * `backup_count` - The number of the rotating backup copies of the previous contents to keep; `0` to keep none.
+///
+/// # Returns
+/// Ok if the file was saved; Error otherwise.
+pub fn save_file_atomically(path: &Path, contents: &[u8], backup_count: usize) -> io::Result<()> {
+    let path = match fs::canonicalize(path) {
+        Ok(path) => path,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => path.to_path_buf(),
+        Err(e) => return Err(e),
+    };
+
+    let directory = match path.parent() {
+        Some(directory) if !directory.as_os_str().is_empty() => directory.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let permissions = match fs::metadata(&path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    // Only the previous contents of an existing file are backed up.
+    let exists = permissions.is_some();
+    let (temp_path, mut temp_file) = create_temp_file(&directory, &path)?;
+
+    let written = write_temp_file(&mut temp_file, contents, permissions);
+    drop(temp_file);
+
+    let saved = written
+        .and_then(|_| {
+            if backup_count > 0 && exists {
+                rotate_backups(&path, backup_count)
+            } else {
+                Ok(())
+            }
+        })
+        .and_then(|_| fs::rename(&temp_path, &path));
+
+    if let Err(e) = saved {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    sync_directory(&directory);
+
+    Ok(())
+}
+
+/// Gets the path of a backup copy of a file.
+///
+/// # Arguments
+/// * `path` - The file.
+/// * `number` - The number of the backup copy; `1` is the newest.
+///
+/// # Returns
+/// The path of the backup copy, e.g. `script.ts.1.bak`.
+fn backup_path(path: &Path, number: usize) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}.{}", number, BACKUP_FILE_EXTENSION));
+    path.with_file_name(file_name)
+}
+
+/// Creates a new temporary file next to the file to save.
+///
+/// # Arguments
+/// * `directory` - The directory of the file.
+/// * `path` - The file to save.
+///
+/// # Returns
+/// The path and the handle of the temporary file; Error if the file could not be created.
+fn create_temp_file(directory: &Path, path: &Path) -> io::Result<(PathBuf, File)> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+
+    let mut attempt = 0;
+    loop {
+        let temp_path = directory.join(format!(
+            ".{}.{}.{}.tmp",
+            file_name,
+            process::id(),
+            nanos.wrapping_add(attempt)
+        ));
+
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+        {
+            Ok(file) => return Ok((temp_path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Writes the contents into the temporary file and flushes them to the disk.
+///
+/// # Arguments
+/// * `file` - The temporary file.
+/// * `contents` - The contents to write.
+/// * `permissions` - The permissions of the existing file to preserve.
+///
+/// # Returns
+/// Ok if the contents were written; Error otherwise.
+fn write_temp_file(
+    file: &mut File,
+    contents: &[u8],
+    permissions: Option<fs::Permissions>,
+) -> io::Result<()> {
+    file.write_all(contents)?;
+    if let Some(permissions) = permissions {
+        file.set_permissions(permissions)?;
+    }
+    file.sync_all()
+}
+
+/// Shifts the backup copies of a file by one, dropping the oldest, and copies the file as the newest backup copy.
+///
+/// # Arguments
+/// * `path` - The file.
+/// * `backup_count` - The number of the backup copies to keep.
+///
+/// # Returns
+/// Ok if the backup copies were rotated; Error otherwise.
+fn rotate_backups(path: &Path, backup_count: usize) -> io::Result<()> {
+    if let Err(e) = fs::remove_file(backup_path(path, backup_count)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e);
+        }
+    }
+
+    for number in (1..backup_count).rev() {
+        if let Err(e) = fs::rename(backup_path(path, number), backup_path(path, number + 1)) {
+            if e.kind() != io::ErrorKind::NotFound {
+                return Err(e);
+            }
+        }
+    }
+
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+/// Flushes the renamed directory entry to the disk. Only supported on Unix; the errors are ignored as the
+/// file contents are already on the disk.
+///
+/// # Arguments
+/// * `directory` - The directory of the saved file.
+#[cfg(unix)]
+fn sync_directory(directory: &Path) {
+    if let Ok(directory) = File::open(directory) {
+        let _ = directory.sync_all();
+    }
+}
+
+/// Flushes the renamed directory entry to the disk. Only supported on Unix.
+///
+/// # Arguments
+/// * `directory` - The directory of the saved file.
+#[cfg(not(unix))]
+fn sync_directory(_directory: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TestDir;
+
+    /// Gets the names of the files in a directory sorted by the name.
+    fn file_names(directory: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn overwrites_existing_file() {
+        let dir = TestDir::new("save_overwrite");
+        let path = dir.path.join("script.ts");
+        fs::write(&path, "old").unwrap();
+
+        save_file_atomically(&path, b"new", 0).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        // The temporary file was renamed over the file.
+        assert_eq!(file_names(&dir.path), vec!["script.ts"]);
+    }
+
+    #[test]
+    fn creates_new_file_without_backup() {
+        let dir = TestDir::new("save_new");
+        let path = dir.path.join("script.ts");
+
+        save_file_atomically(&path, b"new", 2).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(file_names(&dir.path), vec!["script.ts"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TestDir::new("save_permissions");
+        let path = dir.path.join("script.sh");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+
+        save_file_atomically(&path, b"new", 0).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750);
+    }
+
+    #[test]
+    fn keeps_no_backups_with_zero_count() {
+        let dir = TestDir::new("save_no_backups");
+        let path = dir.path.join("script.ts");
+        fs::write(&path, "v1").unwrap();
+
+        save_file_atomically(&path, b"v2", 0).unwrap();
+        save_file_atomically(&path, b"v3", 0).unwrap();
+
+        assert_eq!(file_names(&dir.path), vec!["script.ts"]);
+    }
+
+    #[test]
+    fn rotates_backups_past_the_limit() {
+        let dir = TestDir::new("save_rotate");
+        let path = dir.path.join("script.ts");
+        fs::write(&path, "v1").unwrap();
+
+        for contents in ["v2", "v3", "v4"] {
+            save_file_atomically(&path, contents.as_bytes(), 2).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v4");
+        assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "v3");
+        assert_eq!(fs::read_to_string(backup_path(&path, 2)).unwrap(), "v2");
+        assert_eq!(
+            file_names(&dir.path),
+            vec!["script.ts", "script.ts.1.bak", "script.ts.2.bak"]
+        );
+    }
+
+    #[test]
+    fn keeps_file_on_failed_save() {
+        let dir = TestDir::new("save_failed");
+        let path = dir.path.join("script.ts");
+        fs::write(&path, "old").unwrap();
+        // A directory in place of the backup copy fails the backup rotation.
+        fs::create_dir(backup_path(&path, 1)).unwrap();
+
+        assert!(save_file_atomically(&path, b"new", 1).is_err());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        // The temporary file was removed.
+        assert_eq!(file_names(&dir.path), vec!["script.ts", "script.ts.1.bak"]);
+    }
+}
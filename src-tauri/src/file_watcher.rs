@@ -0,0 +1,362 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    types::AppState,
+    utils::{hash_content, is_unchanged_by_metadata},
+};
+
+/// The name of the event emitted to the frontend when an open file changed in the file system.
+pub const FILE_CHANGED_EVENT: &str = "file-changed";
+
+/// The time to wait for further file system events before handling them. The own file saves of the
+/// application update the file state within this time, so they are not reported as changes.
+const FILE_EVENT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The kind of a change of an open file in the file system.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    /// The file contents were modified.
+    Modified,
+    /// The file was removed.
+    Removed,
+    /// The file was renamed or moved.
+    Renamed,
+}
+
+/// A change of an open file in the file system emitted to the frontend.
+#[derive(Serialize, Clone, Debug)]
+pub struct FileChange {
+    /// The identifier of the tab of the file.
+    pub tab_id: i32,
+    /// The name and path of the file.
+    pub file_name_path: String,
+    /// The kind of the change.
+    pub kind: FileChangeKind,
+    /// The new name and path of the file if the file was renamed.
+    pub new_file_name_path: Option<String>,
+    /// The new last modified date of the file if the file was modified.
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// A file system watcher over the files of the non-temporary tabs. The parent directories of the files
+/// are watched instead of the files themselves so that the files replaced by a rename, as many
+/// editors save, stay watched.
+pub struct FileWatcher {
+    /// The native file system watcher.
+    watcher: RecommendedWatcher,
+    /// The watched files.
+    files: HashSet<PathBuf>,
+    /// The number of the watched files by the watched directory.
+    directories: HashMap<PathBuf, usize>,
+}
+
+impl FileWatcher {
+    /// Starts a new file watcher with its own event handler thread.
+    ///
+    /// # Arguments
+    /// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+    ///
+    /// # Returns
+    /// The file watcher; Error if the native file system watcher could not be created.
+    pub fn start(app_handle: AppHandle) -> notify::Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Event>();
+
+        let watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                let _ = sender.send(event);
+            }
+        })?;
+
+        thread::spawn(move || file_event_loop(app_handle, receiver));
+
+        Ok(Self {
+            watcher,
+            files: HashSet::new(),
+            directories: HashMap::new(),
+        })
+    }
+
+    /// Updates the watched files to match the specified files.
+    ///
+    /// # Arguments
+    /// * `files` - The files to watch.
+    pub fn sync(&mut self, files: HashSet<PathBuf>) {
+        let removed: Vec<PathBuf> = self.files.difference(&files).cloned().collect();
+        let added: Vec<PathBuf> = files.difference(&self.files).cloned().collect();
+
+        for file in removed {
+            let Some(directory) = file.parent() else {
+                continue;
+            };
+
+            if let Some(count) = self.directories.get_mut(directory) {
+                *count -= 1;
+                if *count == 0 {
+                    let _ = self.watcher.unwatch(directory);
+                    self.directories.remove(directory);
+                }
+            }
+        }
+
+        for file in added {
+            let Some(directory) = file.parent() else {
+                continue;
+            };
+
+            match self.directories.get_mut(directory) {
+                Some(count) => *count += 1,
+                None => {
+                    if let Err(e) = self.watcher.watch(directory, RecursiveMode::NonRecursive) {
+                        eprintln!("{}: {}", directory.display(), e);
+                        continue;
+                    }
+                    self.directories.insert(directory.to_path_buf(), 1);
+                }
+            }
+        }
+
+        self.files = files;
+    }
+}
+
+impl AppState {
+    /// Updates the file watcher to watch the files of the non-temporary tabs.
+    pub fn sync_file_watcher(&self) {
+        let files: HashSet<PathBuf> = self
+            .lock_file_tabs()
+            .iter()
+            .filter(|tab| !tab.is_temporary)
+            .filter_map(|tab| tab.file_name_path.as_ref().map(PathBuf::from))
+            .collect();
+
+        if let Some(file_watcher) = self
+            .lock_or_reset("file_watcher", &self.file_watcher)
+            .as_mut()
+        {
+            file_watcher.sync(files);
+        }
+    }
+}
+
+/// Collects the file system events until no more events arrive within the debounce time and then handles them.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+/// * `receiver` - The receiver for the file system events.
+fn file_event_loop(app_handle: AppHandle, receiver: Receiver<Event>) {
+    // Wait for the first event of a batch without a timeout.
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+
+        loop {
+            match receiver.recv_timeout(FILE_EVENT_DEBOUNCE) {
+                Ok(event) => events.push(event),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    handle_file_events(&app_handle, events);
+                    return;
+                }
+            }
+        }
+
+        handle_file_events(&app_handle, events);
+    }
+}
+
+/// Updates the last modified dates of the tabs of the changed files and emits the changes to the frontend.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+/// * `events` - The file system events to handle.
+fn handle_file_events(app_handle: &AppHandle, events: Vec<Event>) {
+    let mut paths: HashSet<PathBuf> = HashSet::new();
+    let mut renames: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+    for event in events {
+        if let (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) =
+            (event.kind, event.paths.as_slice())
+        {
+            renames.insert(from.clone(), to.clone());
+        }
+        paths.extend(event.paths);
+    }
+
+    let app_state = app_handle.state::<AppState>();
+
+    // The files are read and hashed without holding the tabs lock.
+    let watched: Vec<WatchedTab> = app_state
+        .lock_file_tabs()
+        .iter()
+        .filter(|tab| !tab.is_temporary)
+        .filter_map(|tab| {
+            let file_name_path = tab.file_name_path.clone()?;
+            paths
+                .contains(Path::new(&file_name_path))
+                .then(|| WatchedTab {
+                    uid: tab.uid,
+                    file_name_path,
+                    modified_at: tab.modified_at,
+                    content_hash: tab.content_hash.clone(),
+                    content_size: tab.content_size,
+                })
+        })
+        .collect();
+
+    let mut updates = vec![];
+
+    for tab in watched {
+        let path = Path::new(&tab.file_name_path);
+
+        let (kind, modified_at, new_file_name_path) = match path.metadata() {
+            Ok(metadata) => {
+                // The own saves of the application have already updated the date and the size.
+                if is_unchanged_by_metadata(&metadata, tab.modified_at, tab.content_size) {
+                    continue;
+                }
+
+                let modified_at: Option<DateTime<Utc>> = metadata
+                    .modified()
+                    .ok()
+                    .map(|modified_at| modified_at.into());
+
+                // The date changed without the contents changing, e.g. after `touch`, or the date
+                // alone could not tell whether the contents changed.
+                if is_content_unchanged(path, tab.content_hash.as_deref()) {
+                    if modified_at != tab.modified_at {
+                        updates.push((tab, modified_at, None));
+                    }
+                    continue;
+                }
+
+                // Without a hash only a changed date tells a change.
+                if tab.content_hash.is_none() && modified_at == tab.modified_at {
+                    continue;
+                }
+
+                (FileChangeKind::Modified, modified_at, None)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // The removal has already been reported.
+                if tab.modified_at.is_none() {
+                    continue;
+                }
+
+                match renames.get(path) {
+                    Some(new_path) => (
+                        FileChangeKind::Renamed,
+                        None,
+                        Some(new_path.display().to_string()),
+                    ),
+                    None => (FileChangeKind::Removed, None, None),
+                }
+            }
+            Err(_) => continue,
+        };
+
+        let change = FileChange {
+            tab_id: tab.uid,
+            file_name_path: tab.file_name_path.clone(),
+            kind,
+            new_file_name_path,
+            modified_at,
+        };
+        updates.push((tab, modified_at, Some(change)));
+    }
+
+    let mut changes = vec![];
+
+    {
+        let mut tabs = app_state.lock_file_tabs();
+
+        for (watched, modified_at, change) in updates {
+            // A tab closed, renamed or saved while the file was read is left as it is.
+            let Some(tab) = tabs.iter_mut().find(|tab| {
+                tab.uid == watched.uid
+                    && tab.file_name_path.as_deref() == Some(watched.file_name_path.as_str())
+                    && tab.modified_at == watched.modified_at
+            }) else {
+                continue;
+            };
+
+            tab.modified_at = modified_at;
+            changes.extend(change);
+        }
+    }
+
+    for change in changes {
+        let _ = app_handle.emit(FILE_CHANGED_EVENT, change);
+    }
+}
+
+/// The state of the tab of a changed file copied out of the tabs lock.
+struct WatchedTab {
+    /// The identifier of the tab.
+    uid: i32,
+    /// The name and path of the file.
+    file_name_path: String,
+    /// The last modified date of the file known by the tab.
+    modified_at: Option<DateTime<Utc>>,
+    /// The hash of the file contents known by the tab.
+    content_hash: Option<String>,
+    /// The size of the file contents known by the tab.
+    content_size: Option<u64>,
+}
+
+/// Checks whether the file contents match the content hash.
+///
+/// # Arguments
+/// * `path` - The file.
+/// * `content_hash` - The hash of the known file contents.
+///
+/// # Returns
+/// `true` if the contents match the hash; `false` if they differ, the hash is unknown or the file could not be read.
+fn is_content_unchanged(path: &Path, content_hash: Option<&str>) -> bool {
+    let Some(content_hash) = content_hash else {
+        return false;
+    };
+
+    match fs::read(path) {
+        Ok(contents) => hash_content(&contents) == content_hash,
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,275 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::path::Path;
+
+use jsts_engine::ScriptLanguage;
+use serde::{Deserialize, Serialize};
+
+/// The module system of a script file. The module kind is stored with the tab but never read, i.e. the
+/// scripts run the same way and no CommonJS semantics, e.g. `require` or `module.exports`, are applied.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleKind {
+    /// The ECMAScript module with `import` and `export`, e.g. `.mjs` and `.mts` files.
+    #[default]
+    EsModule,
+    /// The CommonJS module with `require` and `module.exports`, e.g. `.cjs` and `.cts` files.
+    CommonJs,
+}
+
+/// The language of a script file detected from its extension or shebang line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScriptFileLanguage {
+    /// The language of the script code for the engine.
+    pub language: ScriptLanguage,
+    /// The module system of the script file.
+    pub module_kind: ModuleKind,
+}
+
+impl ScriptFileLanguage {
+    /// Gets the name of the language for the editor, either `javascript` or `typescript`.
+    pub fn editor_language(&self) -> &'static str {
+        match self.language {
+            ScriptLanguage::JavaScript | ScriptLanguage::Jsx => "javascript",
+            ScriptLanguage::TypeScript | ScriptLanguage::Tsx => "typescript",
+        }
+    }
+
+    /// Checks whether the script code has JSX elements.
+    pub fn is_jsx(&self) -> bool {
+        matches!(self.language, ScriptLanguage::Jsx | ScriptLanguage::Tsx)
+    }
+}
+
+impl Default for ScriptFileLanguage {
+    fn default() -> Self {
+        Self {
+            language: ScriptLanguage::TypeScript,
+            module_kind: ModuleKind::EsModule,
+        }
+    }
+}
+
+/// The script file extensions with their languages and module systems.
+const SCRIPT_EXTENSIONS: &[(&str, ScriptLanguage, ModuleKind)] = &[
+    ("js", ScriptLanguage::JavaScript, ModuleKind::EsModule),
+    ("mjs", ScriptLanguage::JavaScript, ModuleKind::EsModule),
+    ("cjs", ScriptLanguage::JavaScript, ModuleKind::CommonJs),
+    ("jsx", ScriptLanguage::Jsx, ModuleKind::EsModule),
+    ("ts", ScriptLanguage::TypeScript, ModuleKind::EsModule),
+    ("mts", ScriptLanguage::TypeScript, ModuleKind::EsModule),
+    ("cts", ScriptLanguage::TypeScript, ModuleKind::CommonJs),
+    ("tsx", ScriptLanguage::Tsx, ModuleKind::EsModule),
+];
+
+/// The interpreters of the shebang lines with the languages and the module systems of the scripts they run.
+/// Node.js runs the extensionless files as CommonJS modules.
+const SHEBANG_INTERPRETERS: &[(&str, ScriptLanguage, ModuleKind)] = &[
+    ("node", ScriptLanguage::JavaScript, ModuleKind::CommonJs),
+    ("nodejs", ScriptLanguage::JavaScript, ModuleKind::CommonJs),
+    ("bun", ScriptLanguage::JavaScript, ModuleKind::EsModule),
+    ("deno", ScriptLanguage::TypeScript, ModuleKind::EsModule),
+    ("ts-node", ScriptLanguage::TypeScript, ModuleKind::CommonJs),
+    ("tsx", ScriptLanguage::TypeScript, ModuleKind::EsModule),
+];
+
+/// Detects the language of a script file from its extension or, if the extension is unknown, from
+/// the shebang line of the contents.
+///
+/// # Arguments
+/// * `path` - The script file.
+/// * `contents` - The contents of the script file.
+///
+/// # Returns
+/// The detected language; TypeScript if neither the extension nor the shebang line is known.
+pub fn detect_language(path: &Path, contents: &str) -> ScriptFileLanguage {
+    language_by_extension(path)
+        .or_else(|| language_by_shebang(contents))
+        .unwrap_or_default()
+}
+
+/// Gets the language of a script file by its extension.
+///
+/// # Arguments
+/// * `path` - The script file.
+///
+/// # Returns
+/// The language; `None` if the extension is not a script file extension.
+pub fn language_by_extension(path: &Path) -> Option<ScriptFileLanguage> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+
+    SCRIPT_EXTENSIONS
+        .iter()
+        .find(|(script_extension, _, _)| *script_extension == extension)
+        .map(|(_, language, module_kind)| ScriptFileLanguage {
+            language: *language,
+            module_kind: *module_kind,
+        })
+}
+
+/// Gets the language of a script by the interpreter of its shebang line, e.g. `#!/usr/bin/env node`.
+///
+/// # Arguments
+/// * `contents` - The contents of the script.
+///
+/// # Returns
+/// The language; `None` if the script has no shebang line or the interpreter is unknown.
+pub fn language_by_shebang(contents: &str) -> Option<ScriptFileLanguage> {
+    let line = contents.lines().next()?.strip_prefix("#!")?;
+
+    // The interpreter is either the program itself or the first argument of `env` not being an option.
+    let mut words = line
+        .split_whitespace()
+        .map(|word| match word.rsplit_once('/') {
+            Some((_, program)) => program,
+            None => word,
+        });
+    let program = match words.next()? {
+        "env" => words.find(|word| !word.starts_with('-'))?,
+        program => program,
+    };
+
+    SHEBANG_INTERPRETERS
+        .iter()
+        .find(|(interpreter, _, _)| *interpreter == program)
+        .map(|(_, language, module_kind)| ScriptFileLanguage {
+            language: *language,
+            module_kind: *module_kind,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_language_by_extension() {
+        let cases = [
+            (
+                "script.js",
+                ScriptLanguage::JavaScript,
+                ModuleKind::EsModule,
+            ),
+            (
+                "script.mjs",
+                ScriptLanguage::JavaScript,
+                ModuleKind::EsModule,
+            ),
+            (
+                "script.cjs",
+                ScriptLanguage::JavaScript,
+                ModuleKind::CommonJs,
+            ),
+            ("script.jsx", ScriptLanguage::Jsx, ModuleKind::EsModule),
+            (
+                "script.ts",
+                ScriptLanguage::TypeScript,
+                ModuleKind::EsModule,
+            ),
+            (
+                "script.mts",
+                ScriptLanguage::TypeScript,
+                ModuleKind::EsModule,
+            ),
+            (
+                "script.cts",
+                ScriptLanguage::TypeScript,
+                ModuleKind::CommonJs,
+            ),
+            ("script.tsx", ScriptLanguage::Tsx, ModuleKind::EsModule),
+            (
+                "SCRIPT.JS",
+                ScriptLanguage::JavaScript,
+                ModuleKind::EsModule,
+            ),
+            (
+                "Script.CTS",
+                ScriptLanguage::TypeScript,
+                ModuleKind::CommonJs,
+            ),
+            ("Component.TSX", ScriptLanguage::Tsx, ModuleKind::EsModule),
+        ];
+
+        for (file, language, module_kind) in cases {
+            assert_eq!(
+                language_by_extension(Path::new(file)),
+                Some(ScriptFileLanguage {
+                    language,
+                    module_kind
+                }),
+                "{}",
+                file
+            );
+        }
+
+        assert_eq!(language_by_extension(Path::new("notes.txt")), None);
+        assert_eq!(language_by_extension(Path::new("script")), None);
+    }
+
+    #[test]
+    fn detects_language_by_shebang() {
+        let node = Some(ScriptFileLanguage {
+            language: ScriptLanguage::JavaScript,
+            module_kind: ModuleKind::CommonJs,
+        });
+
+        assert_eq!(language_by_shebang("#!/usr/bin/env node\n"), node);
+        assert_eq!(
+            language_by_shebang("#!/usr/bin/env -S node --no-warnings\n"),
+            node
+        );
+        assert_eq!(
+            language_by_shebang("#!/usr/bin/node\nconsole.log(1);"),
+            node
+        );
+        assert_eq!(
+            language_by_shebang("#!/usr/bin/env deno\n"),
+            Some(ScriptFileLanguage {
+                language: ScriptLanguage::TypeScript,
+                module_kind: ModuleKind::EsModule,
+            })
+        );
+        assert_eq!(language_by_shebang("#!/usr/bin/env python3\n"), None);
+        assert_eq!(language_by_shebang("console.log(1);\n"), None);
+    }
+
+    #[test]
+    fn falls_back_to_typescript() {
+        assert_eq!(
+            detect_language(Path::new("script"), "#!/usr/bin/env python3\nprint(1)"),
+            ScriptFileLanguage::default()
+        );
+        assert_eq!(
+            ScriptFileLanguage::default().language,
+            ScriptLanguage::TypeScript
+        );
+
+        // A known extension wins over the shebang line.
+        assert_eq!(
+            detect_language(Path::new("script.js"), "#!/usr/bin/env deno\n").language,
+            ScriptLanguage::JavaScript
+        );
+    }
+}
@@ -22,36 +22,71 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-use config::AppConfig;
+use std::collections::BTreeMap;
 
-use tauri::State;
+use autosave::RecoverySnapshot;
+use batch::{BatchReportFormat, BatchSummary};
+use config::{AppConfig, SidebarLayout};
+use error::CommandResult;
+use file_watcher::FileWatcher;
+use jsts_engine::{SnapshotMismatch, TestReport};
+use local_history::{Revision, RevisionDiff};
+use merge::FileMerge;
+use project_folder::ProjectFile;
+
+use tauri::{AppHandle, Manager, RunEvent, State};
 use tauri_commands::TauriCommands;
-use types::{AppState, AppStateResult, FileTabData};
+use text_encoding::{LineEnding, TextEncoding};
+use types::{AppState, AppStateResult, FileTabData, GlobalVariable, WorkspaceInfo};
 use utils::show_window;
-use v8;
 
 #[macro_use]
 extern crate rust_i18n;
 i18n!();
 
+mod autosave;
+mod batch;
+mod cli;
 mod config;
-mod js_helpers;
+mod error;
+mod file_pages;
+mod file_save;
+mod file_watcher;
+mod language;
+mod live_runner;
+mod local_history;
+mod merge;
+mod project_folder;
+mod recent_files;
+mod repl;
+mod report;
+mod state_recovery;
 mod tauri_commands;
+mod tauri_commands_autosave;
+mod tauri_commands_batch;
 mod tauri_commands_config;
+mod tauri_commands_encoding;
 mod tauri_commands_fs;
+mod tauri_commands_history;
+mod tauri_commands_live;
+mod tauri_commands_merge;
+mod tauri_commands_pages;
+mod tauri_commands_project;
+mod tauri_commands_recent;
 mod tauri_commands_state;
 mod tauri_commands_tabs;
+mod tauri_commands_workspace;
 mod tauri_commans_script;
+mod text_encoding;
 mod types;
 mod utils;
+mod variable_inspector;
+
+pub use cli::run_cli;
 
 #[tokio::main]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub async fn run() {
-    let platform = v8::new_default_platform(0, false).make_shared();
-    v8::V8::initialize_platform(platform);
-    v8::V8::initialize();
-
     tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             let _ = show_window(app);
@@ -63,6 +98,33 @@ pub async fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
         .manage(AppState::default())
+        .setup(|app| {
+            let app_state = app.state::<AppState>();
+            // The application handle is used to notify the frontend about the recovered application state.
+            let _ = app_state.app_handle.set(app.handle().clone());
+
+            // Without the file watcher the changed files are still noticed when the frontend polls them.
+            match FileWatcher::start(app.handle().clone()) {
+                Ok(file_watcher) => {
+                    *app_state.lock_or_reset("file_watcher", &app_state.file_watcher) =
+                        Some(file_watcher);
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+
+            // The unsaved tab contents of a crashed session are offered to be restored after the open files state is loaded.
+            match autosave::start_session() {
+                Ok(true) => {
+                    *app_state.lock_or_reset("recovery", &app_state.recovery) =
+                        autosave::read_snapshot();
+                }
+                Ok(false) => {}
+                Err(e) => eprintln!("{}", e),
+            }
+            autosave::start_autosave(app.handle().clone());
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             load_settings,
             set_i18n_locale,
@@ -73,6 +135,7 @@ pub async fn run() {
             save_open_tabs,
             add_new_tab,
             update_open_tabs,
+            update_tab_content,
             load_file_state,
             get_new_tab_id,
             open_existing_file,
@@ -83,15 +146,55 @@ pub async fn run() {
             save_file_contents,
             is_file_opened,
             test_function_call,
-            set_active_tab_id
+            set_active_tab_id,
+            get_global_variables,
+            get_global_variable_children,
+            update_live_content,
+            stop_live_mode,
+            run_batch,
+            save_batch_report,
+            run_tests,
+            get_snapshots,
+            update_snapshots,
+            merge_file_changes,
+            save_merged_file,
+            convert_file_encoding,
+            reopen_file_with_encoding,
+            open_file_in_pages,
+            read_file_page,
+            get_recent_files,
+            get_closed_tabs,
+            reopen_closed_tab,
+            clear_recent_files,
+            clear_closed_tabs,
+            get_workspaces,
+            save_workspace,
+            switch_workspace,
+            rename_workspace,
+            delete_workspace,
+            get_recovery_snapshot,
+            restore_recovery_snapshot,
+            record_run_revision,
+            get_history_revisions,
+            diff_history_revisions,
+            restore_history_revision,
+            open_project_folder,
+            close_project_folder,
+            get_project_files,
+            set_sidebar_layout
         ])
-        .run(tauri::generate_context!())
-        .expect(t!("messages.tauriRunError").into_owned().as_str());
+        .build(tauri::generate_context!())
+        .expect(t!("messages.tauriRunError").into_owned().as_str())
+        .run(|_app_handle, event| {
+            if let RunEvent::Exit = event {
+                autosave::end_session();
+            }
+        });
 }
 
 /// See [TauriCommands::load_settings]
 #[tauri::command(async)]
-async fn load_settings() -> Result<AppConfig, String> {
+async fn load_settings() -> CommandResult<AppConfig> {
     TauriCommands::load_settings().await
 }
 
@@ -101,7 +204,7 @@ async fn load_settings() -> Result<AppConfig, String> {
 ///
 /// true if the locale was set successfully; Error otherwise.
 #[tauri::command(async)]
-async fn set_i18n_locale() -> Result<bool, String> {
+async fn set_i18n_locale() -> CommandResult<bool> {
     let config = TauriCommands::load_settings().await;
     match config {
         Ok(settings) => {
@@ -117,7 +220,7 @@ async fn set_i18n_locale() -> Result<bool, String> {
 
 /// See [TauriCommands::load_file_state]
 #[tauri::command(async)]
-async fn load_file_state(app_state: State<'_, AppState>) -> Result<bool, String> {
+async fn load_file_state(app_state: State<'_, AppState>) -> CommandResult<bool> {
     TauriCommands::load_file_state(&app_state).await
 }
 
@@ -129,22 +232,58 @@ async fn save_settings(config: AppConfig) -> bool {
 
 /// See [TauriCommands::run_script]
 #[tauri::command(async)]
-async fn run_script(code: String, app_state: State<'_, AppState>) -> Result<String, String> {
+async fn run_script(code: String, app_state: State<'_, AppState>) -> CommandResult<String> {
     TauriCommands::run_script(code, &app_state).await
 }
 
+/// See [TauriCommands::get_global_variables]
+#[tauri::command(async)]
+async fn get_global_variables(
+    app_state: State<'_, AppState>,
+) -> CommandResult<Vec<GlobalVariable>> {
+    TauriCommands::get_global_variables(&app_state).await
+}
+
+/// See [TauriCommands::get_global_variable_children]
+#[tauri::command(async)]
+async fn get_global_variable_children(
+    handle: u32,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Vec<GlobalVariable>> {
+    TauriCommands::get_global_variable_children(handle, &app_state).await
+}
+
+/// See [TauriCommands::update_live_content]
+#[tauri::command(async)]
+async fn update_live_content(
+    app_handle: AppHandle,
+    tab_id: i32,
+    code: Vec<String>,
+    evaluate_per_line: bool,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::update_live_content(app_handle, tab_id, code, evaluate_per_line, &app_state)
+        .await
+}
+
+/// See [TauriCommands::stop_live_mode]
+#[tauri::command(async)]
+async fn stop_live_mode(tab_id: i32, app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::stop_live_mode(tab_id, &app_state).await
+}
+
 /// See [TauriCommands::run_script_line_by_line]
 #[tauri::command(async)]
 async fn run_script_line_by_line(
     code: Vec<String>,
     app_state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+) -> CommandResult<Vec<String>> {
     TauriCommands::run_script_line_by_line(code, &app_state).await
 }
 
 /// See [TauriCommands::save_open_tabs]
 #[tauri::command(async)]
-async fn save_open_tabs(app_state: State<'_, AppState>) -> Result<bool, String> {
+async fn save_open_tabs(app_state: State<'_, AppState>) -> CommandResult<bool> {
     TauriCommands::save_open_tabs(&app_state).await
 }
 
@@ -153,13 +292,23 @@ async fn save_open_tabs(app_state: State<'_, AppState>) -> Result<bool, String>
 async fn update_open_tabs(
     tab_data: Vec<FileTabData>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::update_open_tabs(tab_data, &app_state).await
 }
 
+/// See [TauriCommands::update_tab_content]
+#[tauri::command(async)]
+async fn update_tab_content(
+    uid: i32,
+    content: String,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::update_tab_content(uid, content, &app_state).await
+}
+
 /// See [TauriCommands::get_new_tab_id]
 #[tauri::command(async)]
-async fn get_new_tab_id(app_state: State<'_, AppState>) -> Result<i32, String> {
+async fn get_new_tab_id(app_state: State<'_, AppState>) -> CommandResult<i32> {
     TauriCommands::get_new_tab_id(&app_state).await
 }
 
@@ -169,13 +318,13 @@ async fn add_new_tab(
     tab_data: FileTabData,
     tab_content: Option<String>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::add_new_tab(tab_data, tab_content, &app_state).await
 }
 
 /// See [TauriCommands::get_app_state]
 #[tauri::command(async)]
-async fn get_app_state(app_state: State<'_, AppState>) -> Result<AppStateResult, String> {
+async fn get_app_state(app_state: State<'_, AppState>) -> CommandResult<AppStateResult> {
     TauriCommands::get_app_state(&app_state).await
 }
 
@@ -184,7 +333,7 @@ async fn get_app_state(app_state: State<'_, AppState>) -> Result<AppStateResult,
 async fn open_existing_file(
     file_name: String,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::open_existing_file(file_name, &app_state).await
 }
 
@@ -193,7 +342,7 @@ async fn open_existing_file(
 async fn is_file_changed_in_fs(
     data: FileTabData,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::is_file_changed_in_fs(data, &app_state).await
 }
 
@@ -202,7 +351,7 @@ async fn is_file_changed_in_fs(
 async fn is_existing_file_missing_in_fs(
     data: FileTabData,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::is_existing_file_missing_in_fs(data, &app_state).await
 }
 
@@ -211,7 +360,7 @@ async fn is_existing_file_missing_in_fs(
 async fn reload_file_contents(
     data: FileTabData,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::reload_file_contents(data, &app_state).await
 }
 
@@ -220,7 +369,7 @@ async fn reload_file_contents(
 async fn set_current_file_keep_in_editor(
     data: FileTabData,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     TauriCommands::set_current_file_keep_in_editor(data, &app_state).await
 }
 
@@ -229,20 +378,22 @@ async fn set_current_file_keep_in_editor(
 async fn save_file_contents(
     data: FileTabData,
     file_name_path: Option<String>,
+    overwrite: Option<bool>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
-    TauriCommands::save_file_contents(data, file_name_path, &app_state).await
+) -> CommandResult<bool> {
+    TauriCommands::save_file_contents(data, file_name_path, overwrite.unwrap_or(false), &app_state)
+        .await
 }
 
 /// See [TauriCommands::set_active_tab_id]
 #[tauri::command(async)]
-async fn set_active_tab_id(tab_id: i32, app_state: State<'_, AppState>) -> Result<bool, String> {
+async fn set_active_tab_id(tab_id: i32, app_state: State<'_, AppState>) -> CommandResult<bool> {
     TauriCommands::set_active_tab_id(tab_id, &app_state).await
 }
 
 /// See [TauriCommands::is_file_opened]
 #[tauri::command(async)]
-async fn is_file_opened(file_name: String, app_state: State<'_, AppState>) -> Result<bool, String> {
+async fn is_file_opened(file_name: String, app_state: State<'_, AppState>) -> CommandResult<bool> {
     TauriCommands::is_file_opened(file_name, &app_state).await
 }
 
@@ -254,9 +405,261 @@ async fn is_file_opened(file_name: String, app_state: State<'_, AppState>) -> Re
 ///
 /// # Returns
 ///
-/// * `Result<Vec<String>, String>` - The result of the test function call.
+/// * `CommandResult<Vec<String>>` - The result of the test function call.
 #[tauri::command(async)]
-async fn test_function_call(_app_state: State<'_, AppState>) -> Result<Vec<String>, String> {
+async fn test_function_call(_app_state: State<'_, AppState>) -> CommandResult<Vec<String>> {
     let result: Vec<String> = vec![];
     Ok(result)
 }
+
+/// See [TauriCommands::run_batch]
+#[tauri::command(async)]
+async fn run_batch(directory: String, jobs: Option<usize>) -> CommandResult<BatchSummary> {
+    TauriCommands::run_batch(directory, jobs).await
+}
+
+/// See [TauriCommands::save_batch_report]
+#[tauri::command(async)]
+async fn save_batch_report(
+    summary: BatchSummary,
+    format: BatchReportFormat,
+    file_name: String,
+) -> CommandResult<bool> {
+    TauriCommands::save_batch_report(summary, format, file_name).await
+}
+
+/// See [TauriCommands::run_tests]
+#[tauri::command(async)]
+async fn run_tests(
+    code: String,
+    file_name_path: Option<String>,
+    update_snapshots: bool,
+) -> CommandResult<TestReport> {
+    TauriCommands::run_tests(code, file_name_path, update_snapshots).await
+}
+
+/// See [TauriCommands::get_snapshots]
+#[tauri::command(async)]
+async fn get_snapshots(file_name_path: String) -> CommandResult<BTreeMap<String, String>> {
+    TauriCommands::get_snapshots(file_name_path).await
+}
+
+/// See [TauriCommands::update_snapshots]
+#[tauri::command(async)]
+async fn update_snapshots(
+    file_name_path: String,
+    mismatches: Vec<SnapshotMismatch>,
+) -> CommandResult<usize> {
+    TauriCommands::update_snapshots(file_name_path, mismatches).await
+}
+
+/// See [TauriCommands::merge_file_changes]
+#[tauri::command(async)]
+async fn merge_file_changes(
+    data: FileTabData,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Option<FileMerge>> {
+    TauriCommands::merge_file_changes(data, &app_state).await
+}
+
+/// See [TauriCommands::save_merged_file]
+#[tauri::command(async)]
+async fn save_merged_file(
+    data: FileTabData,
+    theirs_hash: String,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::save_merged_file(data, theirs_hash, &app_state).await
+}
+
+/// See [TauriCommands::convert_file_encoding]
+#[tauri::command(async)]
+async fn convert_file_encoding(
+    data: FileTabData,
+    encoding: TextEncoding,
+    line_ending: LineEnding,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::convert_file_encoding(data, encoding, line_ending, &app_state).await
+}
+
+/// See [TauriCommands::reopen_file_with_encoding]
+#[tauri::command(async)]
+async fn reopen_file_with_encoding(
+    data: FileTabData,
+    encoding: TextEncoding,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::reopen_file_with_encoding(data, encoding, &app_state).await
+}
+
+/// See [TauriCommands::open_file_in_pages]
+#[tauri::command(async)]
+async fn open_file_in_pages(
+    file_name: String,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::open_file_in_pages(file_name, &app_state).await
+}
+
+/// See [TauriCommands::read_file_page]
+#[tauri::command(async)]
+async fn read_file_page(
+    data: FileTabData,
+    page: u64,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::read_file_page(data, page, &app_state).await
+}
+
+/// See [TauriCommands::get_recent_files]
+#[tauri::command(async)]
+async fn get_recent_files(app_state: State<'_, AppState>) -> CommandResult<Vec<String>> {
+    TauriCommands::get_recent_files(&app_state).await
+}
+
+/// See [TauriCommands::get_closed_tabs]
+#[tauri::command(async)]
+async fn get_closed_tabs(app_state: State<'_, AppState>) -> CommandResult<Vec<FileTabData>> {
+    TauriCommands::get_closed_tabs(&app_state).await
+}
+
+/// See [TauriCommands::reopen_closed_tab]
+#[tauri::command(async)]
+async fn reopen_closed_tab(
+    index: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Option<i32>> {
+    TauriCommands::reopen_closed_tab(index, &app_state).await
+}
+
+/// See [TauriCommands::clear_recent_files]
+#[tauri::command(async)]
+async fn clear_recent_files(app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::clear_recent_files(&app_state).await
+}
+
+/// See [TauriCommands::clear_closed_tabs]
+#[tauri::command(async)]
+async fn clear_closed_tabs(app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::clear_closed_tabs(&app_state).await
+}
+
+/// See [TauriCommands::get_workspaces]
+#[tauri::command(async)]
+async fn get_workspaces(app_state: State<'_, AppState>) -> CommandResult<Vec<WorkspaceInfo>> {
+    TauriCommands::get_workspaces(&app_state).await
+}
+
+/// See [TauriCommands::save_workspace]
+#[tauri::command(async)]
+async fn save_workspace(name: String, app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::save_workspace(name, &app_state).await
+}
+
+/// See [TauriCommands::switch_workspace]
+#[tauri::command(async)]
+async fn switch_workspace(name: String, app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::switch_workspace(name, &app_state).await
+}
+
+/// See [TauriCommands::rename_workspace]
+#[tauri::command(async)]
+async fn rename_workspace(
+    name: String,
+    new_name: String,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::rename_workspace(name, new_name, &app_state).await
+}
+
+/// See [TauriCommands::delete_workspace]
+#[tauri::command(async)]
+async fn delete_workspace(name: String, app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::delete_workspace(name, &app_state).await
+}
+
+/// See [TauriCommands::get_recovery_snapshot]
+#[tauri::command(async)]
+async fn get_recovery_snapshot(
+    app_state: State<'_, AppState>,
+) -> CommandResult<Option<RecoverySnapshot>> {
+    TauriCommands::get_recovery_snapshot(&app_state).await
+}
+
+/// See [TauriCommands::restore_recovery_snapshot]
+#[tauri::command(async)]
+async fn restore_recovery_snapshot(
+    restore: bool,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::restore_recovery_snapshot(restore, &app_state).await
+}
+
+/// See [TauriCommands::record_run_revision]
+#[tauri::command(async)]
+async fn record_run_revision(
+    data: FileTabData,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Option<Revision>> {
+    TauriCommands::record_run_revision(data, &app_state).await
+}
+
+/// See [TauriCommands::get_history_revisions]
+#[tauri::command(async)]
+async fn get_history_revisions(
+    data: FileTabData,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Vec<Revision>> {
+    TauriCommands::get_history_revisions(data, &app_state).await
+}
+
+/// See [TauriCommands::diff_history_revisions]
+#[tauri::command(async)]
+async fn diff_history_revisions(
+    from_id: u64,
+    to_id: u64,
+    app_state: State<'_, AppState>,
+) -> CommandResult<RevisionDiff> {
+    TauriCommands::diff_history_revisions(from_id, to_id, &app_state).await
+}
+
+/// See [TauriCommands::restore_history_revision]
+#[tauri::command(async)]
+async fn restore_history_revision(
+    data: FileTabData,
+    id: u64,
+    app_state: State<'_, AppState>,
+) -> CommandResult<String> {
+    TauriCommands::restore_history_revision(data, id, &app_state).await
+}
+
+/// See [TauriCommands::open_project_folder]
+#[tauri::command(async)]
+async fn open_project_folder(
+    folder: String,
+    app_state: State<'_, AppState>,
+) -> CommandResult<Vec<ProjectFile>> {
+    TauriCommands::open_project_folder(folder, &app_state).await
+}
+
+/// See [TauriCommands::close_project_folder]
+#[tauri::command(async)]
+async fn close_project_folder(app_state: State<'_, AppState>) -> CommandResult<bool> {
+    TauriCommands::close_project_folder(&app_state).await
+}
+
+/// See [TauriCommands::get_project_files]
+#[tauri::command(async)]
+async fn get_project_files(app_state: State<'_, AppState>) -> CommandResult<Vec<ProjectFile>> {
+    TauriCommands::get_project_files(&app_state).await
+}
+
+/// See [TauriCommands::set_sidebar_layout]
+#[tauri::command(async)]
+async fn set_sidebar_layout(
+    sidebar: SidebarLayout,
+    app_state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    TauriCommands::set_sidebar_layout(sidebar, &app_state).await
+}
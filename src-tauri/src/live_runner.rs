@@ -0,0 +1,246 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use jsts_engine::{Engine, RunOptions, Session, TerminateHandle};
+
+use crate::types::{LineByLineLog, LineValueSnapshot};
+
+/// The name of the event emitted to the frontend with the live mode results.
+pub const LIVE_RESULT_EVENT: &str = "live-result";
+
+/// A script content update pushed by the frontend for the live mode.
+pub struct LiveUpdate {
+    /// The script code lines to evaluate.
+    pub code: Vec<String>,
+    /// A flag indicating whether to evaluate each line separately or the entire content at once.
+    pub evaluate_per_line: bool,
+}
+
+/// The live mode evaluation result emitted to the frontend.
+#[derive(Serialize, Clone)]
+pub struct LiveResult {
+    /// The identifier of the tab the result belongs to.
+    pub tab_id: i32,
+    /// The results of the script run; a single value unless evaluated line by line.
+    pub results: Vec<String>,
+    /// The log stack of the script run.
+    pub log_stack: Vec<String>,
+    /// The log stack lines of the script run line by line.
+    pub log_stack_lines: Vec<LineByLineLog>,
+    /// The value snapshots of the lines of the script run line by line.
+    pub line_value_snapshots: Vec<LineValueSnapshot>,
+}
+
+/// A live mode session of a single tab. The session owns a thread with a warm engine session which
+/// re-evaluates the latest pushed script content after the debounce time has elapsed.
+pub struct LiveSession {
+    /// The sender for the script content updates.
+    sender: Sender<LiveUpdate>,
+    /// The handle to terminate the script run of the engine session from another thread.
+    terminate_handle: TerminateHandle,
+    /// A flag indicating whether a script run is in progress.
+    running: Arc<AtomicBool>,
+}
+
+impl LiveSession {
+    /// Starts a new live mode session with its own engine session thread.
+    ///
+    /// # Arguments
+    /// * `app_handle` - The Tauri application handle to emit the results with.
+    /// * `tab_id` - The identifier of the tab the session belongs to.
+    /// * `debounce` - The time to wait for further updates before evaluating the content.
+    ///
+    /// # Returns
+    /// The live session; `None` if the engine session thread failed to start.
+    pub fn start(app_handle: AppHandle, tab_id: i32, debounce: Duration) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<LiveUpdate>();
+        let (handle_sender, handle_receiver) = mpsc::channel::<TerminateHandle>();
+        let running = Arc::new(AtomicBool::new(false));
+        let running_thread = running.clone();
+
+        thread::spawn(move || {
+            let mut session = Engine::new().create_session();
+            if handle_sender.send(session.terminate_handle()).is_err() {
+                return;
+            }
+
+            live_session_loop(
+                &mut session,
+                app_handle,
+                tab_id,
+                debounce,
+                receiver,
+                running_thread,
+            );
+        });
+
+        let terminate_handle = handle_receiver.recv().ok()?;
+
+        Some(Self {
+            sender,
+            terminate_handle,
+            running,
+        })
+    }
+
+    /// Pushes a new script content update into the session. A script run in progress is
+    /// cancelled as the result would be outdated.
+    ///
+    /// # Arguments
+    /// * `update` - The script content update.
+    ///
+    /// # Returns
+    /// `Ok` if the update was pushed successfully; the update back if the session thread has exited.
+    pub fn update(&self, update: LiveUpdate) -> Result<(), LiveUpdate> {
+        if self.running.load(Ordering::SeqCst) {
+            self.terminate_handle.terminate();
+        }
+
+        match self.sender.send(update) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(error.0),
+        }
+    }
+}
+
+impl Drop for LiveSession {
+    fn drop(&mut self) {
+        // The session thread exits after the run as the update sender is dropped.
+        self.terminate_handle.terminate();
+    }
+}
+
+/// The live session thread loop receiving the script content updates, debouncing and evaluating them.
+///
+/// # Arguments
+/// * `session` - The warm engine session.
+/// * `app_handle` - The Tauri application handle to emit the results with.
+/// * `tab_id` - The identifier of the tab the session belongs to.
+/// * `debounce` - The time to wait for further updates before evaluating the content.
+/// * `receiver` - The receiver for the script content updates.
+/// * `running` - A flag indicating whether a script run is in progress.
+fn live_session_loop(
+    session: &mut Session,
+    app_handle: AppHandle,
+    tab_id: i32,
+    debounce: Duration,
+    receiver: Receiver<LiveUpdate>,
+    running: Arc<AtomicBool>,
+) {
+    let mut pending: Option<LiveUpdate> = None;
+
+    loop {
+        let mut update = match pending.take() {
+            Some(update) => update,
+            None => match receiver.recv() {
+                Ok(update) => update,
+                Err(_) => return,
+            },
+        };
+
+        // Wait until no newer updates arrive within the debounce time.
+        loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(newer) => update = newer,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        running.store(true, Ordering::SeqCst);
+        let result = run_live_update(session, tab_id, &update);
+        running.store(false, Ordering::SeqCst);
+
+        // Discard the result if a newer update cancelled the run or is waiting to be evaluated.
+        match receiver.try_recv() {
+            Ok(newer) => {
+                pending = Some(newer);
+                continue;
+            }
+            Err(TryRecvError::Disconnected) => return,
+            Err(TryRecvError::Empty) => {}
+        }
+
+        let _ = app_handle.emit(LIVE_RESULT_EVENT, result);
+    }
+}
+
+/// Evaluates a script content update in a new context of the warm engine session.
+///
+/// # Arguments
+/// * `session` - The warm engine session.
+/// * `tab_id` - The identifier of the tab the update belongs to.
+/// * `update` - The script content update.
+///
+/// # Returns
+/// The live mode evaluation result.
+fn run_live_update(session: &mut Session, tab_id: i32, update: &LiveUpdate) -> LiveResult {
+    // A new context for each run so the previous run doesn't leave its variables behind.
+    session.reset();
+
+    let outcome = if update.evaluate_per_line {
+        session.run_lines(
+            &update.code,
+            &RunOptions {
+                capture_line_values: true,
+                ..Default::default()
+            },
+        )
+    } else {
+        session.run(&update.code.join("\n"), &RunOptions::default())
+    };
+
+    let log_stack = outcome.log_stack();
+    let log_stack_lines = outcome.log_stack_lines();
+
+    let results = outcome
+        .results
+        .into_iter()
+        .map(|result| match result {
+            Ok(value) => value.text,
+            Err(exception) => exception.message,
+        })
+        .collect();
+
+    LiveResult {
+        tab_id,
+        results,
+        log_stack,
+        log_stack_lines,
+        line_value_snapshots: outcome.line_value_snapshots,
+    }
+}
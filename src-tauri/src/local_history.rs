@@ -0,0 +1,492 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{collections::HashSet, fs, io, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use uuid::Uuid;
+
+use crate::{file_save::save_file_atomically, types::FileTabData, utils::hash_content};
+
+/// The name of the index file of the revisions in the local history directory.
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// The name of the directory of the revision contents by their hash in the local history directory.
+const OBJECTS_DIR_NAME: &str = "objects";
+
+/// The event which created a revision of a file.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RevisionKind {
+    /// The file was saved.
+    Save,
+    /// The script was run.
+    Run,
+}
+
+/// A revision of a file in the local history.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Revision {
+    /// The unique identifier of the revision.
+    pub id: u64,
+    /// The key of the file the revision belongs to, see [history_key].
+    pub key: String,
+    /// The name of the file when the revision was created.
+    pub file_name: String,
+    /// The event which created the revision.
+    pub kind: RevisionKind,
+    /// The time the revision was created.
+    pub created_at: DateTime<Utc>,
+    /// The SHA-256 hash of the content, i.e. the name of the content file.
+    pub hash: String,
+    /// The size of the content in bytes.
+    pub size: u64,
+}
+
+/// The difference of two revisions of a file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RevisionDiff {
+    /// The older revision.
+    pub from: Revision,
+    /// The newer revision.
+    pub to: Revision,
+    /// The unified diff of the contents of the revisions.
+    pub unified_diff: String,
+    /// The number of the inserted lines.
+    pub insertions: usize,
+    /// The number of the deleted lines.
+    pub deletions: usize,
+}
+
+/// The index of the revisions in the local history.
+#[derive(Serialize, Deserialize, Default)]
+struct HistoryIndex {
+    /// The identifier of the next revision.
+    next_id: u64,
+    /// The revisions, the oldest first.
+    revisions: Vec<Revision>,
+}
+
+/// Gets the key of the file of a tab in the local history. The files are keyed by their name and path
+/// and the temporary tabs by their persistent unique identifier, see [assign_history_id].
+///
+/// # Arguments
+/// * `tab` - The tab.
+///
+/// # Returns
+/// The key of the file.
+pub fn history_key(tab: &FileTabData) -> String {
+    match (&tab.file_name_path, &tab.history_id) {
+        (Some(file_name_path), _) if !tab.is_temporary => file_name_path.clone(),
+        (_, Some(history_id)) => format!("temporary:{}", history_id),
+        // A tab of an older version without an identifier until the tabs are restored again.
+        (_, None) => format!("temporary:{}:{}", tab.uid, tab.file_name),
+    }
+}
+
+/// Assigns a persistent unique identifier to a temporary tab without one. The tab identifiers are reused
+/// and overlap between the workspaces, so they cannot key the local history of the temporary tabs.
+///
+/// # Arguments
+/// * `tab` - The tab.
+pub fn assign_history_id(tab: &mut FileTabData) {
+    if tab.is_temporary && tab.history_id.is_none() {
+        tab.history_id = Some(Uuid::new_v4().to_string());
+    }
+}
+
+/// A local history of the file revisions. The contents are stored once by their hash and the oldest
+/// revisions are removed when the size of the contents exceeds the size limit.
+pub struct LocalHistory {
+    /// The local history directory.
+    directory: PathBuf,
+}
+
+impl LocalHistory {
+    /// Creates a local history in a directory.
+    ///
+    /// # Arguments
+    /// * `directory` - The local history directory; created on the first added revision.
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Adds a revision of a file unless the content equals the content of the latest revision of the file.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file, see [history_key].
+    /// * `file_name` - The name of the file.
+    /// * `kind` - The event which created the revision.
+    /// * `content` - The content of the file.
+    /// * `size_limit` - The size limit of the contents of the local history in bytes.
+    ///
+    /// # Returns
+    /// The added revision; `None` if the content was unchanged. Error if the local history could not be written.
+    pub fn add(
+        &self,
+        key: &str,
+        file_name: &str,
+        kind: RevisionKind,
+        content: &str,
+        size_limit: u64,
+    ) -> io::Result<Option<Revision>> {
+        let mut index = self.read_index()?;
+        let hash = hash_content(content.as_bytes());
+
+        let latest = index.revisions.iter().rev().find(|r| r.key == key);
+        if latest.is_some_and(|revision| revision.hash == hash) {
+            return Ok(None);
+        }
+
+        let objects = self.directory.join(OBJECTS_DIR_NAME);
+        fs::create_dir_all(&objects)?;
+        let object = objects.join(&hash);
+        if !object.exists() {
+            save_file_atomically(&object, content.as_bytes(), 0)?;
+        }
+
+        let revision = Revision {
+            id: index.next_id,
+            key: key.to_string(),
+            file_name: file_name.to_string(),
+            kind,
+            created_at: Utc::now(),
+            hash,
+            size: content.len() as u64,
+        };
+        index.next_id += 1;
+        index.revisions.push(revision.clone());
+
+        let removed_hashes = prune(&mut index, size_limit);
+        self.write_index(&index)?;
+
+        // The contents are removed only after the index no longer refers to them. A content file failing
+        // to be removed is just left unreferenced.
+        for hash in removed_hashes {
+            if let Err(e) = fs::remove_file(self.object_path(&hash)) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    eprintln!("{}: {}", self.object_path(&hash).display(), e);
+                }
+            }
+        }
+
+        Ok(Some(revision))
+    }
+
+    /// Lists the revisions of a file.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file, see [history_key].
+    ///
+    /// # Returns
+    /// The revisions of the file, the newest first; Error if the local history could not be read.
+    pub fn list(&self, key: &str) -> io::Result<Vec<Revision>> {
+        Ok(self
+            .read_index()?
+            .revisions
+            .into_iter()
+            .rev()
+            .filter(|r| r.key == key)
+            .collect())
+    }
+
+    /// Reads a revision and its content.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier of the revision.
+    ///
+    /// # Returns
+    /// The revision and its content; `None` if the revision does not exist. Error if the local history could not be read.
+    pub fn read(&self, id: u64) -> io::Result<Option<(Revision, String)>> {
+        let index = self.read_index()?;
+        let Some(revision) = index.revisions.into_iter().find(|r| r.id == id) else {
+            return Ok(None);
+        };
+
+        let content = fs::read_to_string(self.object_path(&revision.hash))?;
+        Ok(Some((revision, content)))
+    }
+
+    /// Compares two revisions.
+    ///
+    /// # Arguments
+    /// * `from_id` - The identifier of the older revision.
+    /// * `to_id` - The identifier of the newer revision.
+    ///
+    /// # Returns
+    /// The difference of the revisions; `None` if either revision does not exist. Error if the local history could not be read.
+    pub fn diff(&self, from_id: u64, to_id: u64) -> io::Result<Option<RevisionDiff>> {
+        let (Some((from, old)), Some((to, new))) = (self.read(from_id)?, self.read(to_id)?) else {
+            return Ok(None);
+        };
+
+        let text_diff = TextDiff::from_lines(&old, &new);
+        let (mut insertions, mut deletions) = (0, 0);
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => insertions += 1,
+                ChangeTag::Delete => deletions += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+
+        let unified_diff = text_diff
+            .unified_diff()
+            .header(
+                &format!("{} #{}", from.file_name, from.id),
+                &format!("{} #{}", to.file_name, to.id),
+            )
+            .to_string();
+
+        Ok(Some(RevisionDiff {
+            from,
+            to,
+            unified_diff,
+            insertions,
+            deletions,
+        }))
+    }
+
+    /// Gets the path of a content file.
+    ///
+    /// # Arguments
+    /// * `hash` - The hash of the content.
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.directory.join(OBJECTS_DIR_NAME).join(hash)
+    }
+
+    /// Reads the index of the revisions.
+    ///
+    /// # Returns
+    /// The index; an empty index if the local history does not exist yet. Error if the index could not be read.
+    fn read_index(&self) -> io::Result<HistoryIndex> {
+        match fs::read(self.index_path()) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HistoryIndex::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the index of the revisions.
+    ///
+    /// # Arguments
+    /// * `index` - The index.
+    ///
+    /// # Returns
+    /// Ok if the index was written; Error otherwise.
+    fn write_index(&self, index: &HistoryIndex) -> io::Result<()> {
+        save_file_atomically(&self.index_path(), &serde_json::to_vec(index)?, 0)
+    }
+
+    /// Gets the path of the index file.
+    fn index_path(&self) -> PathBuf {
+        self.directory.join(INDEX_FILE_NAME)
+    }
+}
+
+/// Removes the oldest revisions until the contents fit in the size limit. The latest revision of each
+/// file is kept. A content shared by several revisions is counted once.
+///
+/// # Arguments
+/// * `index` - The index of the revisions.
+/// * `size_limit` - The size limit of the contents in bytes.
+///
+/// # Returns
+/// The hashes of the contents no longer referred by any revision.
+fn prune(index: &mut HistoryIndex, size_limit: u64) -> Vec<String> {
+    let mut counted = HashSet::new();
+    let mut total: u64 = index
+        .revisions
+        .iter()
+        .filter(|r| counted.insert(r.hash.clone()))
+        .map(|r| r.size)
+        .sum();
+
+    let mut removed_hashes = vec![];
+    let mut position = 0;
+    while total > size_limit && position < index.revisions.len() {
+        let revision = &index.revisions[position];
+        let is_latest = !index.revisions[position + 1..]
+            .iter()
+            .any(|r| r.key == revision.key);
+        if is_latest {
+            position += 1;
+            continue;
+        }
+
+        let revision = index.revisions.remove(position);
+        if !index.revisions.iter().any(|r| r.hash == revision.hash) {
+            total = total.saturating_sub(revision.size);
+            removed_hashes.push(revision.hash);
+        }
+    }
+
+    removed_hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TestDir;
+
+    /// A size limit large enough to never prune.
+    const NO_LIMIT: u64 = u64::MAX;
+
+    /// Adds a revision asserting it was added.
+    fn add(history: &LocalHistory, key: &str, content: &str, size_limit: u64) -> Revision {
+        history
+            .add(key, key, RevisionKind::Save, content, size_limit)
+            .unwrap()
+            .unwrap()
+    }
+
+    /// Creates an index revision with the specified key, hash and size.
+    fn revision(id: u64, key: &str, hash: &str, size: u64) -> Revision {
+        Revision {
+            id,
+            key: key.to_string(),
+            file_name: key.to_string(),
+            kind: RevisionKind::Save,
+            created_at: Utc::now(),
+            hash: hash.to_string(),
+            size,
+        }
+    }
+
+    /// Gets the identifiers of the revisions of an index.
+    fn ids(index: &HistoryIndex) -> Vec<u64> {
+        index.revisions.iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn skips_unchanged_content() {
+        let dir = TestDir::new("history_unchanged");
+        let history = LocalHistory::new(dir.path.clone());
+
+        add(&history, "a.ts", "let a = 1;", NO_LIMIT);
+        let unchanged = history
+            .add("a.ts", "a.ts", RevisionKind::Run, "let a = 1;", NO_LIMIT)
+            .unwrap();
+
+        assert!(unchanged.is_none());
+        assert_eq!(history.list("a.ts").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn stores_shared_content_once() {
+        let dir = TestDir::new("history_shared");
+        let history = LocalHistory::new(dir.path.clone());
+
+        add(&history, "a.ts", "let a = 1;", NO_LIMIT);
+        add(&history, "b.ts", "let a = 1;", NO_LIMIT);
+
+        let objects = fs::read_dir(dir.path.join(OBJECTS_DIR_NAME)).unwrap();
+        assert_eq!(objects.count(), 1);
+    }
+
+    #[test]
+    fn prunes_oldest_revisions_keeping_latest_of_each_file() {
+        let mut index = HistoryIndex {
+            next_id: 5,
+            revisions: vec![
+                revision(1, "a", "h1", 10),
+                revision(2, "b", "h2", 10),
+                revision(3, "a", "h3", 10),
+                revision(4, "b", "h4", 10),
+                revision(5, "a", "h5", 10),
+            ],
+        };
+
+        let removed = prune(&mut index, 30);
+        assert_eq!(ids(&index), vec![3, 4, 5]);
+        assert_eq!(removed, vec!["h1", "h2"]);
+
+        // The latest revisions are kept even over the size limit.
+        let removed = prune(&mut index, 0);
+        assert_eq!(ids(&index), vec![4, 5]);
+        assert_eq!(removed, vec!["h3"]);
+    }
+
+    #[test]
+    fn counts_shared_content_once_when_pruning() {
+        let mut index = HistoryIndex {
+            next_id: 4,
+            revisions: vec![
+                revision(1, "a", "h1", 10),
+                revision(2, "b", "h1", 10),
+                revision(3, "a", "h2", 10),
+            ],
+        };
+
+        // The shared content is counted once, so the contents fit in the limit.
+        assert!(prune(&mut index, 20).is_empty());
+        assert_eq!(ids(&index), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn keeps_shared_content_of_pruned_revision() {
+        let dir = TestDir::new("history_prune_shared");
+        let history = LocalHistory::new(dir.path.clone());
+
+        add(&history, "a.ts", "shared", NO_LIMIT);
+        let shared = add(&history, "b.ts", "shared", NO_LIMIT);
+        add(&history, "a.ts", "changed", 0);
+
+        // The first revision of a.ts was pruned but its content is still used by b.ts.
+        assert_eq!(history.list("a.ts").unwrap().len(), 1);
+        let (_, content) = history.read(shared.id).unwrap().unwrap();
+        assert_eq!(content, "shared");
+    }
+
+    #[test]
+    fn removes_content_of_pruned_revision() {
+        let dir = TestDir::new("history_prune");
+        let history = LocalHistory::new(dir.path.clone());
+
+        let first = add(&history, "a.ts", "first", NO_LIMIT);
+        add(&history, "a.ts", "second", 0);
+
+        assert!(history.read(first.id).unwrap().is_none());
+        assert!(!history.object_path(&first.hash).exists());
+    }
+
+    #[test]
+    fn counts_inserted_and_deleted_lines() {
+        let dir = TestDir::new("history_diff");
+        let history = LocalHistory::new(dir.path.clone());
+
+        let from = add(&history, "a.ts", "a\nb\nc\n", NO_LIMIT);
+        let to = add(&history, "a.ts", "a\nB\nc\nd\n", NO_LIMIT);
+
+        let diff = history.diff(from.id, to.id).unwrap().unwrap();
+        assert_eq!(diff.insertions, 2);
+        assert_eq!(diff.deletions, 1);
+        assert!(diff.unified_diff.contains("-b\n"));
+        assert!(diff.unified_diff.contains("+B\n"));
+
+        assert!(history.diff(from.id, 99).unwrap().is_none());
+    }
+}
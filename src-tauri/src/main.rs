@@ -25,5 +25,10 @@ SOFTWARE.
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Run without the GUI if started with the command line mode arguments, e.g. `js_ts_runner run file.ts`.
+    if let Some(exit_code) = app_lib::run_cli() {
+        std::process::exit(exit_code);
+    }
+
     app_lib::run();
 }
@@ -0,0 +1,360 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{capture_diff_slices, Algorithm, DiffTag};
+
+/// The marker line starting the editor side of a merge conflict.
+pub const CONFLICT_MARKER_OURS: &str = "<<<<<<< editor";
+/// The marker line separating the editor and the file system sides of a merge conflict.
+pub const CONFLICT_MARKER_SEPARATOR: &str = "=======";
+/// The marker line ending the file system side of a merge conflict.
+pub const CONFLICT_MARKER_THEIRS: &str = ">>>>>>> file system";
+
+/// A conflict of a three-way merge where the editor and the file system changed the same lines differently.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The one-based line number of the first conflict marker line in the merged content.
+    pub start_line: usize,
+    /// The one-based line number of the last conflict marker line in the merged content.
+    pub end_line: usize,
+    /// The lines of the last saved content.
+    pub base: String,
+    /// The lines of the editor content.
+    pub ours: String,
+    /// The lines of the file system content.
+    pub theirs: String,
+}
+
+/// The result of a three-way merge of the editor content and the changed file in the file system.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileMerge {
+    /// The merged content with the conflicts marked with the conflict marker lines.
+    pub merged: String,
+    /// The conflicts of the merge.
+    pub conflicts: Vec<MergeConflict>,
+    /// The hash of the file system content the merge was made against.
+    pub theirs_hash: String,
+    /// The last modified date of the file system content the merge was made against.
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// A changed range of the base lines and the lines replacing it on one side of the merge.
+struct Hunk {
+    /// The replaced range of the base lines.
+    base: Range<usize>,
+    /// The range of the replacing lines of the side.
+    lines: Range<usize>,
+}
+
+/// Merges the changes of two contents derived from the same base content line by line.
+///
+/// # Arguments
+/// * `base` - The common base content, i.e. the last saved content.
+/// * `ours` - The editor content.
+/// * `theirs` - The file system content.
+///
+/// # Returns
+/// The merged content with the conflicts marked with the conflict marker lines and the conflicts.
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> (String, Vec<MergeConflict>) {
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let mut ours_hunks = changed_hunks(&base, &ours).into_iter().peekable();
+    let mut theirs_hunks = changed_hunks(&base, &theirs).into_iter().peekable();
+
+    let mut merged: Vec<String> = vec![];
+    let mut conflicts = vec![];
+    let mut position = 0;
+
+    loop {
+        let ours_start = ours_hunks.peek().map(|hunk| hunk.base.start);
+        let theirs_start = theirs_hunks.peek().map(|hunk| hunk.base.start);
+
+        let start = match (ours_start, theirs_start) {
+            (None, None) => break,
+            (Some(ours_start), Some(theirs_start)) => ours_start.min(theirs_start),
+            (Some(start), None) | (None, Some(start)) => start,
+        };
+
+        // Group the overlapping and touching hunks of both sides.
+        let mut end = start;
+        let mut ours_group = vec![];
+        let mut theirs_group = vec![];
+        loop {
+            if let Some(hunk) = ours_hunks.next_if(|hunk| hunk.base.start <= end) {
+                end = end.max(hunk.base.end);
+                ours_group.push(hunk);
+            } else if let Some(hunk) = theirs_hunks.next_if(|hunk| hunk.base.start <= end) {
+                end = end.max(hunk.base.end);
+                theirs_group.push(hunk);
+            } else {
+                break;
+            }
+        }
+
+        push_lines(&mut merged, &base[position..start]);
+        position = end;
+
+        let ours_lines = side_lines(&base, &ours, &ours_group, start..end);
+        let theirs_lines = side_lines(&base, &theirs, &theirs_group, start..end);
+
+        if theirs_group.is_empty() || ours_lines == theirs_lines {
+            push_lines(&mut merged, &ours_lines);
+        } else if ours_group.is_empty() {
+            push_lines(&mut merged, &theirs_lines);
+        } else {
+            let start_line = merged.len() + 1;
+            merged.push(CONFLICT_MARKER_OURS.to_string());
+            push_lines(&mut merged, &ours_lines);
+            merged.push(CONFLICT_MARKER_SEPARATOR.to_string());
+            push_lines(&mut merged, &theirs_lines);
+            merged.push(CONFLICT_MARKER_THEIRS.to_string());
+
+            conflicts.push(MergeConflict {
+                start_line,
+                end_line: merged.len(),
+                base: base[start..end].concat(),
+                ours: ours_lines.concat(),
+                theirs: theirs_lines.concat(),
+            });
+        }
+    }
+
+    push_lines(&mut merged, &base[position..]);
+
+    (join_lines(&merged), conflicts)
+}
+
+/// Counts the conflicts still marked with the conflict marker lines in the content.
+///
+/// # Arguments
+/// * `content` - The content to check.
+///
+/// # Returns
+/// The number of the unresolved conflicts; at least one if any conflict marker line remains.
+pub fn count_unresolved_conflicts(content: &str) -> usize {
+    let mut conflicts = 0;
+    let mut has_markers = false;
+
+    // A separator line counts only between the editor and the file system marker lines, which count
+    // themselves, so a lone `=======`, e.g. a heading underline in a comment, is not a marker.
+    for line in content.lines() {
+        if line == CONFLICT_MARKER_OURS {
+            conflicts += 1;
+        }
+
+        has_markers |= line == CONFLICT_MARKER_OURS || line == CONFLICT_MARKER_THEIRS;
+    }
+
+    if has_markers {
+        conflicts.max(1)
+    } else {
+        0
+    }
+}
+
+/// Gets the changed hunks of one side compared to the base lines.
+///
+/// # Arguments
+/// * `base` - The base lines.
+/// * `side` - The lines of the side.
+///
+/// # Returns
+/// The changed hunks in the base line order.
+fn changed_hunks(base: &[&str], side: &[&str]) -> Vec<Hunk> {
+    capture_diff_slices(Algorithm::Myers, base, side)
+        .iter()
+        .map(|op| op.as_tag_tuple())
+        .filter(|(tag, _, _)| *tag != DiffTag::Equal)
+        .map(|(_, base, lines)| Hunk { base, lines })
+        .collect()
+}
+
+/// Gets the lines of one side for a range of the base lines.
+///
+/// # Arguments
+/// * `base` - The base lines.
+/// * `side` - The lines of the side.
+/// * `hunks` - The changed hunks of the side within the range.
+/// * `range` - The range of the base lines.
+///
+/// # Returns
+/// The lines of the side replacing the base lines of the range.
+fn side_lines<'a>(
+    base: &[&'a str],
+    side: &[&'a str],
+    hunks: &[Hunk],
+    range: Range<usize>,
+) -> Vec<&'a str> {
+    let mut lines = vec![];
+    let mut position = range.start;
+
+    for hunk in hunks {
+        lines.extend_from_slice(&base[position..hunk.base.start]);
+        lines.extend_from_slice(&side[hunk.lines.clone()]);
+        position = hunk.base.end;
+    }
+
+    lines.extend_from_slice(&base[position..range.end]);
+    lines
+}
+
+/// Pushes lines into the merged lines.
+///
+/// # Arguments
+/// * `merged` - The merged lines.
+/// * `lines` - The lines to push.
+fn push_lines(merged: &mut Vec<String>, lines: &[&str]) {
+    merged.extend(lines.iter().map(|line| line.to_string()));
+}
+
+/// Joins the merged lines into content. The lines keep their own line breaks; the conflict marker
+/// lines and a last line of a side without a line break get a line break unless it is the last line.
+///
+/// # Arguments
+/// * `merged` - The merged lines.
+///
+/// # Returns
+/// The merged content.
+fn join_lines(merged: &[String]) -> String {
+    let mut content = String::new();
+
+    for (index, line) in merged.iter().enumerate() {
+        content.push_str(line);
+        if !line.ends_with('\n') && index + 1 < merged.len() {
+            content.push('\n');
+        }
+    }
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Merges the contents asserting the merged content and the number of the conflicts.
+    fn assert_merge(base: &str, ours: &str, theirs: &str, expected: &str, conflicts: usize) {
+        let (merged, merge_conflicts) = merge_three_way(base, ours, theirs);
+        assert_eq!(merged, expected);
+        assert_eq!(merge_conflicts.len(), conflicts);
+        assert_eq!(count_unresolved_conflicts(&merged), conflicts);
+    }
+
+    #[test]
+    fn merges_non_overlapping_changes() {
+        assert_merge(
+            "a\nb\nc\nd\ne\n",
+            "a\nB\nc\nd\ne\n",
+            "a\nb\nc\nD\ne\n",
+            "a\nB\nc\nD\ne\n",
+            0,
+        );
+    }
+
+    #[test]
+    fn merges_identical_changes() {
+        assert_merge("a\nb\nc\n", "a\nX\nc\n", "a\nX\nc\n", "a\nX\nc\n", 0);
+    }
+
+    #[test]
+    fn marks_overlapping_changes_as_conflict() {
+        let (merged, conflicts) = merge_three_way("a\nb\nc\n", "a\nB1\nc\n", "a\nB2\nc\n");
+
+        assert_eq!(
+            merged,
+            "a\n<<<<<<< editor\nB1\n=======\nB2\n>>>>>>> file system\nc\n"
+        );
+        assert_eq!(
+            conflicts,
+            vec![MergeConflict {
+                start_line: 2,
+                end_line: 6,
+                base: "b\n".to_string(),
+                ours: "B1\n".to_string(),
+                theirs: "B2\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn marks_touching_changes_as_conflict() {
+        let (merged, conflicts) = merge_three_way("a\nb\nc\nd\n", "a\nB\nc\nd\n", "a\nb\nC\nd\n");
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours, "B\nc\n");
+        assert_eq!(conflicts[0].theirs, "b\nC\n");
+        assert_eq!(count_unresolved_conflicts(&merged), 1);
+    }
+
+    #[test]
+    fn merges_inserts_at_start_and_end() {
+        assert_merge("a\nb\n", "x\na\nb\n", "a\nb\nz\n", "x\na\nb\nz\n", 0);
+    }
+
+    #[test]
+    fn keeps_last_line_without_line_break() {
+        assert_merge("a\nb\nc", "A\nb\nc", "a\nb\nC", "A\nb\nC", 0);
+        assert_merge(
+            "a\nb",
+            "a\nB",
+            "a\nC",
+            "a\n<<<<<<< editor\nB\n=======\nC\n>>>>>>> file system",
+            1,
+        );
+    }
+
+    #[test]
+    fn merges_against_empty_base() {
+        assert_merge("", "a\nb\n", "a\nb\n", "a\nb\n", 0);
+
+        let (merged, conflicts) = merge_three_way("", "a\nb\n", "a\nc\n");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours, "a\nb\n");
+        assert_eq!(conflicts[0].theirs, "a\nc\n");
+        assert_eq!(
+            merged,
+            "<<<<<<< editor\na\nb\n=======\na\nc\n>>>>>>> file system"
+        );
+    }
+
+    #[test]
+    fn counts_separator_only_between_markers() {
+        assert_eq!(count_unresolved_conflicts("Title\n=======\ntext\n"), 0);
+        assert_eq!(
+            count_unresolved_conflicts("<<<<<<< editor\na\n=======\nb\n>>>>>>> file system\n"),
+            1
+        );
+        // A partially resolved conflict still counts.
+        assert_eq!(
+            count_unresolved_conflicts("a\n=======\nb\n>>>>>>> file system\n"),
+            1
+        );
+    }
+}
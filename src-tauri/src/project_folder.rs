@@ -0,0 +1,381 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use ignore::WalkBuilder;
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{language::language_by_extension, types::AppState};
+
+/// The name of the event emitted to the frontend when script files were added to or removed from the project folder.
+pub const PROJECT_FILES_CHANGED_EVENT: &str = "project-files-changed";
+
+/// The time to wait for further file system events before listing the project folder again.
+const PROJECT_EVENT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The names of the ignore files whose changes change the listed files.
+const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".ignore"];
+
+/// A script file in the project folder.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProjectFile {
+    /// The name and path of the file.
+    pub file_name_path: String,
+    /// The path of the file relative to the project folder with `/` separators.
+    pub relative_path: String,
+    /// The name of the file.
+    pub file_name: String,
+}
+
+/// The script files added to or removed from the project folder emitted to the frontend.
+#[derive(Serialize, Clone, Debug)]
+pub struct ProjectFilesChange {
+    /// The path of the project folder.
+    pub folder: String,
+    /// The added files.
+    pub added: Vec<ProjectFile>,
+    /// The removed files.
+    pub removed: Vec<ProjectFile>,
+}
+
+/// The script files and the directories of a project folder not excluded by the ignore files.
+struct ProjectListing {
+    /// The script files sorted by their relative path.
+    files: Vec<ProjectFile>,
+    /// The directories including the project folder itself.
+    directories: HashSet<PathBuf>,
+}
+
+/// Lists the script files of a project folder recursively. The files and the directories excluded by
+/// the `.gitignore` and `.ignore` files and the hidden ones are skipped.
+///
+/// # Arguments
+/// * `folder` - The project folder.
+///
+/// # Returns
+/// The script files sorted by their relative path.
+pub fn list_project_files(folder: &Path) -> Vec<ProjectFile> {
+    list_project_folder(folder).files
+}
+
+/// Lists the script files and the directories of a project folder recursively.
+///
+/// # Arguments
+/// * `folder` - The project folder.
+///
+/// # Returns
+/// The listing of the project folder. The entries which could not be read are skipped.
+fn list_project_folder(folder: &Path) -> ProjectListing {
+    let mut files = vec![];
+    let mut directories = HashSet::new();
+
+    // The .gitignore files apply even if the folder is not a Git repository.
+    let walker = WalkBuilder::new(folder).require_git(false).build();
+
+    for entry in walker.flatten() {
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        let path = entry.path();
+        if file_type.is_dir() {
+            directories.insert(path.to_path_buf());
+        } else if file_type.is_file() && language_by_extension(path).is_some() {
+            let Ok(relative_path) = path.strip_prefix(folder) else {
+                continue;
+            };
+
+            files.push(ProjectFile {
+                file_name_path: path.display().to_string(),
+                relative_path: relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                file_name: entry.file_name().to_string_lossy().to_string(),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    ProjectListing { files, directories }
+}
+
+/// A file system watcher over a project folder. The directories not excluded by the ignore files are
+/// watched one by one so that e.g. `node_modules` does not flood the watcher with events.
+pub struct ProjectWatcher {
+    /// The project folder.
+    folder: PathBuf,
+    /// The native file system watcher.
+    watcher: RecommendedWatcher,
+    /// The watched directories.
+    directories: HashSet<PathBuf>,
+    /// The script files of the last listing of the project folder.
+    files: Vec<ProjectFile>,
+}
+
+impl ProjectWatcher {
+    /// Starts a new project folder watcher with its own event handler thread. The thread ends when the
+    /// watcher is dropped. The project folder is walked, so the watcher should be started in the blocking
+    /// thread pool.
+    ///
+    /// # Arguments
+    /// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+    /// * `folder` - The project folder to watch.
+    ///
+    /// # Returns
+    /// The project folder watcher; Error if the native file system watcher could not be created.
+    pub fn start(app_handle: AppHandle, folder: PathBuf) -> notify::Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Event>();
+
+        let watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                let _ = sender.send(event);
+            }
+        })?;
+
+        let mut project_watcher = Self {
+            folder: folder.clone(),
+            watcher,
+            directories: HashSet::new(),
+            files: vec![],
+        };
+
+        // The directories are watched before the files are listed, so a file created in between is
+        // either listed or reported by an event.
+        let directories = list_project_folder(&folder).directories;
+        project_watcher.watch_directories(directories);
+        project_watcher.apply(list_project_folder(&folder));
+
+        thread::spawn(move || project_event_loop(app_handle, folder, receiver));
+
+        Ok(project_watcher)
+    }
+
+    /// Gets the script files of the last listing of the project folder.
+    pub fn files(&self) -> &[ProjectFile] {
+        &self.files
+    }
+
+    /// Updates the watched directories to match the specified directories.
+    ///
+    /// # Arguments
+    /// * `directories` - The directories to watch.
+    fn watch_directories(&mut self, directories: HashSet<PathBuf>) {
+        for directory in self.directories.difference(&directories) {
+            let _ = self.watcher.unwatch(directory);
+        }
+
+        let mut watched = HashSet::new();
+        for directory in directories {
+            if !self.directories.contains(&directory) {
+                if let Err(e) = self.watcher.watch(&directory, RecursiveMode::NonRecursive) {
+                    eprintln!("{}: {}", directory.display(), e);
+                    continue;
+                }
+            }
+            watched.insert(directory);
+        }
+        self.directories = watched;
+    }
+
+    /// Applies a new listing of the project folder updating the watched directories to match it.
+    ///
+    /// # Arguments
+    /// * `listing` - The listing of the project folder.
+    ///
+    /// # Returns
+    /// The script files added to and removed from the project folder since the previous listing.
+    fn apply(&mut self, listing: ProjectListing) -> (Vec<ProjectFile>, Vec<ProjectFile>) {
+        self.watch_directories(listing.directories);
+
+        let added = listing
+            .files
+            .iter()
+            .filter(|file| !self.files.contains(file))
+            .cloned()
+            .collect();
+        let removed = self
+            .files
+            .iter()
+            .filter(|file| !listing.files.contains(file))
+            .cloned()
+            .collect();
+        self.files = listing.files;
+
+        (added, removed)
+    }
+}
+
+impl AppState {
+    /// Watches the open project folder replacing the previous project folder watcher. The folder is
+    /// walked in the blocking thread pool without holding the project folder watcher lock.
+    ///
+    /// # Returns
+    /// The script files of the open project folder; empty if no folder is open.
+    pub async fn sync_project_watcher(&self) -> Vec<ProjectFile> {
+        let folder = self.lock_project_folder().clone();
+
+        // Dropping the previous watcher ends its event handler thread.
+        *self.lock_or_reset("project_watcher", &self.project_watcher) = None;
+
+        let Some(folder) = folder else {
+            return vec![];
+        };
+
+        let app_handle = self.app_handle.get().cloned();
+        let watched_folder = PathBuf::from(&folder);
+        let started =
+            tokio::task::spawn_blocking(move || start_project_watcher(app_handle, watched_folder))
+                .await;
+
+        let (watcher, files) = match started {
+            Ok(started) => started,
+            Err(e) => {
+                eprintln!("{}: {}", folder, e);
+                return vec![];
+            }
+        };
+
+        // The project folder may have been changed while the folder was walked.
+        if self.lock_project_folder().as_ref() == Some(&folder) {
+            *self.lock_or_reset("project_watcher", &self.project_watcher) = watcher;
+        }
+
+        files
+    }
+}
+
+/// Starts a project folder watcher or, if it could not be started, lists the project folder.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle; `None` to only list the project folder.
+/// * `folder` - The project folder.
+///
+/// # Returns
+/// The started watcher if any and the script files of the project folder.
+fn start_project_watcher(
+    app_handle: Option<AppHandle>,
+    folder: PathBuf,
+) -> (Option<ProjectWatcher>, Vec<ProjectFile>) {
+    // Without the watcher the project folder is still listed, just not updated.
+    let Some(app_handle) = app_handle else {
+        return (None, list_project_files(&folder));
+    };
+
+    match ProjectWatcher::start(app_handle, folder.clone()) {
+        Ok(watcher) => {
+            let files = watcher.files().to_vec();
+            (Some(watcher), files)
+        }
+        Err(e) => {
+            eprintln!("{}: {}", folder.display(), e);
+            (None, list_project_files(&folder))
+        }
+    }
+}
+
+/// Collects the file system events until no more events arrive within the debounce time and then handles them.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+/// * `folder` - The watched project folder.
+/// * `receiver` - The receiver for the file system events.
+fn project_event_loop(app_handle: AppHandle, folder: PathBuf, receiver: Receiver<Event>) {
+    // Wait for the first event of a batch without a timeout.
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+
+        loop {
+            match receiver.recv_timeout(PROJECT_EVENT_DEBOUNCE) {
+                Ok(event) => events.push(event),
+                Err(RecvTimeoutError::Timeout) => break,
+                // The watcher was dropped, so the folder is no longer the project folder.
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        handle_project_events(&app_handle, &folder, events);
+    }
+}
+
+/// Lists the project folder again if the events may have added or removed files and emits the changes to the frontend.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle to access the application state and to emit the changes with.
+/// * `folder` - The watched project folder.
+/// * `events` - The file system events to handle.
+fn handle_project_events(app_handle: &AppHandle, folder: &Path, events: Vec<Event>) {
+    let changes_listing = events.iter().any(|event| match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_)) => {
+            true
+        }
+        EventKind::Modify(_) => event.paths.iter().any(|path| {
+            path.file_name()
+                .is_some_and(|name| IGNORE_FILE_NAMES.iter().any(|ignore| name == *ignore))
+        }),
+        _ => false,
+    });
+
+    if !changes_listing {
+        return;
+    }
+
+    // The folder is walked without holding the project folder watcher lock.
+    let listing = list_project_folder(folder);
+
+    let app_state = app_handle.state::<AppState>();
+    let (added, removed) = {
+        let mut project_watcher =
+            app_state.lock_or_reset("project_watcher", &app_state.project_watcher);
+        match project_watcher.as_mut() {
+            // A replaced watcher may still handle its last events.
+            Some(watcher) if watcher.folder == folder => watcher.apply(listing),
+            _ => return,
+        }
+    };
+
+    if added.is_empty() && removed.is_empty() {
+        return;
+    }
+
+    let _ = app_handle.emit(
+        PROJECT_FILES_CHANGED_EVENT,
+        ProjectFilesChange {
+            folder: folder.display().to_string(),
+            added,
+            removed,
+        },
+    );
+}
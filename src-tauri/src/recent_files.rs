@@ -0,0 +1,64 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use crate::types::{AppState, FileTabData};
+
+/// The maximum number of the recently opened files to remember.
+pub const RECENT_FILES_LIMIT: usize = 20;
+
+/// The maximum number of the recently closed tabs to remember.
+pub const CLOSED_TABS_LIMIT: usize = 20;
+
+impl AppState {
+    /// Adds a file as the most recently opened file. A file already in the list is moved to the top.
+    ///
+    /// # Arguments
+    /// * `file_name_path` - The name and path of the file.
+    pub fn add_recent_file(&self, file_name_path: &str) {
+        let mut recent_files = self.lock_recent_files();
+        recent_files.retain(|file| file != file_name_path);
+        recent_files.insert(0, file_name_path.to_string());
+        recent_files.truncate(RECENT_FILES_LIMIT);
+    }
+
+    /// Adds closed tabs on top of the stack of the recently closed tabs. The contents of the tabs of
+    /// the files in the file system are not kept as the files are reopened from the file system.
+    ///
+    /// # Arguments
+    /// * `tabs` - The closed tabs in the order they were closed.
+    pub fn add_closed_tabs(&self, tabs: Vec<FileTabData>) {
+        if tabs.is_empty() {
+            return;
+        }
+
+        let mut closed_tabs = self.lock_closed_tabs();
+        for mut tab in tabs {
+            if !tab.is_temporary {
+                tab.content = None;
+            }
+            closed_tabs.insert(0, tab);
+        }
+        closed_tabs.truncate(CLOSED_TABS_LIMIT);
+    }
+}
@@ -0,0 +1,381 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{fs, path::PathBuf};
+
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+use jsts_engine::{Engine, RunOptions, ScriptLanguage, Session};
+
+use crate::cli::{
+    print_exception, print_log_stack, read_script_file, script_language, EXIT_FILE_ERROR,
+    EXIT_SUCCESS,
+};
+
+/// The result of a REPL command.
+enum ReplCommand {
+    /// Continue reading the input.
+    Continue,
+    /// Exit the REPL.
+    Exit,
+}
+
+/// An interactive REPL session keeping the script context between the inputs.
+struct ReplSession {
+    /// The engine session keeping the variables between the inputs.
+    session: Session,
+    /// A flag indicating whether the input is TypeScript.
+    typescript: bool,
+    /// The successfully evaluated inputs of the session.
+    inputs: Vec<String>,
+}
+
+impl ReplSession {
+    /// Creates a new REPL session.
+    ///
+    /// # Arguments
+    /// * `typescript` - A flag indicating whether the input is TypeScript.
+    ///
+    /// # Returns
+    /// The REPL session.
+    fn new(typescript: bool) -> Self {
+        Self {
+            session: Engine::new().create_session(),
+            typescript,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Evaluates an input in the session context and prints the logs and the result.
+    ///
+    /// # Arguments
+    /// * `input` - The input code.
+    /// * `language` - The language of the input.
+    fn evaluate(&mut self, input: &str, language: ScriptLanguage) {
+        let options = RunOptions {
+            language,
+            ..Default::default()
+        };
+
+        let outcome = self.session.run(input, &options);
+
+        print_log_stack(&outcome.logs);
+
+        match outcome.results.into_iter().next() {
+            Some(Ok(value)) => {
+                // Show the plain objects and arrays as JSON as the preview only contains their type or length.
+                let is_json_value =
+                    matches!(value.snapshot.value_type.as_str(), "object" | "array");
+                match value.snapshot.json {
+                    Some(json) if is_json_value => println!("{}", json),
+                    _ => println!("{}", value.snapshot.preview),
+                }
+                self.inputs.push(input.to_string());
+            }
+            Some(Err(exception)) => print_exception(None, &exception),
+            None => {}
+        }
+    }
+
+    /// Runs a REPL command, e.g. `.load file.ts`.
+    ///
+    /// # Arguments
+    /// * `command` - The command line starting with a dot.
+    ///
+    /// # Returns
+    /// Whether to continue or exit the REPL.
+    fn run_command(&mut self, command: &str) -> ReplCommand {
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command, ""),
+        };
+
+        match name {
+            ".exit" => return ReplCommand::Exit,
+            ".help" => println!("{}", t!("long_texts.replHelp")),
+            ".clear" => {
+                self.session.reset();
+                self.inputs.clear();
+            }
+            ".load" | ".save" if argument.is_empty() => {
+                eprintln!("{}", t!("messages.replFileNotSpecified"))
+            }
+            ".load" => {
+                let file = PathBuf::from(argument);
+                match read_script_file(&file) {
+                    Ok(code) => self.evaluate(&code, script_language(&file)),
+                    Err(e) => eprintln!("{}: {}", file.display(), e),
+                }
+            }
+            ".save" => match fs::write(argument, self.inputs.join("\n")) {
+                Ok(_) => println!("{}", t!("messages.replSessionSaved", file = argument)),
+                Err(e) => eprintln!("{}: {}", argument, e),
+            },
+            _ => eprintln!("{}", t!("messages.replUnknownCommand", command = name)),
+        }
+
+        ReplCommand::Continue
+    }
+}
+
+/// Runs the interactive REPL in the terminal until the input ends or the `.exit` command.
+///
+/// # Arguments
+/// * `typescript` - A flag indicating whether the input is TypeScript.
+///
+/// # Returns
+/// The process exit code.
+pub fn run_repl(typescript: bool) -> i32 {
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_FILE_ERROR;
+        }
+    };
+
+    let history_path = repl_history_path();
+    if let Some(history_path) = &history_path {
+        let _ = editor.load_history(history_path);
+    }
+
+    let mut session = ReplSession::new(typescript);
+    let mut buffer = String::new();
+
+    println!("{}", t!("messages.replWelcome"));
+
+    loop {
+        let prompt = if buffer.is_empty() { "> " } else { "... " };
+
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            // Ctrl+C discards the current input.
+            Err(ReadlineError::Interrupted) => {
+                if buffer.is_empty() {
+                    println!("{}", t!("messages.replExitHint"));
+                }
+                buffer.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                eprintln!("{}", e);
+                break;
+            }
+        };
+
+        if buffer.is_empty() && line.trim_start().starts_with('.') {
+            let _ = editor.add_history_entry(line.as_str());
+            match session.run_command(line.trim()) {
+                ReplCommand::Continue => continue,
+                ReplCommand::Exit => break,
+            }
+        }
+
+        if !buffer.is_empty() {
+            buffer.push('\n');
+        }
+        buffer.push_str(&line);
+
+        // Continue reading lines until the brackets and template literals are closed.
+        if is_input_incomplete(&buffer) {
+            continue;
+        }
+
+        let input = std::mem::take(&mut buffer);
+        if input.trim().is_empty() {
+            continue;
+        }
+
+        let _ = editor.add_history_entry(input.as_str());
+        let language = if session.typescript {
+            ScriptLanguage::TypeScript
+        } else {
+            ScriptLanguage::JavaScript
+        };
+        session.evaluate(&input, language);
+    }
+
+    if let Some(history_path) = &history_path {
+        let _ = editor.save_history(history_path);
+    }
+
+    EXIT_SUCCESS
+}
+
+/// Gets the path of the REPL history file located next to the application settings.
+///
+/// # Returns
+/// The path of the REPL history file; `None` if the settings location is not available.
+fn repl_history_path() -> Option<PathBuf> {
+    match confy::get_configuration_file_path("js_ts_runner", "repl_history") {
+        Ok(path) => Some(path.with_extension("txt")),
+        Err(_) => None,
+    }
+}
+
+/// Checks if the input has unclosed brackets or template literals, meaning the input continues on the next line.
+/// Mismatched brackets and unterminated strings are left for the engine to report.
+///
+/// # Arguments
+/// * `code` - The input code.
+///
+/// # Returns
+/// `true` if the input is incomplete; `false` otherwise.
+fn is_input_incomplete(code: &str) -> bool {
+    let chars: Vec<char> = code.chars().collect();
+    // The expected closing characters, a backtick for a template literal.
+    let mut stack: Vec<char> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if stack.last() == Some(&'`') {
+            match c {
+                // Skip the escaped character.
+                '\\' => i += 1,
+                '`' => {
+                    stack.pop();
+                }
+                '$' if next == Some('{') => {
+                    stack.push('}');
+                    i += 1;
+                }
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return true;
+                }
+                i += 1;
+            }
+            '"' | '\'' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '`' => stack.push('`'),
+            '(' => stack.push(')'),
+            '[' => stack.push(']'),
+            '{' => stack.push('}'),
+            ')' | ']' | '}' => {
+                if stack.last() != Some(&c) {
+                    return false;
+                }
+                stack.pop();
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    !stack.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_incomplete_input() {
+        let cases = [
+            // Brackets.
+            ("1 + 2", false),
+            ("function f() {", true),
+            ("function f() {}", false),
+            ("[1, 2,", true),
+            ("f(1, [2, {a: 3}])", false),
+            ("f(1, [2, {a: 3}]", true),
+            ("if (x) { y(", true),
+            // A mismatched or an extra closing bracket is reported by the engine.
+            ("f(]", false),
+            ("}", false),
+            ("{ a: 1 ]", false),
+            // Brackets in strings.
+            ("'('", false),
+            ("\"{[\"", false),
+            ("f('}'", true),
+            ("f(\"a)\")", false),
+            ("'a\\'('", false),
+            ("\"a\\\"{\"", false),
+            ("{ s: ')' }", false),
+            // An unterminated string is reported by the engine.
+            ("'(", false),
+            ("\"abc", false),
+            // Template literals.
+            ("`abc", true),
+            ("`abc`", false),
+            ("`a\nb`", false),
+            ("`(`", false),
+            ("`\\``", false),
+            ("`\\`", true),
+            ("`${", true),
+            ("`${a}`", false),
+            ("`${a`", true),
+            ("`${ f({ b: 1 }) }`", false),
+            ("`${ f({ b: 1 }) `", true),
+            ("`a ${ `b ${c}` } d`", false),
+            ("`a ${ `b ${c}` } d", true),
+            ("`a ${ `b ${c` } d`", true),
+            ("`${ '}' }`", false),
+            ("`{`", false),
+            // Comments.
+            ("// {", false),
+            ("f( // )", true),
+            ("f( // )\n)", false),
+            ("/* { */", false),
+            ("/* { ", true),
+            ("f(/* ) */)", false),
+            ("/* a */ {", true),
+            ("x = 1 // `", false),
+            ("'//' + (", true),
+            ("\"/*\" + 1", false),
+        ];
+
+        for (code, incomplete) in cases {
+            assert_eq!(is_input_incomplete(code), incomplete, "{:?}", code);
+        }
+    }
+}
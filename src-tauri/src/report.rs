@@ -0,0 +1,402 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use jsts_engine::{
+    transpile_script, Engine, LogEntry, RunOptions, RunOutcome, ScriptException, ScriptLanguage,
+    ScriptValue, Session,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{
+    read_script_file, script_language, EXIT_FILE_ERROR, EXIT_SCRIPT_EXCEPTION, EXIT_SUCCESS,
+};
+
+/// The version of the JSON report format. Increment on any breaking change of the report structure.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// The status of a script run or a single line of a script run.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// The script ran without an uncaught exception.
+    Success,
+    /// The script threw an uncaught exception.
+    Exception,
+    /// The line was empty and was not evaluated.
+    Skipped,
+    /// The script file could not be read.
+    FileError,
+}
+
+/// The timings of a script run in milliseconds.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunTimings {
+    /// The time spent reading the script file.
+    pub read_ms: f64,
+    /// The time spent transpiling and running the script.
+    pub run_ms: f64,
+    /// The total time of the script run.
+    pub total_ms: f64,
+}
+
+/// The result of a single line of a script run line by line.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LineReport {
+    /// The one-based line number.
+    pub line_number: usize,
+    /// The status of the line.
+    pub status: RunStatus,
+    /// The completion value of the line; `None` if the line threw an exception or was skipped.
+    pub result: Option<ScriptValue>,
+    /// The details of the exception the line threw.
+    pub exception: Option<ScriptException>,
+    /// The time spent evaluating the line in milliseconds.
+    pub duration_ms: f64,
+}
+
+/// The machine-readable report of a headless script run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunReport {
+    /// The version of the report format, see [REPORT_SCHEMA_VERSION].
+    pub schema_version: u32,
+    /// The path of the script file.
+    pub file: String,
+    /// The language the script was run as.
+    pub language: ScriptLanguage,
+    /// A flag indicating whether the script was evaluated line by line.
+    pub evaluate_per_line: bool,
+    /// The status of the script run.
+    pub status: RunStatus,
+    /// The process exit code of the script run.
+    pub exit_code: i32,
+    /// The time the script run started.
+    pub started_at: DateTime<Utc>,
+    /// The completion value of the script; `None` if evaluated line by line or the script failed.
+    pub result: Option<ScriptValue>,
+    /// The results of the lines if evaluated line by line.
+    pub lines: Vec<LineReport>,
+    /// The captured console calls with one-based line numbers.
+    pub logs: Vec<LogEntry>,
+    /// The details of the first uncaught exception.
+    pub exception: Option<ScriptException>,
+    /// The error message if the script file could not be read.
+    pub error: Option<String>,
+    /// The timings of the script run.
+    pub timings: RunTimings,
+}
+
+impl RunReport {
+    /// Runs a script file in a new engine session and creates a report of the run.
+    ///
+    /// # Arguments
+    /// * `file` - The JavaScript or TypeScript file to run.
+    /// * `evaluate_per_line` - A flag indicating whether to evaluate each line separately.
+    /// * `timeout` - The time after which the script run is terminated as failed; `None` for no limit.
+    ///
+    /// # Returns
+    /// The script run report.
+    pub fn run_file(file: &Path, evaluate_per_line: bool, timeout: Option<Duration>) -> Self {
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let language = script_language(file);
+
+        // The entire file is transpiled beforehand so that the syntax errors are reported as file errors.
+        let code = match read_script_file(file) {
+            Ok(code) if language != ScriptLanguage::JavaScript && !evaluate_per_line => {
+                transpile_script(&code, language)
+            }
+            result => result,
+        };
+
+        let read_duration = started.elapsed();
+
+        let code = match code {
+            Ok(code) => code,
+            Err(e) => {
+                return Self::file_error(
+                    file,
+                    language,
+                    evaluate_per_line,
+                    started_at,
+                    read_duration,
+                    e,
+                    EXIT_FILE_ERROR,
+                );
+            }
+        };
+
+        let engine = Engine::new();
+        let outcome = run_with_timeout(
+            &mut engine.create_session(),
+            &code,
+            &RunOptions {
+                language: if evaluate_per_line {
+                    language
+                } else {
+                    ScriptLanguage::JavaScript
+                },
+                evaluate_per_line,
+                ..Default::default()
+            },
+            timeout,
+        );
+
+        let exit_code = match outcome.exception() {
+            Some(_) => EXIT_SCRIPT_EXCEPTION,
+            None => EXIT_SUCCESS,
+        };
+
+        Self::from_outcome(
+            file,
+            &code,
+            language,
+            evaluate_per_line,
+            started_at,
+            read_duration,
+            outcome,
+            exit_code,
+        )
+    }
+
+    /// Creates a report of a completed script run.
+    ///
+    /// # Arguments
+    /// * `file` - The script file.
+    /// * `code` - The script code.
+    /// * `language` - The language the script was run as.
+    /// * `evaluate_per_line` - A flag indicating whether the script was evaluated line by line.
+    /// * `started_at` - The time the script run started.
+    /// * `read_duration` - The time spent reading the script file.
+    /// * `outcome` - The outcome of the script run.
+    /// * `exit_code` - The process exit code of the script run.
+    ///
+    /// # Returns
+    /// The script run report.
+    #[allow(clippy::too_many_arguments)]
+    fn from_outcome(
+        file: &Path,
+        code: &str,
+        language: ScriptLanguage,
+        evaluate_per_line: bool,
+        started_at: DateTime<Utc>,
+        read_duration: Duration,
+        outcome: RunOutcome,
+        exit_code: i32,
+    ) -> Self {
+        let run_duration: Duration = outcome.durations.iter().sum();
+
+        let logs = outcome
+            .logs
+            .into_iter()
+            .map(|entry| LogEntry {
+                line_number: entry.line_number.map(|line_number| line_number + 1),
+                ..entry
+            })
+            .collect();
+
+        let mut result = None;
+        let mut exception = None;
+        let mut lines = Vec::new();
+
+        if evaluate_per_line {
+            let code_lines: Vec<&str> = code.split('\n').collect();
+
+            for (i, (line_result, duration)) in outcome
+                .results
+                .into_iter()
+                .zip(outcome.durations)
+                .enumerate()
+            {
+                let skipped = code_lines
+                    .get(i)
+                    .map(|line| line.trim().is_empty())
+                    .unwrap_or(false);
+
+                let (status, line_value, line_exception) = match line_result {
+                    _ if skipped => (RunStatus::Skipped, None, None),
+                    Ok(value) => (RunStatus::Success, Some(value), None),
+                    // Each line is evaluated as its own script, so the exception is always on its first line.
+                    Err(exception) => (
+                        RunStatus::Exception,
+                        None,
+                        Some(ScriptException {
+                            line_number: Some(i + 1),
+                            ..exception
+                        }),
+                    ),
+                };
+
+                if exception.is_none() {
+                    exception = line_exception.clone();
+                }
+
+                lines.push(LineReport {
+                    line_number: i + 1,
+                    status,
+                    result: line_value,
+                    exception: line_exception,
+                    duration_ms: duration_ms(duration),
+                });
+            }
+        } else {
+            match outcome.results.into_iter().next() {
+                Some(Ok(value)) => result = Some(value),
+                Some(Err(e)) => exception = Some(e),
+                None => {}
+            }
+        }
+
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            file: file.display().to_string(),
+            language,
+            evaluate_per_line,
+            status: if exception.is_some() {
+                RunStatus::Exception
+            } else {
+                RunStatus::Success
+            },
+            exit_code,
+            started_at,
+            result,
+            lines,
+            logs,
+            exception,
+            error: None,
+            timings: RunTimings {
+                read_ms: duration_ms(read_duration),
+                run_ms: duration_ms(run_duration),
+                total_ms: duration_ms(read_duration + run_duration),
+            },
+        }
+    }
+
+    /// Creates a report of a script run which failed as the script file could not be read.
+    ///
+    /// # Arguments
+    /// * `file` - The script file.
+    /// * `language` - The language the script would have been run as.
+    /// * `evaluate_per_line` - A flag indicating whether the script would have been evaluated line by line.
+    /// * `started_at` - The time the script run started.
+    /// * `read_duration` - The time spent reading the script file.
+    /// * `error` - The error message.
+    /// * `exit_code` - The process exit code.
+    ///
+    /// # Returns
+    /// The script run report.
+    fn file_error(
+        file: &Path,
+        language: ScriptLanguage,
+        evaluate_per_line: bool,
+        started_at: DateTime<Utc>,
+        read_duration: Duration,
+        error: String,
+        exit_code: i32,
+    ) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            file: file.display().to_string(),
+            language,
+            evaluate_per_line,
+            status: RunStatus::FileError,
+            exit_code,
+            started_at,
+            result: None,
+            lines: Vec::new(),
+            logs: Vec::new(),
+            exception: None,
+            error: Some(error),
+            timings: RunTimings {
+                read_ms: duration_ms(read_duration),
+                run_ms: 0.0,
+                total_ms: duration_ms(read_duration),
+            },
+        }
+    }
+}
+
+/// Runs the script code in a session terminating the run if it does not complete within a timeout.
+/// The exception of a terminated run tells the timeout.
+///
+/// # Arguments
+/// * `session` - The engine session.
+/// * `code` - The script code.
+/// * `options` - The options of the script run.
+/// * `timeout` - The time after which the script run is terminated; `None` for no limit.
+///
+/// # Returns
+/// The outcome of the script run.
+fn run_with_timeout(
+    session: &mut Session,
+    code: &str,
+    options: &RunOptions,
+    timeout: Option<Duration>,
+) -> RunOutcome {
+    let Some(timeout) = timeout else {
+        return session.run(code, options);
+    };
+
+    let handle = session.terminate_handle();
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    // The watchdog stops when the sender is dropped after the run.
+    let watchdog = thread::spawn(move || match receiver.recv_timeout(timeout) {
+        Err(RecvTimeoutError::Timeout) => handle.terminate(),
+        _ => false,
+    });
+
+    let mut outcome = session.run(code, options);
+    drop(sender);
+
+    let timed_out = watchdog.join().unwrap_or(false);
+    if timed_out && outcome.terminated {
+        if let Some(Err(exception)) = outcome.results.last_mut() {
+            let timeout = timeout.as_secs().to_string();
+            *exception = ScriptException::from_message(
+                t!("messages.scriptTimedOut", timeout = timeout).into_owned(),
+            );
+        }
+    }
+
+    outcome
+}
+
+/// Converts a duration into fractional milliseconds.
+///
+/// # Arguments
+/// * `duration` - The duration.
+///
+/// # Returns
+/// The duration in milliseconds.
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
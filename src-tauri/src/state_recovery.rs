@@ -0,0 +1,308 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::{
+    config::{get_file_state, get_workspaces},
+    types::{AppState, FileTabData},
+};
+
+/// The name of the event emitted to the frontend when a poisoned application state was recovered.
+pub const STATE_RECOVERED_EVENT: &str = "state-recovered";
+
+/// The action taken to recover a poisoned application state.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecoveryAction {
+    /// The state was restored from the last saved open files state.
+    Restored,
+    /// The transient state was reset to its default value.
+    Reset,
+    /// The state could not be restored and was left as the panicking thread left it.
+    Kept,
+}
+
+/// The last saved tabs of the workspace of the open tabs.
+struct SavedTabs {
+    /// The saved tabs.
+    files: Vec<FileTabData>,
+    /// The identifiers of the saved tabs.
+    file_ids: Vec<i32>,
+    /// The identifier of the active tab.
+    active_tab_id: Option<i32>,
+}
+
+/// An incident of a poisoned application state lock, i.e. a thread panicked while holding the lock.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StateRecovery {
+    /// The name of the recovered application state field, e.g. `file_tabs`.
+    pub state: String,
+    /// The action taken to recover the state.
+    pub action: RecoveryAction,
+    /// The time the poisoned state was recovered.
+    pub recovered_at: DateTime<Utc>,
+    /// The localized message describing the recovery.
+    pub message: String,
+}
+
+impl AppState {
+    /// Locks the open file tabs. A poisoned state is restored from the last saved tabs of the workspace of
+    /// the open tabs together with the tab identifiers.
+    ///
+    /// # Returns
+    /// The guard of the open file tabs.
+    pub fn lock_file_tabs(&self) -> MutexGuard<'_, Vec<FileTabData>> {
+        self.lock_or_recover("file_tabs", &self.file_tabs, |tabs| {
+            match self.saved_tabs() {
+                Some(saved) => {
+                    *tabs = saved.files;
+                    // The identifiers must match the restored tabs.
+                    restore_together(&self.file_ids, saved.file_ids);
+                    RecoveryAction::Restored
+                }
+                None => RecoveryAction::Kept,
+            }
+        })
+    }
+
+    /// Locks the identifiers of the open file tabs. A poisoned state is restored from the last saved tabs
+    /// of the workspace of the open tabs together with the tabs.
+    ///
+    /// # Returns
+    /// The guard of the open file tab identifiers.
+    pub fn lock_file_ids(&self) -> MutexGuard<'_, Vec<i32>> {
+        self.lock_or_recover("file_ids", &self.file_ids, |ids| match self.saved_tabs() {
+            Some(saved) => {
+                *ids = saved.file_ids;
+                // The tabs must match the restored identifiers.
+                restore_together(&self.file_tabs, saved.files);
+                RecoveryAction::Restored
+            }
+            None => RecoveryAction::Kept,
+        })
+    }
+
+    /// Locks the identifier of the active tab. A poisoned state is restored from the last saved tabs of the
+    /// workspace of the open tabs.
+    ///
+    /// # Returns
+    /// The guard of the active tab identifier.
+    pub fn lock_active_tab_id(&self) -> MutexGuard<'_, Option<i32>> {
+        self.lock_or_recover("active_tab_id", &self.active_tab_id, |id| {
+            match self.saved_tabs() {
+                Some(saved) => {
+                    *id = saved.active_tab_id;
+                    RecoveryAction::Restored
+                }
+                None => RecoveryAction::Kept,
+            }
+        })
+    }
+
+    /// Locks the recently opened files. A poisoned state is restored from the last saved open files state.
+    ///
+    /// # Returns
+    /// The guard of the recently opened files.
+    pub fn lock_recent_files(&self) -> MutexGuard<'_, Vec<String>> {
+        self.lock_or_recover(
+            "recent_files",
+            &self.recent_files,
+            |files| match get_file_state() {
+                Ok(file_state) => {
+                    *files = file_state.recent_files;
+                    RecoveryAction::Restored
+                }
+                Err(_) => RecoveryAction::Kept,
+            },
+        )
+    }
+
+    /// Locks the recently closed tabs. A poisoned state is restored from the last saved open files state.
+    ///
+    /// # Returns
+    /// The guard of the recently closed tabs.
+    pub fn lock_closed_tabs(&self) -> MutexGuard<'_, Vec<FileTabData>> {
+        self.lock_or_recover(
+            "closed_tabs",
+            &self.closed_tabs,
+            |tabs| match get_file_state() {
+                Ok(file_state) => {
+                    *tabs = file_state.closed_tabs;
+                    RecoveryAction::Restored
+                }
+                Err(_) => RecoveryAction::Kept,
+            },
+        )
+    }
+
+    /// Locks the name of the workspace of the open tabs. A poisoned state is restored from the last saved open files state.
+    ///
+    /// # Returns
+    /// The guard of the workspace name.
+    pub fn lock_workspace(&self) -> MutexGuard<'_, Option<String>> {
+        self.lock_or_recover(
+            "workspace",
+            &self.workspace,
+            |workspace| match get_file_state() {
+                Ok(file_state) => {
+                    *workspace = file_state.workspace;
+                    RecoveryAction::Restored
+                }
+                Err(_) => RecoveryAction::Kept,
+            },
+        )
+    }
+
+    /// Locks the path of the open project folder. A poisoned state is restored from the last saved open files state.
+    ///
+    /// # Returns
+    /// The guard of the project folder path.
+    pub fn lock_project_folder(&self) -> MutexGuard<'_, Option<String>> {
+        self.lock_or_recover("project_folder", &self.project_folder, |project_folder| {
+            match get_file_state() {
+                Ok(file_state) => {
+                    *project_folder = file_state.project_folder;
+                    RecoveryAction::Restored
+                }
+                Err(_) => RecoveryAction::Kept,
+            }
+        })
+    }
+
+    /// Locks a transient application state field, e.g. the results of the last script run.
+    /// A poisoned state is reset to its default value.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the application state field for the incident report.
+    /// * `mutex` - The mutex of the application state field.
+    ///
+    /// # Returns
+    /// The guard of the application state field.
+    pub fn lock_or_reset<'a, T: Default>(
+        &self,
+        name: &str,
+        mutex: &'a Mutex<T>,
+    ) -> MutexGuard<'a, T> {
+        self.lock_or_recover(name, mutex, |value| {
+            *value = T::default();
+            RecoveryAction::Reset
+        })
+    }
+
+    /// Gets the last saved tabs of the workspace of the open tabs to restore a poisoned state from. The open
+    /// files state is used if it was saved for the same workspace, otherwise the saved named workspace.
+    ///
+    /// # Returns
+    /// The saved tabs; `None` if no saved tabs of the workspace were found.
+    fn saved_tabs(&self) -> Option<SavedTabs> {
+        let workspace = self.lock_workspace().clone();
+
+        if let Ok(file_state) = get_file_state() {
+            if file_state.workspace == workspace {
+                return Some(SavedTabs {
+                    files: file_state.files,
+                    file_ids: file_state.file_ids,
+                    active_tab_id: file_state.active_tab_id,
+                });
+            }
+        }
+
+        let name = workspace?;
+        let saved = get_workspaces()
+            .ok()?
+            .workspaces
+            .into_iter()
+            .find(|workspace| workspace.name == name)?;
+
+        Some(SavedTabs {
+            files: saved.files,
+            file_ids: saved.file_ids,
+            active_tab_id: saved.active_tab_id,
+        })
+    }
+
+    /// Locks an application state field recovering it if a thread panicked while holding the lock.
+    /// The recovery is written to the standard error and emitted to the frontend as the `state-recovered` event.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the application state field for the incident report.
+    /// * `mutex` - The mutex of the application state field.
+    /// * `restore` - The function restoring the poisoned value returning the action taken.
+    ///
+    /// # Returns
+    /// The guard of the application state field.
+    fn lock_or_recover<'a, T>(
+        &self,
+        name: &str,
+        mutex: &'a Mutex<T>,
+        restore: impl FnOnce(&mut T) -> RecoveryAction,
+    ) -> MutexGuard<'a, T> {
+        let poisoned = match mutex.lock() {
+            Ok(guard) => return guard,
+            Err(poisoned) => poisoned,
+        };
+
+        let mut guard = poisoned.into_inner();
+        let action = restore(&mut guard);
+        mutex.clear_poison();
+
+        let message = match action {
+            RecoveryAction::Restored => t!("messages.stateRecovered", state = name),
+            RecoveryAction::Reset => t!("messages.stateReset", state = name),
+            RecoveryAction::Kept => t!("messages.stateRecoveryFailed", state = name),
+        }
+        .into_owned();
+
+        eprintln!("{}", message);
+
+        let recovery = StateRecovery {
+            state: name.to_string(),
+            action,
+            recovered_at: Utc::now(),
+            message,
+        };
+
+        if let Some(app_handle) = self.app_handle.get() {
+            let _ = app_handle.emit(STATE_RECOVERED_EVENT, recovery);
+        }
+
+        guard
+    }
+}
+
+/// Replaces the value of an application state field restored together with a poisoned field so that the
+/// fields stay consistent. The field is replaced even if it is poisoned itself.
+///
+/// # Arguments
+/// * `mutex` - The mutex of the application state field.
+/// * `value` - The restored value.
+fn restore_together<T>(mutex: &Mutex<T>, value: T) {
+    *mutex.lock().unwrap_or_else(PoisonError::into_inner) = value;
+    mutex.clear_poison();
+}
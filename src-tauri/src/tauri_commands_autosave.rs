@@ -0,0 +1,139 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    autosave::{remove_snapshot, EditedContent, RecoverySnapshot},
+    error::CommandResult,
+    local_history::assign_history_id,
+    tauri_commands::TauriCommands,
+    types::AppState,
+    utils::first_missing_in_sequence,
+};
+
+impl TauriCommands {
+    /// Updates the editor content of a tab for the autosave. The open tabs and the open files state are
+    /// not updated, so the update is cheap enough to be done on each edit.
+    ///
+    /// # Arguments
+    /// `uid` - The identifier of the tab.
+    /// `content` - The content in the editor.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the content was updated successfully.
+    pub async fn update_tab_content(
+        uid: i32,
+        content: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        app_state
+            .lock_or_reset("edited_contents", &app_state.edited_contents)
+            .insert(
+                uid,
+                EditedContent {
+                    content,
+                    edited_at: Utc::now(),
+                },
+            );
+        Ok(true)
+    }
+
+    /// Gets the unsaved tab contents of the previous session which ended uncleanly.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The snapshot of the unsaved tab contents; `None` if there is nothing to recover.
+    pub async fn get_recovery_snapshot(
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Option<RecoverySnapshot>> {
+        Ok(app_state
+            .lock_or_reset("recovery", &app_state.recovery)
+            .clone())
+    }
+
+    /// Restores or discards the unsaved tab contents of the previous session which ended uncleanly.
+    /// A recovered tab replaces the content of the same open tab as an unsaved change; a tab no longer
+    /// open is reopened with the recovered content.
+    ///
+    /// # Arguments
+    /// `restore` - `true` to restore the unsaved tab contents; `false` to discard them.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the contents were restored or discarded; `false` if there was nothing to recover. Error otherwise.
+    pub async fn restore_recovery_snapshot(
+        restore: bool,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let snapshot = match app_state
+            .lock_or_reset("recovery", &app_state.recovery)
+            .take()
+        {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
+        };
+
+        if restore {
+            for mut recovered in snapshot.tabs {
+                {
+                    let mut tabs = app_state.lock_file_tabs();
+                    if let Some(tab) = tabs.iter_mut().find(|tab| {
+                        tab.uid == recovered.uid
+                            && tab.file_name == recovered.file_name
+                            && tab.file_name_path == recovered.file_name_path
+                    }) {
+                        tab.content = recovered.content;
+                        app_state.discard_edited_content(tab.uid);
+                        // The recovered content is an unsaved change of the file.
+                        tab.modified_at_state = Some(snapshot.saved_at);
+                        continue;
+                    }
+                }
+
+                {
+                    let mut ids = app_state.lock_file_ids();
+                    let new_id = first_missing_in_sequence(&ids);
+                    ids.push(new_id);
+                    recovered.uid = new_id;
+                }
+                recovered.modified_at_state = Some(snapshot.saved_at);
+                assign_history_id(&mut recovered);
+                app_state.lock_file_tabs().push(recovered);
+            }
+
+            app_state.sync_file_watcher();
+        }
+
+        if let Err(e) = remove_snapshot() {
+            eprintln!("{}", e);
+        }
+
+        TauriCommands::save_open_tabs(app_state).await
+    }
+}
@@ -0,0 +1,71 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{fs, path::PathBuf};
+
+use crate::{
+    batch::{run_batch, BatchReportFormat, BatchSummary},
+    error::{CommandError, CommandResult},
+    tauri_commands::TauriCommands,
+};
+
+impl TauriCommands {
+    /// Runs every JavaScript and TypeScript file of a directory in parallel isolates.
+    ///
+    /// # Arguments
+    /// `directory` - The directory of the script files.
+    /// `jobs` - The number of the files to run in parallel; `None` to use the number of the CPU cores.
+    ///
+    /// # Returns
+    /// The summary of the batch run; Error if the directory could not be read.
+    pub async fn run_batch(directory: String, jobs: Option<usize>) -> CommandResult<BatchSummary> {
+        let path = PathBuf::from(&directory);
+
+        match tokio::task::spawn_blocking(move || run_batch(&path, jobs, None)).await {
+            Ok(Ok(summary)) => Ok(summary),
+            Ok(Err(e)) => Err(CommandError::directory_read_failed(&directory, e)),
+            Err(e) => Err(CommandError::task_failed(e.to_string())),
+        }
+    }
+
+    /// Exports the summary of a batch run into a report file.
+    ///
+    /// # Arguments
+    /// `summary` - The summary of the batch run.
+    /// `format` - The report format.
+    /// `file_name` - The file name and path to save the report into.
+    ///
+    /// # Returns
+    /// `true` if the report was saved successfully; Error otherwise.
+    pub async fn save_batch_report(
+        summary: BatchSummary,
+        format: BatchReportFormat,
+        file_name: String,
+    ) -> CommandResult<bool> {
+        match fs::write(&file_name, summary.format(format)) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(CommandError::file_write_failed(&file_name, &e)),
+        }
+    }
+}
@@ -24,6 +24,7 @@ SOFTWARE.
 
 use crate::{
     config::{get_app_config, set_app_config, AppConfig},
+    error::CommandResult,
     tauri_commands::TauriCommands,
 };
 
@@ -43,7 +44,7 @@ impl TauriCommands {
     ///
     /// # Returns
     /// Application settings.
-    pub async fn load_settings() -> Result<AppConfig, String> {
+    pub async fn load_settings() -> CommandResult<AppConfig> {
         let config = get_app_config();
         Ok(config)
     }
@@ -0,0 +1,164 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    error::{CommandError, CommandResult},
+    tauri_commands::TauriCommands,
+    text_encoding::{encode_text, LineEnding, TextEncoding},
+    types::{AppState, FileTabData},
+    utils::read_file_contents,
+};
+
+impl TauriCommands {
+    /// Converts the file of a tab into another text encoding and line endings. The file is written with
+    /// them on the next save.
+    ///
+    /// # Arguments
+    /// `data` - The file data with the editor content.
+    /// `encoding` - The new text encoding of the file.
+    /// `line_ending` - The new line endings of the file.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the file was converted successfully; Error otherwise.
+    ///
+    /// An encoding error if the editor content has a character the new encoding cannot represent.
+    pub async fn convert_file_encoding(
+        data: FileTabData,
+        encoding: TextEncoding,
+        line_ending: LineEnding,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let mut tabs = app_state.lock_file_tabs();
+        let tab = match tabs.iter_mut().find(|tab| tab.uid == data.uid) {
+            Some(tab) => tab,
+            None => {
+                return Err(CommandError::tab_not_found(data.uid));
+            }
+        };
+
+        let content = match data.content.or_else(|| tab.content.clone()) {
+            Some(content) => content,
+            None => {
+                return Err(CommandError::file_content_not_specified());
+            }
+        };
+
+        if tab.read_only {
+            let file = tab.file_name_path.as_ref().unwrap_or(&tab.file_name);
+            return Err(CommandError::file_read_only(file));
+        }
+
+        if let Err(character) = encode_text(&content, encoding, line_ending) {
+            let file = tab.file_name_path.as_ref().unwrap_or(&tab.file_name);
+            return Err(CommandError::encoding_failed(file, encoding, character));
+        }
+
+        tab.content = Some(content);
+        if tab.encoding != encoding || tab.line_ending != line_ending {
+            tab.encoding = encoding;
+            tab.line_ending = line_ending;
+            // The file in the file system differs from the converted file until it is saved.
+            tab.modified_at_state = Some(Utc::now());
+        }
+
+        Ok(true)
+    }
+
+    /// Reopens the file of a tab from the file system decoding it with the specified text encoding
+    /// instead of the detected one. The unsaved changes of the tab are discarded.
+    ///
+    /// # Arguments
+    /// `data` - The data of the file to reopen.
+    /// `encoding` - The text encoding to decode the file with.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the file was reopened successfully; `false` if the file is temporary. Error otherwise.
+    pub async fn reopen_file_with_encoding(
+        data: FileTabData,
+        encoding: TextEncoding,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let (file_name_path, read_only_page) = {
+            let mut tabs = app_state.lock_file_tabs();
+            match tabs.iter_mut().find(|tab| tab.uid == data.uid) {
+                // Temporary files have nothing to reopen.
+                Some(tab) if tab.is_temporary => return Ok(false),
+                Some(tab) => match &tab.file_name_path {
+                    Some(file_name_path) => {
+                        // A file opened in pages rereads the current page with the encoding.
+                        if tab.read_only {
+                            tab.encoding = encoding;
+                        }
+                        (file_name_path.clone(), tab.read_only.then_some(tab.page))
+                    }
+                    None => {
+                        return Err(CommandError::file_path_not_specified());
+                    }
+                },
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
+            }
+        };
+
+        if let Some(page) = read_only_page {
+            return TauriCommands::read_file_page(data, page, app_state).await;
+        }
+
+        let data_file = match read_file_contents(file_name_path.as_str(), Some(encoding)).await {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(CommandError::file_read_failed(&file_name_path, &e));
+            }
+        };
+
+        app_state
+            .lock_or_reset("saved_contents", &app_state.saved_contents)
+            .insert(data.uid, data_file.decoded.text.clone());
+
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            match tabs.iter_mut().find(|tab| tab.uid == data.uid) {
+                Some(tab) => {
+                    tab.content_hash = Some(data_file.content_hash);
+                    tab.content_size = Some(data_file.content_size);
+                    tab.content = Some(data_file.decoded.text);
+                    app_state.discard_edited_content(tab.uid);
+                    tab.encoding = data_file.decoded.encoding;
+                    tab.line_ending = data_file.decoded.line_ending;
+                    tab.modified_at = data_file.modified_at;
+                    tab.modified_at_state = data_file.modified_at;
+                }
+                None => return Err(CommandError::tab_not_found(data.uid)),
+            }
+        }
+
+        Ok(true)
+    }
+}
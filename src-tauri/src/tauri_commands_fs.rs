@@ -22,20 +22,27 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-use std::path::Path;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
 use chrono::{DateTime, Utc};
-use rust_i18n::t;
 use tauri::State;
-use tokio::{
-    fs::{self, File},
-    io::AsyncWriteExt,
-};
+use tokio::fs;
 
 use crate::{
+    config::get_app_config,
+    error::{CommandError, CommandResult},
+    file_save::save_file_atomically,
+    language::{detect_language, language_by_extension, language_by_shebang},
+    local_history::RevisionKind,
     tauri_commands::TauriCommands,
+    text_encoding::{decode_text, encode_text},
     types::{AppState, FileTabData},
-    utils::{first_missing_in_sequence, get_file_contents_and_modified_at},
+    utils::{
+        first_missing_in_sequence, hash_content, is_unchanged_by_metadata, read_file_contents,
+    },
 };
 
 impl TauriCommands {
@@ -52,34 +59,40 @@ impl TauriCommands {
     pub async fn is_file_changed_in_fs(
         data: FileTabData,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        let tab = match app_state.file_tabs.lock() {
-            Ok(tabs) => {
-                let tab = tabs.iter().find(|tab| tab.uid == data.uid);
-
-                match tab {
-                    // Do a light copy dropping the contents of the file.
-                    // The MutexGuard prevents from returning the protected data out of the code block.
-                    Some(tab) => FileTabData {
-                        uid: tab.uid,
-                        path: tab.path.clone(),
-                        file_name: tab.file_name.clone(),
-                        is_temporary: tab.is_temporary,
-                        script_language: tab.script_language.clone(),
-                        content: None,
-                        modified_at: tab.modified_at.clone(),
-                        file_name_path: tab.file_name_path.clone(),
-                        modified_at_state: tab.modified_at_state.clone(),
-                        evalueate_per_line: tab.evalueate_per_line,
-                    },
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
-                    }
+    ) -> CommandResult<bool> {
+        let tab = {
+            let tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter().find(|tab| tab.uid == data.uid);
+
+            match tab {
+                // Do a light copy dropping the contents of the file.
+                // The MutexGuard prevents from returning the protected data out of the code block.
+                Some(tab) => FileTabData {
+                    uid: tab.uid,
+                    path: tab.path.clone(),
+                    file_name: tab.file_name.clone(),
+                    is_temporary: tab.is_temporary,
+                    script_language: tab.script_language.clone(),
+                    jsx: tab.jsx,
+                    module_kind: tab.module_kind,
+                    content: None,
+                    modified_at: tab.modified_at.clone(),
+                    file_name_path: tab.file_name_path.clone(),
+                    modified_at_state: tab.modified_at_state.clone(),
+                    evalueate_per_line: tab.evalueate_per_line,
+                    content_hash: tab.content_hash.clone(),
+                    content_size: tab.content_size,
+                    history_id: tab.history_id.clone(),
+                    encoding: tab.encoding,
+                    line_ending: tab.line_ending,
+                    read_only: tab.read_only,
+                    page: tab.page,
+                    page_count: tab.page_count,
+                },
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
                 }
             }
-            Err(e) => {
-                return Err(e.to_string());
-            }
         };
 
         // Abort the check here if the file has no last modified date.
@@ -105,7 +118,10 @@ impl TauriCommands {
         let meta_data = match fs::metadata(tab.file_name_path.clone().unwrap()).await {
             Ok(meta_data) => meta_data,
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(
+                    tab.file_name_path.as_deref().unwrap_or_default(),
+                    &e,
+                ));
             }
         };
 
@@ -122,26 +138,46 @@ impl TauriCommands {
             None => None,
         };
 
-        // Whatever the file content load question was answered, the file's timestamp is updated.
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+        // The metadata is a fast pre-check; the file contents are not read if the date and the size are unchanged.
+        if is_unchanged_by_metadata(&meta_data, tab.modified_at, tab.content_size) {
+            return Ok(false);
+        }
 
-                match tab {
-                    Some(tab) => {
-                        tab.modified_at = modified_at;
-                    }
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
+        // A changed date alone does not mean changed contents, e.g. after `touch` or with a coarse date resolution.
+        let changed = match &tab.content_hash {
+            Some(content_hash) => match fs::read(&path).await {
+                Ok(contents) => hash_content(&contents) != *content_hash,
+                Err(e) => {
+                    return Err(CommandError::file_read_failed(
+                        &path.display().to_string(),
+                        &e,
+                    ));
+                }
+            },
+            // Without a hash only a changed date tells a change.
+            None => modified_at != tab.modified_at,
+        };
+
+        // Whatever the file content load question was answered, the file's timestamp is updated.
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+
+            match tab {
+                Some(tab) => {
+                    tab.modified_at = modified_at;
+                    // The size is known once the contents matched the hash, e.g. for a tab of an older version.
+                    if !changed && tab.content_hash.is_some() {
+                        tab.content_size = Some(meta_data.len());
                     }
                 }
-            }
-            Err(e) => {
-                return Err(e.to_string());
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
             }
         }
 
-        Ok(modified_at != tab.modified_at)
+        Ok(changed)
     }
 
     /// Checks if the file in the application state is missing in the file system.
@@ -157,31 +193,37 @@ impl TauriCommands {
     pub async fn is_existing_file_missing_in_fs(
         data: FileTabData,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        let tab = match app_state.file_tabs.lock() {
-            Ok(tabs) => {
-                let tab = tabs.iter().find(|tab| tab.uid == data.uid);
-                match tab {
-                    Some(tab) => FileTabData {
-                        uid: tab.uid,
-                        path: tab.path.clone(),
-                        file_name: tab.file_name.clone(),
-                        is_temporary: tab.is_temporary,
-                        script_language: tab.script_language.clone(),
-                        content: None,
-                        modified_at: tab.modified_at.clone(),
-                        file_name_path: tab.file_name_path.clone(),
-                        modified_at_state: tab.modified_at_state.clone(),
-                        evalueate_per_line: tab.evalueate_per_line,
-                    },
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
-                    }
+    ) -> CommandResult<bool> {
+        let tab = {
+            let tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter().find(|tab| tab.uid == data.uid);
+            match tab {
+                Some(tab) => FileTabData {
+                    uid: tab.uid,
+                    path: tab.path.clone(),
+                    file_name: tab.file_name.clone(),
+                    is_temporary: tab.is_temporary,
+                    script_language: tab.script_language.clone(),
+                    jsx: tab.jsx,
+                    module_kind: tab.module_kind,
+                    content: None,
+                    modified_at: tab.modified_at.clone(),
+                    file_name_path: tab.file_name_path.clone(),
+                    modified_at_state: tab.modified_at_state.clone(),
+                    evalueate_per_line: tab.evalueate_per_line,
+                    content_hash: tab.content_hash.clone(),
+                    content_size: tab.content_size,
+                    history_id: tab.history_id.clone(),
+                    encoding: tab.encoding,
+                    line_ending: tab.line_ending,
+                    read_only: tab.read_only,
+                    page: tab.page,
+                    page_count: tab.page_count,
+                },
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
                 }
             }
-            Err(e) => {
-                return Err(e.to_string());
-            }
         };
 
         let path = tab.file_name_path.clone().unwrap();
@@ -191,7 +233,10 @@ impl TauriCommands {
         let exists = match Path::try_exists(path) {
             Ok(exists) => exists,
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(
+                    &path.display().to_string(),
+                    &e,
+                ));
             }
         };
 
@@ -210,17 +255,24 @@ impl TauriCommands {
     ///
     /// # Returns
     /// `true` if the file was opened successfully; Error otherwise.
+    ///
+    /// A file too large error if the file is larger than the file size limit setting; the file can be opened
+    /// read-only in pages with [TauriCommands::open_file_in_pages] instead.
     pub async fn open_existing_file(
         file_name: String,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
+    ) -> CommandResult<bool> {
         let meta_data = fs::metadata(file_name.clone()).await;
+        let size_limit = get_app_config().file_size_limit_mb * 1_000_000;
 
         let modified_at = match meta_data {
             Ok(meta_data) => {
-                if meta_data.len() > 10000000 {
-                    // Limit the file size to 10 MB
-                    return Err(t!("messages.fileTooLargeHardCoded", file = file_name).into_owned());
+                if meta_data.len() > size_limit {
+                    return Err(CommandError::file_too_large(
+                        &file_name,
+                        meta_data.len(),
+                        size_limit,
+                    ));
                 }
 
                 match meta_data.modified() {
@@ -230,7 +282,7 @@ impl TauriCommands {
                 }
             }
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(&file_name, &e));
             }
         };
 
@@ -239,16 +291,19 @@ impl TauriCommands {
             None => None,
         };
 
-        let contents = fs::read_to_string(file_name.clone()).await;
+        let contents = fs::read(file_name.clone()).await;
 
         let mut tab_data = match contents {
             Ok(contents) => {
+                let content_hash = hash_content(&contents);
+                let content_size = contents.len() as u64;
+                let decoded = decode_text(&contents);
                 let path = Path::new(&file_name);
                 let file_name_path = file_name.clone();
+                let language = detect_language(path, &decoded.text);
 
                 // The following should be safe to unwrap() as the None options
-                // shouldn't occur in case of file name being in format of path/file_name.[ts/js]
-                let extension = path.extension().unwrap().to_str().unwrap().to_string();
+                // shouldn't occur in case of file name being in format of path/file_name.
                 let file_name = path.file_name().unwrap().to_str().unwrap().to_string();
                 let path = path.parent().unwrap().to_str().unwrap().to_string();
 
@@ -257,48 +312,50 @@ impl TauriCommands {
                     path: Some(path),
                     file_name: file_name,
                     is_temporary: false,
-                    script_language: if extension == "js" {
-                        "javascript"
-                    } else {
-                        "typescript"
-                    }
-                    .to_string(),
-                    content: Some(contents),
+                    script_language: language.editor_language().to_string(),
+                    jsx: language.is_jsx(),
+                    module_kind: language.module_kind,
+                    content: Some(decoded.text),
                     modified_at: modified_at,
                     file_name_path: Some(file_name_path),
                     modified_at_state: modified_at,
                     evalueate_per_line: false,
+                    content_hash: Some(content_hash),
+                    content_size: Some(content_size),
+                    history_id: None,
+                    encoding: decoded.encoding,
+                    line_ending: decoded.line_ending,
+                    read_only: false,
+                    page: 0,
+                    page_count: 0,
                 };
                 tab_data
             }
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(&file_name, &e));
             }
         };
 
-        match app_state.file_ids.lock() {
-            Ok(mut ids) => {
-                let mut new_ids: Vec<i32> = ids.clone();
+        {
+            let mut ids = app_state.lock_file_ids();
+            let mut new_ids: Vec<i32> = ids.clone();
 
-                let new_id = first_missing_in_sequence(&new_ids);
-                new_ids.push(new_id);
-                *ids = new_ids;
-                tab_data.uid = new_id;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
+            let new_id = first_missing_in_sequence(&new_ids);
+            new_ids.push(new_id);
+            *ids = new_ids;
+            tab_data.uid = new_id;
         }
 
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                tabs.push(tab_data);
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
+        if let Some(contents) = &tab_data.content {
+            app_state
+                .lock_or_reset("saved_contents", &app_state.saved_contents)
+                .insert(tab_data.uid, contents.clone());
         }
 
+        app_state.add_recent_file(&file_name);
+        app_state.lock_file_tabs().push(tab_data);
+        app_state.sync_file_watcher();
+
         Ok(true)
     }
 
@@ -313,59 +370,65 @@ impl TauriCommands {
     pub async fn reload_file_contents(
         data: FileTabData,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        let file_name_path = match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
-
-                match tab {
-                    Some(tab) => {
-                        // Temporary files cannot be reloaded
-                        if tab.is_temporary {
-                            return Ok(false);
-                        }
+    ) -> CommandResult<bool> {
+        let (file_name_path, read_only_page) = {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+
+            match tab {
+                Some(tab) => {
+                    // Temporary files cannot be reloaded
+                    if tab.is_temporary {
+                        return Ok(false);
+                    }
 
-                        let file_name_path = match &tab.file_name_path {
-                            Some(file_name_path) => file_name_path.clone(),
+                    let file_name_path = match &tab.file_name_path {
+                        Some(file_name_path) => file_name_path.clone(),
 
-                            None => {
-                                return Err(t!("messages.filePathNotSpecified").into_owned());
-                            }
-                        };
+                        None => {
+                            return Err(CommandError::file_path_not_specified());
+                        }
+                    };
 
-                        file_name_path
-                    }
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
-                    }
+                    (file_name_path, tab.read_only.then_some(tab.page))
+                }
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
                 }
-            }
-            Err(e) => {
-                return Err(e.to_string());
             }
         };
 
-        let data_file = match get_file_contents_and_modified_at(file_name_path.as_str()).await {
+        // A file opened in pages reloads only the current page.
+        if let Some(page) = read_only_page {
+            return TauriCommands::read_file_page(data, page, app_state).await;
+        }
+
+        let data_file = match read_file_contents(file_name_path.as_str(), None).await {
             Ok(data) => data,
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(&file_name_path, &e));
             }
         };
 
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
-                match tab {
-                    Some(tab) => {
-                        tab.content = Some(data_file.0);
-                        tab.modified_at = data_file.1;
-                        tab.modified_at_state = data_file.1;
-                    }
-                    None => return Err(t!("messages.failedFindFileBackendState").into_owned()),
+        app_state
+            .lock_or_reset("saved_contents", &app_state.saved_contents)
+            .insert(data.uid, data_file.decoded.text.clone());
+
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+            match tab {
+                Some(tab) => {
+                    tab.content_hash = Some(data_file.content_hash);
+                    tab.content_size = Some(data_file.content_size);
+                    tab.content = Some(data_file.decoded.text);
+                    app_state.discard_edited_content(tab.uid);
+                    tab.encoding = data_file.decoded.encoding;
+                    tab.line_ending = data_file.decoded.line_ending;
+                    tab.modified_at = data_file.modified_at;
+                    tab.modified_at_state = data_file.modified_at;
                 }
-            }
-            Err(e) => {
-                return Err(e.to_string());
+                None => return Err(CommandError::tab_not_found(data.uid)),
             }
         }
         Ok(true)
@@ -382,26 +445,23 @@ impl TauriCommands {
     pub async fn set_current_file_keep_in_editor(
         data: FileTabData,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
-
-                match tab {
-                    Some(tab) => {
-                        tab.is_temporary = true;
-                        tab.modified_at = None;
-                        tab.modified_at_state = Some(Utc::now());
-                    }
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
-                    }
+    ) -> CommandResult<bool> {
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+
+            match tab {
+                Some(tab) => {
+                    tab.is_temporary = true;
+                    tab.modified_at = None;
+                    tab.modified_at_state = Some(Utc::now());
+                }
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
                 }
-            }
-            Err(e) => {
-                return Err(e.to_string());
             }
         };
+        app_state.sync_file_watcher();
 
         Ok(true)
     }
@@ -411,31 +471,40 @@ impl TauriCommands {
     /// # Arguments
     /// `data` - The data of the file to save.
     /// `file_name_path` - The path of the file to save.
+    /// `overwrite` - A flag indicating whether to overwrite the file even if it changed in the file system after it was loaded.
     /// `app_state` - The Tauri application state.
     ///
     /// # Returns
     /// `true` if the file was saved successfully; Error otherwise.
+    ///
+    /// A save conflict error if the file changed in the file system after it was loaded and `overwrite` is not set.
     pub async fn save_file_contents(
         data: FileTabData,
         file_name_path: Option<String>,
+        overwrite: bool,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
+    ) -> CommandResult<bool> {
         // Get the matching file data from the application state.
-        let mut existing_data = match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
-                match tab {
-                    Some(tab) => tab.clone(),
-                    None => {
-                        return Err(t!("messages.failedFindFileBackendState").into_owned());
-                    }
+        let mut existing_data = {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+            match tab {
+                Some(tab) => tab.clone(),
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
                 }
             }
-            Err(e) => {
-                return Err(e.to_string());
-            }
         };
 
+        // A page of a file opened in pages cannot be saved over the file.
+        if existing_data.read_only {
+            let file = existing_data
+                .file_name_path
+                .as_ref()
+                .unwrap_or(&existing_data.file_name);
+            return Err(CommandError::file_read_only(file));
+        }
+
         // If saving an existing file the file should exist.
         if existing_data.file_name_path.is_some() && !existing_data.is_temporary {
             let path = existing_data.file_name_path.clone().unwrap();
@@ -444,18 +513,21 @@ impl TauriCommands {
             match Path::try_exists(path) {
                 Ok(exists) => {
                     if !exists {
-                        return Err(t!("messages.theFileDoesNotExist").into_owned());
+                        return Err(CommandError::file_not_found(&path.display().to_string()));
                     }
                 }
                 Err(e) => {
-                    return Err(e.to_string());
+                    return Err(CommandError::file_read_failed(
+                        &path.display().to_string(),
+                        &e,
+                    ));
                 }
             }
         }
 
         // Verify that the file name and path are specified.
         if file_name_path.is_none() && existing_data.file_name_path.is_none() {
-            return Err(t!("messages.filePathNotSpecified").into_owned());
+            return Err(CommandError::file_path_not_specified());
         }
 
         // Determine the file name and path to save the contents into.
@@ -464,42 +536,84 @@ impl TauriCommands {
             None => match &existing_data.file_name_path {
                 Some(file_name_path) => file_name_path.clone(),
                 None => {
-                    return Err(t!("messages.filePathNotSpecified").into_owned());
+                    return Err(CommandError::file_path_not_specified());
                 }
             },
         };
 
+        // Refuse to overwrite the changes made outside the editor unless asked to.
+        if !overwrite
+            && !existing_data.is_temporary
+            && existing_data.file_name_path.as_deref() == Some(file_name_path.as_str())
+        {
+            match is_changed_since_loaded(&file_name_path, &existing_data).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    return Err(CommandError::save_conflict(&file_name_path));
+                }
+                Err(e) => {
+                    return Err(CommandError::file_read_failed(&file_name_path, &e));
+                }
+            }
+        }
+
         // Get the file contents to be saved.
         let file_contents = match data.content {
             Some(file_contents) => file_contents,
             None => {
-                return Err(t!("messages.fileContentNotSpecified").into_owned());
+                return Err(CommandError::file_content_not_specified());
             }
         };
 
-        let file_bytes = file_contents.as_bytes();
+        // Encode the file contents with the encoding and the line endings of the file.
+        let file_bytes = match encode_text(
+            &file_contents,
+            existing_data.encoding,
+            existing_data.line_ending,
+        ) {
+            Ok(file_bytes) => file_bytes,
+            Err(character) => {
+                return Err(CommandError::encoding_failed(
+                    &file_name_path,
+                    existing_data.encoding,
+                    character,
+                ));
+            }
+        };
 
-        // Save the file contents to the file path overriding any existing contents.
-        match File::create(file_name_path.clone()).await {
-            Ok(mut file) => match file.write_all(file_bytes).await {
-                Ok(_) => {
-                    existing_data.content = Some(file_contents);
-                    existing_data.is_temporary = false;
-                    existing_data.file_name_path = Some(file_name_path.clone());
-                }
-                Err(e) => {
-                    return Err(e.to_string());
-                }
-            },
+        // Save the file contents to the file path replacing any existing contents atomically.
+        let save_path = PathBuf::from(&file_name_path);
+        let save_bytes = file_bytes.clone();
+        let backup_count = get_app_config().save_backup_count;
+        let saved = tokio::task::spawn_blocking(move || {
+            save_file_atomically(&save_path, &save_bytes, backup_count)
+        })
+        .await;
+
+        match saved {
+            Ok(Ok(_)) => {
+                existing_data.content_hash = Some(hash_content(&file_bytes));
+                existing_data.content_size = Some(file_bytes.len() as u64);
+                app_state
+                    .lock_or_reset("saved_contents", &app_state.saved_contents)
+                    .insert(data.uid, file_contents.clone());
+                existing_data.content = Some(file_contents);
+                existing_data.is_temporary = false;
+                existing_data.file_name_path = Some(file_name_path.clone());
+                app_state.add_recent_file(&file_name_path);
+            }
+            Ok(Err(e)) => {
+                return Err(CommandError::file_write_failed(&file_name_path, &e));
+            }
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::task_failed(e.to_string()));
             }
         }
 
         let meta_data = match fs::metadata(existing_data.file_name_path.clone().unwrap()).await {
             Ok(meta_data) => meta_data,
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::file_read_failed(&file_name_path, &e));
             }
         };
 
@@ -510,39 +624,79 @@ impl TauriCommands {
 
         let path = Path::new(file_name_path.as_str());
 
+        // A file without a known extension or shebang line keeps the language of the tab.
+        let content = existing_data.content.as_deref().unwrap_or_default();
+        if let Some(language) = language_by_extension(path).or_else(|| language_by_shebang(content))
+        {
+            existing_data.script_language = language.editor_language().to_string();
+            existing_data.jsx = language.is_jsx();
+            existing_data.module_kind = language.module_kind;
+        }
+
         // The following should be safe to unwrap() as the None options
-        // shouldn't occur in case of file name being in format of path/file_name.[ts/js]
-        let extension = path.extension().unwrap().to_str().unwrap().to_string();
+        // shouldn't occur in case of file name being in format of path/file_name.
         let file_name = path.file_name().unwrap().to_str().unwrap().to_string();
         let path = path.parent().unwrap().to_str().unwrap().to_string();
 
         existing_data.file_name = file_name;
         existing_data.path = Some(path);
-        existing_data.script_language = if extension == "js" {
-            "javascript"
-        } else {
-            "typescript"
-        }
-        .to_string();
 
         existing_data.modified_at_state = existing_data.modified_at.clone();
 
+        // A failure to record the saved version must not fail the save itself.
+        if let Err(e) = app_state
+            .record_revision(&existing_data, RevisionKind::Save)
+            .await
+        {
+            eprintln!("{}", e);
+        }
+
         // Update the file data in the application state.
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
-                match tab {
-                    Some(tab) => {
-                        *tab = existing_data;
-                    }
-                    None => return Err(t!("messages.failedFindFileBackendState").into_owned()),
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = tabs.iter_mut().find(|tab| tab.uid == data.uid);
+            match tab {
+                Some(tab) => {
+                    *tab = existing_data;
                 }
-            }
-            Err(e) => {
-                return Err(e.to_string());
+                None => return Err(CommandError::tab_not_found(data.uid)),
             }
         }
+        app_state.sync_file_watcher();
 
         Ok(true)
     }
 }
+
+/// Checks whether the file changed in the file system after it was last opened, reloaded or saved. The last
+/// modified date and the size are a fast pre-check and the content hash decides, so a `touch` is not a change.
+///
+/// # Arguments
+/// `file_name_path` - The file.
+/// `tab` - The file data of the tab in the application state.
+///
+/// # Returns
+/// `true` if the file contents changed; `false` otherwise. Error if the file could not be read.
+async fn is_changed_since_loaded(file_name_path: &str, tab: &FileTabData) -> io::Result<bool> {
+    let metadata = fs::metadata(file_name_path).await?;
+    let modified_at: Option<DateTime<Utc>> = metadata
+        .modified()
+        .ok()
+        .map(|modified_at| modified_at.into());
+
+    if is_unchanged_by_metadata(&metadata, tab.modified_at_state, tab.content_size) {
+        return Ok(false);
+    }
+
+    match &tab.content_hash {
+        Some(content_hash) => {
+            let contents = fs::read(file_name_path).await?;
+            Ok(hash_content(&contents) != *content_hash)
+        }
+        // Without a hash only a file modified after the tab state was changed is known to be changed.
+        None => Ok(match (modified_at, tab.modified_at_state) {
+            (Some(modified_at), Some(modified_at_state)) => modified_at > modified_at_state,
+            _ => false,
+        }),
+    }
+}
@@ -0,0 +1,244 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::io;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    config::{get_app_config, get_settings_dir},
+    error::{CommandError, CommandResult},
+    local_history::{history_key, LocalHistory, Revision, RevisionDiff, RevisionKind},
+    tauri_commands::TauriCommands,
+    types::{AppState, FileTabData},
+};
+
+/// The name of the local history directory in the settings directory.
+const HISTORY_DIR_NAME: &str = "history";
+
+impl AppState {
+    /// Records the content of a tab as a revision in the local history. The revision is not recorded
+    /// if the content equals the content of the latest revision of the file.
+    ///
+    /// # Arguments
+    /// * `tab` - The tab with the content to record.
+    /// * `kind` - The event which created the revision.
+    ///
+    /// # Returns
+    /// The recorded revision; `None` if the tab has no content or the content was unchanged. Error otherwise.
+    pub async fn record_revision(
+        &self,
+        tab: &FileTabData,
+        kind: RevisionKind,
+    ) -> CommandResult<Option<Revision>> {
+        let Some(content) = tab.content.clone() else {
+            return Ok(None);
+        };
+
+        let key = history_key(tab);
+        let file_name = tab.file_name.clone();
+        let size_limit = get_app_config().history_size_limit_mb * 1_000_000;
+
+        self.with_local_history(move |history| {
+            history.add(&key, &file_name, kind, &content, size_limit)
+        })
+        .await
+    }
+
+    /// Runs an operation on the local history in the blocking thread pool. The operations are serialized
+    /// by the local history lock.
+    ///
+    /// # Arguments
+    /// * `operation` - The operation to run.
+    ///
+    /// # Returns
+    /// The result of the operation; Error if the local history could not be read or written.
+    async fn with_local_history<T: Send + 'static>(
+        &self,
+        operation: impl FnOnce(LocalHistory) -> io::Result<T> + Send + 'static,
+    ) -> CommandResult<T> {
+        let history = local_history()?;
+
+        let _guard = self.local_history.lock().await;
+        match tokio::task::spawn_blocking(move || operation(history)).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(CommandError::history_failed(e.to_string())),
+            Err(e) => Err(CommandError::task_failed(e.to_string())),
+        }
+    }
+}
+
+impl TauriCommands {
+    /// Records the content of a tab as a run revision in the local history.
+    ///
+    /// # Arguments
+    /// `data` - The file data with the editor content.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The recorded revision; `None` if the content equals the latest revision. Error otherwise.
+    pub async fn record_run_revision(
+        data: FileTabData,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Option<Revision>> {
+        let mut tab = find_tab(data.uid, app_state)?;
+        if data.content.is_some() {
+            tab.content = data.content;
+        }
+
+        app_state.record_revision(&tab, RevisionKind::Run).await
+    }
+
+    /// Gets the revisions of the file of a tab in the local history.
+    ///
+    /// # Arguments
+    /// `data` - The data of the file.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The revisions of the file, the newest first; Error if the local history could not be read.
+    pub async fn get_history_revisions(
+        data: FileTabData,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<Revision>> {
+        let key = history_key(&find_tab(data.uid, app_state)?);
+
+        app_state
+            .with_local_history(move |history| history.list(&key))
+            .await
+    }
+
+    /// Compares two revisions in the local history.
+    ///
+    /// # Arguments
+    /// `from_id` - The identifier of the older revision.
+    /// `to_id` - The identifier of the newer revision.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The difference of the revisions; Error if either revision was not found.
+    pub async fn diff_history_revisions(
+        from_id: u64,
+        to_id: u64,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<RevisionDiff> {
+        let diff = app_state
+            .with_local_history(move |history| match history.diff(from_id, to_id)? {
+                Some(diff) => Ok(Ok(diff)),
+                None => {
+                    // Report the first of the revisions not found.
+                    let missing_id = match history.read(from_id) {
+                        Ok(Some(_)) => to_id,
+                        _ => from_id,
+                    };
+                    Ok(Err(missing_id))
+                }
+            })
+            .await?;
+
+        diff.map_err(CommandError::revision_not_found)
+    }
+
+    /// Restores a revision from the local history as the content of a tab and saves the open tabs. The file
+    /// in the file system is not changed until the tab is saved.
+    ///
+    /// # Arguments
+    /// `data` - The data of the file.
+    /// `id` - The identifier of the revision to restore.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The restored content to update the editor with; Error otherwise.
+    pub async fn restore_history_revision(
+        data: FileTabData,
+        id: u64,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<String> {
+        let revision = app_state
+            .with_local_history(move |history| history.read(id))
+            .await?;
+
+        let content = match revision {
+            Some((_, content)) => content,
+            None => {
+                return Err(CommandError::revision_not_found(id));
+            }
+        };
+
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            let tab = match tabs.iter_mut().find(|tab| tab.uid == data.uid) {
+                Some(tab) => tab,
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
+            };
+
+            if tab.read_only {
+                let file = tab.file_name_path.as_ref().unwrap_or(&tab.file_name);
+                return Err(CommandError::file_read_only(file));
+            }
+
+            if tab.content.as_deref() != Some(content.as_str()) {
+                tab.content = Some(content.clone());
+                app_state.discard_edited_content(tab.uid);
+                // The file in the file system differs from the restored content until it is saved.
+                tab.modified_at_state = Some(Utc::now());
+            }
+        }
+
+        // The restored content is persisted, as the frontend updates the open tabs only after a pause in the editing.
+        TauriCommands::save_open_tabs(app_state).await?;
+
+        Ok(content)
+    }
+}
+
+/// Gets the local history in the settings directory.
+///
+/// # Returns
+/// The local history; Error if the settings directory could not be determined.
+fn local_history() -> CommandResult<LocalHistory> {
+    match get_settings_dir() {
+        Ok(directory) => Ok(LocalHistory::new(directory.join(HISTORY_DIR_NAME))),
+        Err(e) => Err(CommandError::history_failed(e)),
+    }
+}
+
+/// Gets a copy of a tab from the application state.
+///
+/// # Arguments
+/// * `uid` - The identifier of the tab.
+/// * `app_state` - The Tauri application state.
+///
+/// # Returns
+/// The tab; Error if the tab was not found.
+fn find_tab(uid: i32, app_state: &AppState) -> CommandResult<FileTabData> {
+    let tabs = app_state.lock_file_tabs();
+    match tabs.iter().find(|tab| tab.uid == uid) {
+        Some(tab) => Ok(tab.clone()),
+        None => Err(CommandError::tab_not_found(uid)),
+    }
+}
@@ -0,0 +1,107 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::time::Duration;
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::get_app_config,
+    error::{CommandError, CommandResult},
+    live_runner::{LiveSession, LiveUpdate},
+    tauri_commands::TauriCommands,
+    types::AppState,
+};
+
+impl TauriCommands {
+    /// Pushes the script content of a tab into the live mode. The live mode session of the tab is started
+    /// on the first update. The content is evaluated after the debounce time and the result is emitted
+    /// to the frontend as the `live-result` event.
+    ///
+    /// # Arguments
+    /// `app_handle` - The Tauri application handle.
+    /// `tab_id` - The identifier of the tab.
+    /// `code` - The script code lines. The lines are joined together unless evaluated line by line.
+    /// `evaluate_per_line` - A flag indicating whether to evaluate each line separately.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the content was pushed successfully; Error otherwise.
+    pub async fn update_live_content(
+        app_handle: AppHandle,
+        tab_id: i32,
+        code: Vec<String>,
+        evaluate_per_line: bool,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let debounce = Duration::from_millis(get_app_config().live_mode_debounce_ms);
+
+        {
+            let mut sessions = app_state.lock_or_reset("live_sessions", &app_state.live_sessions);
+            let mut update = LiveUpdate {
+                code,
+                evaluate_per_line,
+            };
+
+            if let Some(session) = sessions.get(&tab_id) {
+                match session.update(update) {
+                    Ok(_) => return Ok(true),
+                    // The session thread has exited, so the session is restarted below.
+                    Err(returned) => update = returned,
+                }
+            }
+
+            let session = match LiveSession::start(app_handle, tab_id, debounce) {
+                Some(session) => session,
+                None => {
+                    return Err(CommandError::live_mode_failed(tab_id));
+                }
+            };
+
+            if session.update(update).is_err() {
+                return Err(CommandError::live_mode_failed(tab_id));
+            }
+
+            sessions.insert(tab_id, session);
+        }
+
+        Ok(true)
+    }
+
+    /// Stops the live mode of a tab.
+    ///
+    /// # Arguments
+    /// `tab_id` - The identifier of the tab.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the live mode was running for the tab; `false` otherwise. Error in case of failure.
+    pub async fn stop_live_mode(
+        tab_id: i32,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let mut sessions = app_state.lock_or_reset("live_sessions", &app_state.live_sessions);
+        Ok(sessions.remove(&tab_id).is_some())
+    }
+}
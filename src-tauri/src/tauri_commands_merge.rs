@@ -0,0 +1,174 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use tauri::State;
+
+use crate::{
+    error::{CommandError, CommandResult},
+    merge::{count_unresolved_conflicts, merge_three_way, FileMerge},
+    tauri_commands::TauriCommands,
+    text_encoding::encode_text,
+    types::{AppState, FileTabData},
+    utils::{hash_content, read_file_contents},
+};
+
+impl TauriCommands {
+    /// Merges the unsaved editor content of a tab with the changed file in the file system using the
+    /// file contents of the last open, reload or save as the common base.
+    ///
+    /// # Arguments
+    /// `data` - The file data with the editor content.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The merge; `None` if the editor content has no unsaved changes and the file can just be reloaded.
+    ///
+    /// Error if the file was not found in the application state or the file could not be read.
+    pub async fn merge_file_changes(
+        data: FileTabData,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Option<FileMerge>> {
+        let (file_name_path, content_hash, encoding, line_ending) = {
+            let tabs = app_state.lock_file_tabs();
+            match tabs.iter().find(|tab| tab.uid == data.uid) {
+                // A file opened read-only in pages has no changes to merge and is just reloaded.
+                Some(tab) if tab.read_only => return Ok(None),
+                Some(tab) => (
+                    tab.file_name_path.clone(),
+                    tab.content_hash.clone(),
+                    tab.encoding,
+                    tab.line_ending,
+                ),
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
+            }
+        };
+
+        let file_name_path = match file_name_path {
+            Some(file_name_path) => file_name_path,
+            None => {
+                return Err(CommandError::file_path_not_specified());
+            }
+        };
+
+        let ours = match data.content {
+            Some(content) => content,
+            None => {
+                return Err(CommandError::file_content_not_specified());
+            }
+        };
+
+        // The editor content is compared as it would be saved; content the encoding cannot represent is unsaved.
+        if let Ok(ours_bytes) = encode_text(&ours, encoding, line_ending) {
+            if content_hash.as_deref() == Some(hash_content(&ours_bytes).as_str()) {
+                return Ok(None);
+            }
+        }
+
+        let theirs = match read_file_contents(file_name_path.as_str(), Some(encoding)).await {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(CommandError::file_read_failed(&file_name_path, &e));
+            }
+        };
+
+        // Without a known base both sides are merged as new contents, i.e. the differing lines conflict.
+        let base = app_state
+            .lock_or_reset("saved_contents", &app_state.saved_contents)
+            .get(&data.uid)
+            .cloned()
+            .unwrap_or_default();
+
+        let (merged, conflicts) = merge_three_way(&base, &ours, &theirs.decoded.text);
+
+        Ok(Some(FileMerge {
+            merged,
+            conflicts,
+            theirs_hash: theirs.content_hash,
+            modified_at: theirs.modified_at,
+        }))
+    }
+
+    /// Saves the merged content of a tab into the file system if the file has not changed since the merge.
+    ///
+    /// # Arguments
+    /// `data` - The file data with the merged content.
+    /// `theirs_hash` - The hash of the file system content the merge was made against.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the merged content was saved successfully; Error if the content still has conflict markers,
+    /// the file changed after the merge or the file could not be saved.
+    pub async fn save_merged_file(
+        data: FileTabData,
+        theirs_hash: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let file_name_path = {
+            let tabs = app_state.lock_file_tabs();
+            match tabs.iter().find(|tab| tab.uid == data.uid) {
+                Some(tab) => tab.file_name_path.clone(),
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
+            }
+        };
+
+        let file_name_path = match file_name_path {
+            Some(file_name_path) => file_name_path,
+            None => {
+                return Err(CommandError::file_path_not_specified());
+            }
+        };
+
+        let content = match &data.content {
+            Some(content) => content,
+            None => {
+                return Err(CommandError::file_content_not_specified());
+            }
+        };
+
+        let conflicts = count_unresolved_conflicts(content);
+        if conflicts > 0 {
+            return Err(CommandError::merge_conflicts_unresolved(
+                &file_name_path,
+                conflicts,
+            ));
+        }
+
+        match read_file_contents(file_name_path.as_str(), None).await {
+            Ok(theirs) => {
+                if theirs.content_hash != theirs_hash {
+                    return Err(CommandError::file_changed_during_merge(&file_name_path));
+                }
+            }
+            Err(e) => {
+                return Err(CommandError::file_read_failed(&file_name_path, &e));
+            }
+        }
+
+        TauriCommands::save_file_contents(data, Some(file_name_path), true, app_state).await
+    }
+}
@@ -0,0 +1,182 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use tauri::State;
+use tokio::fs;
+
+use crate::{
+    error::{CommandError, CommandResult},
+    file_pages::{read_file_page, FilePage},
+    language::detect_language,
+    tauri_commands::TauriCommands,
+    text_encoding::{LineEnding, TextEncoding},
+    types::{AppState, FileTabData},
+    utils::first_missing_in_sequence,
+};
+
+impl TauriCommands {
+    /// Opens a file too large to edit read-only in pages. The first page is loaded as the content of the tab.
+    ///
+    /// # Arguments
+    /// `file_name` - The name of the file to open.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the file was opened successfully; Error otherwise.
+    pub async fn open_file_in_pages(
+        file_name: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let (first_page, modified_at) = read_page(&file_name, 0, None).await?;
+
+        let path = Path::new(&file_name);
+        let language = detect_language(path, &first_page.content);
+
+        // The following should be safe to unwrap() as the None options
+        // shouldn't occur in case of file name being in format of path/file_name.
+        let tab_file_name = path.file_name().unwrap().to_str().unwrap().to_string();
+        let tab_path = path.parent().unwrap().to_str().unwrap().to_string();
+
+        let mut tab_data = FileTabData {
+            uid: 0,
+            path: Some(tab_path),
+            file_name: tab_file_name,
+            is_temporary: false,
+            script_language: language.editor_language().to_string(),
+            jsx: language.is_jsx(),
+            module_kind: language.module_kind,
+            content: Some(first_page.content),
+            modified_at,
+            file_name_path: Some(file_name.clone()),
+            modified_at_state: modified_at,
+            evalueate_per_line: false,
+            content_hash: None,
+            content_size: None,
+            history_id: None,
+            encoding: first_page.encoding,
+            line_ending: LineEnding::default(),
+            read_only: true,
+            page: first_page.page,
+            page_count: first_page.page_count,
+        };
+
+        {
+            let mut ids = app_state.lock_file_ids();
+            let new_id = first_missing_in_sequence(&ids);
+            ids.push(new_id);
+            tab_data.uid = new_id;
+        }
+
+        app_state.add_recent_file(&file_name);
+        app_state.lock_file_tabs().push(tab_data);
+        app_state.sync_file_watcher();
+
+        Ok(true)
+    }
+
+    /// Loads a page of a file opened read-only in pages as the content of the tab.
+    ///
+    /// # Arguments
+    /// `data` - The data of the file.
+    /// `page` - The zero-based number of the page to load; the last page if out of range.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the page was loaded successfully; Error otherwise.
+    pub async fn read_file_page(
+        data: FileTabData,
+        page: u64,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let (file_name_path, encoding) = {
+            let tabs = app_state.lock_file_tabs();
+            match tabs.iter().find(|tab| tab.uid == data.uid) {
+                Some(tab) if tab.read_only => match &tab.file_name_path {
+                    Some(file_name_path) => (file_name_path.clone(), tab.encoding),
+                    None => {
+                        return Err(CommandError::file_path_not_specified());
+                    }
+                },
+                // Only the files opened in pages have pages to load.
+                Some(_) => return Ok(false),
+                None => {
+                    return Err(CommandError::tab_not_found(data.uid));
+                }
+            }
+        };
+
+        let (file_page, modified_at) = read_page(&file_name_path, page, Some(encoding)).await?;
+
+        {
+            let mut tabs = app_state.lock_file_tabs();
+            match tabs.iter_mut().find(|tab| tab.uid == data.uid) {
+                Some(tab) => {
+                    tab.content = Some(file_page.content);
+                    tab.page = file_page.page;
+                    tab.page_count = file_page.page_count;
+                    tab.modified_at = modified_at;
+                    tab.modified_at_state = modified_at;
+                }
+                None => return Err(CommandError::tab_not_found(data.uid)),
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Reads a page of a file in a blocking task and gets the last modified date of the file.
+///
+/// # Arguments
+/// * `file_name_path` - The name and path of the file.
+/// * `page` - The zero-based number of the page.
+/// * `encoding` - The text encoding of the file; `None` to detect it.
+///
+/// # Returns
+/// The page and the last modified date of the file; Error if the file could not be read.
+async fn read_page(
+    file_name_path: &str,
+    page: u64,
+    encoding: Option<TextEncoding>,
+) -> CommandResult<(FilePage, Option<DateTime<Utc>>)> {
+    let modified_at = match fs::metadata(file_name_path).await {
+        Ok(meta_data) => meta_data
+            .modified()
+            .ok()
+            .map(|modified_at| modified_at.into()),
+        Err(e) => {
+            return Err(CommandError::file_read_failed(file_name_path, &e));
+        }
+    };
+
+    let path = PathBuf::from(file_name_path);
+    match tokio::task::spawn_blocking(move || read_file_page(&path, page, encoding)).await {
+        Ok(Ok(file_page)) => Ok((file_page, modified_at)),
+        Ok(Err(e)) => Err(CommandError::file_read_failed(file_name_path, &e)),
+        Err(e) => Err(CommandError::task_failed(e.to_string())),
+    }
+}
@@ -0,0 +1,115 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    config::SidebarLayout,
+    error::{CommandError, CommandResult},
+    project_folder::{list_project_files, ProjectFile},
+    tauri_commands::TauriCommands,
+    types::AppState,
+};
+
+impl TauriCommands {
+    /// Opens a folder as the project folder replacing the previous one. The script files of the folder are
+    /// listed and watched for additions and removals.
+    ///
+    /// # Arguments
+    /// `folder` - The path of the folder to open.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The script files of the folder sorted by their relative path; Error if the folder does not exist.
+    pub async fn open_project_folder(
+        folder: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<ProjectFile>> {
+        if !Path::new(&folder).is_dir() {
+            return Err(CommandError::folder_not_found(&folder));
+        }
+
+        *app_state.lock_project_folder() = Some(folder);
+        Ok(app_state.sync_project_watcher().await)
+    }
+
+    /// Closes the project folder and stops watching it.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if a project folder was closed; `false` if no folder was open.
+    pub async fn close_project_folder(app_state: &State<'_, AppState>) -> CommandResult<bool> {
+        let closed = app_state.lock_project_folder().take().is_some();
+        app_state.sync_project_watcher().await;
+        Ok(closed)
+    }
+
+    /// Gets the script files of the project folder.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The script files of the project folder sorted by their relative path; empty if no folder is open.
+    pub async fn get_project_files(
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<ProjectFile>> {
+        if let Some(watcher) = app_state
+            .lock_or_reset("project_watcher", &app_state.project_watcher)
+            .as_ref()
+        {
+            return Ok(watcher.files().to_vec());
+        }
+
+        let Some(folder) = app_state.lock_project_folder().clone() else {
+            return Ok(vec![]);
+        };
+
+        // The folder is walked in the blocking thread pool.
+        match tokio::task::spawn_blocking(move || list_project_files(Path::new(&folder))).await {
+            Ok(files) => Ok(files),
+            Err(e) => Err(CommandError::task_failed(e.to_string())),
+        }
+    }
+
+    /// Sets the layout of the project folder sidebar and saves it into the open files state.
+    ///
+    /// # Arguments
+    /// `sidebar` - The layout of the sidebar.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the layout was saved successfully; Error otherwise.
+    pub async fn set_sidebar_layout(
+        sidebar: SidebarLayout,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        *app_state.lock_or_reset("sidebar", &app_state.sidebar) = sidebar;
+        TauriCommands::save_open_tabs(app_state).await
+    }
+}
@@ -0,0 +1,232 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    error::{CommandError, CommandResult},
+    local_history::assign_history_id,
+    tauri_commands::TauriCommands,
+    types::{AppState, FileTabData},
+    utils::first_missing_in_sequence,
+};
+
+impl TauriCommands {
+    /// Gets the recently opened files. The files no longer in the file system are removed from the list.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The names and paths of the recently opened files, the most recent first.
+    pub async fn get_recent_files(app_state: &State<'_, AppState>) -> CommandResult<Vec<String>> {
+        let mut recent_files = app_state.lock_recent_files();
+        recent_files.retain(|file| Path::new(file).is_file());
+        Ok(recent_files.clone())
+    }
+
+    /// Gets the recently closed tabs.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The recently closed tabs, the most recently closed first.
+    pub async fn get_closed_tabs(
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<FileTabData>> {
+        Ok(app_state.lock_closed_tabs().clone())
+    }
+
+    /// Reopens a recently closed tab and makes it the active tab. A temporary tab is restored with its
+    /// content and a file tab is reopened from the file system with the per-tab settings of the closed tab,
+    /// e.g. the line by line evaluation, the text encoding and the page of a file opened in pages.
+    /// The tab is removed from the recently closed tabs unless it fails to reopen.
+    ///
+    /// # Arguments
+    /// `index` - The index of the tab in the recently closed tabs; `None` for the most recently closed tab.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The identifier of the reopened tab; `None` if there is no such closed tab. Error if the file could not be opened.
+    pub async fn reopen_closed_tab(
+        index: Option<usize>,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Option<i32>> {
+        let index = index.unwrap_or_default();
+        let tab = {
+            let mut closed_tabs = app_state.lock_closed_tabs();
+            if index >= closed_tabs.len() {
+                return Ok(None);
+            }
+            closed_tabs.remove(index)
+        };
+
+        let uid = match &tab.file_name_path {
+            Some(file_name_path) if !tab.is_temporary => {
+                let opened_uid = app_state
+                    .lock_file_tabs()
+                    .iter()
+                    .find(|f| f.file_name_path.as_ref() == Some(file_name_path))
+                    .map(|f| f.uid);
+
+                match opened_uid {
+                    // The file was opened again after the tab was closed.
+                    Some(uid) => uid,
+                    None => {
+                        let opened = if tab.read_only {
+                            TauriCommands::open_file_in_pages(file_name_path.clone(), app_state)
+                                .await
+                        } else {
+                            TauriCommands::open_existing_file(file_name_path.clone(), app_state)
+                                .await
+                        };
+
+                        // A tab failing to reopen stays in the recently closed tabs.
+                        if let Err(e) = opened {
+                            restore_closed_tab(app_state, index, tab.clone());
+                            return Err(e);
+                        }
+
+                        let uid = app_state
+                            .lock_file_tabs()
+                            .iter()
+                            .find(|f| f.file_name_path.as_ref() == Some(file_name_path))
+                            .map(|f| f.uid);
+
+                        match uid {
+                            Some(uid) => {
+                                restore_tab_settings(app_state, uid, &tab).await;
+                                uid
+                            }
+                            None => {
+                                restore_closed_tab(app_state, index, tab.clone());
+                                return Err(CommandError::file_not_found(file_name_path));
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut tab = tab;
+                {
+                    let mut ids = app_state.lock_file_ids();
+                    let new_id = first_missing_in_sequence(&ids);
+                    ids.push(new_id);
+                    tab.uid = new_id;
+                }
+                assign_history_id(&mut tab);
+
+                let uid = tab.uid;
+                app_state.lock_file_tabs().push(tab);
+                uid
+            }
+        };
+
+        *app_state.lock_active_tab_id() = Some(uid);
+        TauriCommands::save_open_tabs(app_state).await?;
+
+        Ok(Some(uid))
+    }
+
+    /// Clears the recently opened files.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the recently opened files were cleared successfully; Error otherwise.
+    pub async fn clear_recent_files(app_state: &State<'_, AppState>) -> CommandResult<bool> {
+        app_state.lock_recent_files().clear();
+        TauriCommands::save_open_tabs(app_state).await
+    }
+
+    /// Clears the recently closed tabs.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the recently closed tabs were cleared successfully; Error otherwise.
+    pub async fn clear_closed_tabs(app_state: &State<'_, AppState>) -> CommandResult<bool> {
+        app_state.lock_closed_tabs().clear();
+        TauriCommands::save_open_tabs(app_state).await
+    }
+}
+
+/// Puts a closed tab back into the recently closed tabs at its previous position.
+///
+/// # Arguments
+/// * `app_state` - The Tauri application state.
+/// * `index` - The previous index of the tab in the recently closed tabs.
+/// * `tab` - The closed tab.
+fn restore_closed_tab(app_state: &AppState, index: usize, tab: FileTabData) {
+    let mut closed_tabs = app_state.lock_closed_tabs();
+    let index = index.min(closed_tabs.len());
+    closed_tabs.insert(index, tab);
+}
+
+/// Restores the per-tab settings of a closed file tab into the tab reopened from the file system.
+/// The file is reread if the closed tab used another text encoding or showed another page than the reopened tab.
+/// A failure to reread the file is only logged as the file is already reopened.
+///
+/// # Arguments
+/// * `app_state` - The Tauri application state.
+/// * `uid` - The identifier of the reopened tab.
+/// * `closed_tab` - The closed tab.
+async fn restore_tab_settings(app_state: &State<'_, AppState>, uid: i32, closed_tab: &FileTabData) {
+    let reopened = {
+        let mut tabs = app_state.lock_file_tabs();
+        match tabs.iter_mut().find(|tab| tab.uid == uid) {
+            Some(reopened) => {
+                let reopened_tab = reopened.clone();
+                reopened.evalueate_per_line = closed_tab.evalueate_per_line;
+                // A file opened in pages reads the page with the encoding of the tab.
+                if reopened.read_only {
+                    reopened.encoding = closed_tab.encoding;
+                }
+                reopened_tab
+            }
+            None => return,
+        }
+    };
+
+    let reread = if reopened.read_only {
+        if reopened.encoding == closed_tab.encoding && reopened.page == closed_tab.page {
+            return;
+        }
+        TauriCommands::read_file_page(reopened, closed_tab.page, app_state).await
+    } else {
+        if reopened.encoding == closed_tab.encoding {
+            return;
+        }
+        TauriCommands::reopen_file_with_encoding(reopened, closed_tab.encoding, app_state).await
+    };
+
+    if let Err(e) = reread {
+        eprintln!("{}", e);
+    }
+}
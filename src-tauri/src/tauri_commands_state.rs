@@ -22,14 +22,20 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-use std::path::Path;
+use std::{collections::HashMap, fs, path::Path};
 
 use chrono::{DateTime, Utc};
 use tauri::State;
 
 use crate::{
-    config::get_file_state, tauri_commands::TauriCommands, types::AppState,
-    utils::first_missing_in_sequence,
+    autosave::{remove_restored_tabs, remove_snapshot},
+    config::get_file_state,
+    error::{CommandError, CommandResult},
+    local_history::assign_history_id,
+    tauri_commands::TauriCommands,
+    text_encoding::decode_text_with,
+    types::{AppState, FileTabData},
+    utils::{first_missing_in_sequence, hash_content},
 };
 
 impl TauriCommands {
@@ -40,54 +46,38 @@ impl TauriCommands {
     ///
     /// # Returns
     /// `true` if the file state was loaded successfully; Error otherwise.
-    pub async fn load_file_state(app_state: &State<'_, AppState>) -> Result<bool, String> {
-        let mut state = match get_file_state() {
+    pub async fn load_file_state(app_state: &State<'_, AppState>) -> CommandResult<bool> {
+        let state = match get_file_state() {
             Ok(v) => v,
             Err(e) => {
-                return Err(e);
+                return Err(CommandError::state_load_failed(e));
             }
         };
 
-        match app_state.active_tab_id.lock() {
-            Ok(mut uid) => {
-                *uid = state.active_tab_id;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
-
-        match app_state.file_ids.lock() {
-            Ok(mut ids) => {
-                *ids = state.file_ids;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
-
-        state.files.iter_mut().for_each(|f| {
-            if f.file_name_path.is_some() {
-                let path = Path::new(f.file_name_path.as_ref().unwrap());
-
-                let last_modified: Option<DateTime<Utc>> = match path.metadata() {
-                    Ok(metadata) => match metadata.modified() {
-                        Ok(modified) => Some(modified.into()),
-                        Err(_) => None,
-                    },
-                    Err(_) => None,
-                };
-
-                f.modified_at = last_modified;
-            }
-        });
-
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                *tabs = state.files;
-            }
-            Err(e) => {
-                return Err(e.to_string());
+        *app_state.lock_recent_files() = state.recent_files;
+        *app_state.lock_closed_tabs() = state.closed_tabs;
+        *app_state.lock_workspace() = state.workspace;
+        *app_state.lock_or_reset("sidebar", &app_state.sidebar) = state.sidebar;
+
+        // A project folder removed since the last session is not reopened.
+        *app_state.lock_project_folder() = state
+            .project_folder
+            .filter(|folder| Path::new(folder).is_dir());
+        app_state.sync_project_watcher().await;
+
+        restore_file_tabs(app_state, state.files, state.file_ids, state.active_tab_id).await?;
+
+        // The recovered tabs identical to the restored open tabs have nothing to recover.
+        let tabs = app_state.lock_file_tabs().clone();
+        let mut recovery = app_state.lock_or_reset("recovery", &app_state.recovery);
+        if let Some(snapshot) = recovery.as_mut() {
+            remove_restored_tabs(snapshot, &tabs);
+
+            if snapshot.tabs.is_empty() {
+                *recovery = None;
+                if let Err(e) = remove_snapshot() {
+                    eprintln!("{}", e);
+                }
             }
         }
 
@@ -101,17 +91,10 @@ impl TauriCommands {
     ///
     /// # Returns
     /// The new tab unique id.
-    pub async fn get_new_tab_id(app_state: &State<'_, AppState>) -> Result<i32, String> {
-        match app_state.file_ids.lock() {
-            Ok(ids) => {
-                let new_ids: Vec<i32> = ids.clone();
-                let new_id = first_missing_in_sequence(&new_ids);
-                return Ok(new_id);
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
+    pub async fn get_new_tab_id(app_state: &State<'_, AppState>) -> CommandResult<i32> {
+        let new_ids: Vec<i32> = app_state.lock_file_ids().clone();
+        let new_id = first_missing_in_sequence(&new_ids);
+        Ok(new_id)
     }
 
     /// Checks if a file is opened in the application state.
@@ -125,16 +108,91 @@ impl TauriCommands {
     pub async fn is_file_opened(
         file_name: String,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        match app_state.file_tabs.lock() {
-            Ok(tabs) => {
-                return Ok(tabs
-                    .iter()
-                    .any(|f| f.file_name_path == Some(file_name.clone())));
-            }
+    ) -> CommandResult<bool> {
+        let tabs = app_state.lock_file_tabs();
+        Ok(tabs
+            .iter()
+            .any(|f| f.file_name_path == Some(file_name.clone())))
+    }
+}
+
+/// Restores the open tabs into the application state updating the last modified dates of their unchanged files.
+/// A file changed while the application was closed keeps the last modified date of the tab, so the change is detected.
+///
+/// # Arguments
+/// * `app_state` - The Tauri application state.
+/// * `files` - The tabs to restore.
+/// * `file_ids` - The identifiers of the tabs.
+/// * `active_tab_id` - The identifier of the active tab.
+///
+/// # Returns
+/// Ok if the tabs were restored; Error if reading the files of the tabs failed to complete.
+pub async fn restore_file_tabs(
+    app_state: &AppState,
+    files: Vec<FileTabData>,
+    file_ids: Vec<i32>,
+    active_tab_id: Option<i32>,
+) -> CommandResult<()> {
+    // The files are read and hashed in the blocking thread pool.
+    let (files, saved_contents) =
+        match tokio::task::spawn_blocking(move || check_file_tabs(files)).await {
+            Ok(checked) => checked,
             Err(e) => {
-                return Err(e.to_string());
+                return Err(CommandError::task_failed(e.to_string()));
+            }
+        };
+
+    *app_state.lock_active_tab_id() = active_tab_id;
+    *app_state.lock_file_ids() = file_ids;
+    *app_state.lock_or_reset("saved_contents", &app_state.saved_contents) = saved_contents;
+    *app_state.lock_file_tabs() = files;
+    app_state.sync_file_watcher();
+
+    Ok(())
+}
+
+/// Updates the last modified dates of the tabs whose files are unchanged since they were last saved.
+///
+/// # Arguments
+/// * `files` - The tabs to check.
+///
+/// # Returns
+/// The checked tabs and the file contents of the unchanged files by the tab identifier.
+fn check_file_tabs(mut files: Vec<FileTabData>) -> (Vec<FileTabData>, HashMap<i32, String>) {
+    let mut saved_contents = HashMap::new();
+
+    files.iter_mut().for_each(|f| {
+        assign_history_id(f);
+
+        if f.file_name_path.is_some() {
+            let path = Path::new(f.file_name_path.as_ref().unwrap());
+
+            let last_modified: Option<DateTime<Utc>> = match path.metadata() {
+                Ok(metadata) => match metadata.modified() {
+                    Ok(modified) => Some(modified.into()),
+                    Err(_) => None,
+                },
+                Err(_) => None,
+            };
+
+            // The file contents are the merge base only if the file is unchanged since it was last saved.
+            let mut unchanged = false;
+            if let Some(content_hash) = &f.content_hash {
+                if let Ok(contents) = fs::read(path) {
+                    if hash_content(&contents) == *content_hash {
+                        unchanged = true;
+                        f.content_size = Some(contents.len() as u64);
+                        saved_contents.insert(f.uid, decode_text_with(&contents, f.encoding).text);
+                    }
+                }
+            }
+
+            // A removed file has no date; a changed file keeps the date of the tab.
+            if unchanged || last_modified.is_none() {
+                f.modified_at = last_modified;
             }
         }
-    }
+    });
+
+    (files, saved_contents)
 }
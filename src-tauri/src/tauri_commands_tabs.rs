@@ -26,6 +26,8 @@ use tauri::State;
 
 use crate::{
     config::{save_file_state, FileState},
+    error::{CommandError, CommandResult},
+    local_history::assign_history_id,
     tauri_commands::TauriCommands,
     types::{AppState, AppStateResult, FileTabData},
     utils::first_missing_in_sequence,
@@ -39,36 +41,24 @@ impl TauriCommands {
     ///
     /// # Returns
     /// `true` if the open tabs were saved successfully; Error otherwise.
-    pub async fn save_open_tabs(app_state: &State<'_, AppState>) -> Result<bool, String> {
+    pub async fn save_open_tabs(app_state: &State<'_, AppState>) -> CommandResult<bool> {
         let mut config = FileState::default();
 
-        match app_state.file_ids.lock() {
-            Ok(ids) => {
-                config.file_ids = ids.clone();
-            }
-            Err(_) => {}
-        }
-
-        match app_state.file_tabs.lock() {
-            Ok(tabs) => {
-                config.files = tabs.clone();
-            }
-            Err(_) => {}
-        }
-
-        match app_state.active_tab_id.lock() {
-            Ok(id) => {
-                config.active_tab_id = *id;
-            }
-            Err(_) => {}
-        }
+        config.file_ids = app_state.lock_file_ids().clone();
+        config.files = app_state.lock_file_tabs().clone();
+        config.active_tab_id = *app_state.lock_active_tab_id();
+        config.recent_files = app_state.lock_recent_files().clone();
+        config.closed_tabs = app_state.lock_closed_tabs().clone();
+        config.workspace = app_state.lock_workspace().clone();
+        config.project_folder = app_state.lock_project_folder().clone();
+        config.sidebar = *app_state.lock_or_reset("sidebar", &app_state.sidebar);
 
         match save_file_state(config) {
             Ok(_) => {
                 return Ok(true);
             }
             Err(e) => {
-                return Err(e);
+                return Err(CommandError::state_save_failed(e));
             }
         }
     }
@@ -76,15 +66,8 @@ impl TauriCommands {
     pub async fn set_active_tab_id(
         tab_id: i32,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
-        match app_state.active_tab_id.lock() {
-            Ok(mut id) => {
-                *id = Some(tab_id);
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
+    ) -> CommandResult<bool> {
+        *app_state.lock_active_tab_id() = Some(tab_id);
         Ok(true)
     }
 
@@ -97,39 +80,66 @@ impl TauriCommands {
     /// # Returns
     /// `true` if the open tabs were updated successfully; Error otherwise.
     pub async fn update_open_tabs(
-        tab_data: Vec<FileTabData>,
+        mut tab_data: Vec<FileTabData>,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
+    ) -> CommandResult<bool> {
         let mut new_ids: Vec<i32> = vec![];
 
         for tab in &tab_data {
             new_ids.push(tab.uid);
         }
 
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                *tabs = tab_data;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
+        // The tabs missing from the updated tabs were closed.
+        let closed_tabs: Vec<FileTabData> = {
+            let mut tabs = app_state.lock_file_tabs();
+            let closed_tabs = tabs
+                .iter()
+                .filter(|tab| !new_ids.contains(&tab.uid))
+                .cloned()
+                .collect();
+
+            // The updated tabs keep the local history identifiers of the temporary tabs.
+            for tab in tab_data.iter_mut() {
+                if tab.history_id.is_none() {
+                    tab.history_id = tabs
+                        .iter()
+                        .find(|existing| existing.uid == tab.uid)
+                        .and_then(|existing| existing.history_id.clone());
+                }
+                assign_history_id(tab);
+            }
+
+            // The edited contents already in the updated tabs are no longer newer than the tabs.
+            app_state
+                .lock_or_reset("edited_contents", &app_state.edited_contents)
+                .retain(|tab_id, edited| {
+                    tab_data.iter().any(|tab| {
+                        tab.uid == *tab_id && tab.content.as_ref() != Some(&edited.content)
+                    })
+                });
 
-        match app_state.file_ids.lock() {
-            Ok(mut ids) => {
-                *ids = new_ids;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
+            *tabs = tab_data;
+            closed_tabs
+        };
+        app_state.add_closed_tabs(closed_tabs);
+
+        // Stop the live mode of the closed tabs.
+        app_state
+            .lock_or_reset("live_sessions", &app_state.live_sessions)
+            .retain(|tab_id, _| new_ids.contains(tab_id));
+        app_state
+            .lock_or_reset("saved_contents", &app_state.saved_contents)
+            .retain(|tab_id, _| new_ids.contains(tab_id));
+
+        *app_state.lock_file_ids() = new_ids;
+        app_state.sync_file_watcher();
 
         match TauriCommands::save_open_tabs(app_state).await {
             Ok(_) => {
                 return Ok(true);
             }
             Err(e) => {
-                return Err(e.to_string());
+                return Err(e);
             }
         }
     }
@@ -147,21 +157,17 @@ impl TauriCommands {
         tab_data: FileTabData,
         tab_content: Option<String>,
         app_state: &State<'_, AppState>,
-    ) -> Result<bool, String> {
+    ) -> CommandResult<bool> {
         let mut tab_data = tab_data;
 
-        match app_state.file_ids.lock() {
-            Ok(mut ids) => {
-                let mut new_ids: Vec<i32> = ids.clone();
+        {
+            let mut ids = app_state.lock_file_ids();
+            let mut new_ids: Vec<i32> = ids.clone();
 
-                let new_id = first_missing_in_sequence(&new_ids);
-                new_ids.push(new_id);
-                *ids = new_ids;
-                tab_data.uid = new_id;
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
+            let new_id = first_missing_in_sequence(&new_ids);
+            new_ids.push(new_id);
+            *ids = new_ids;
+            tab_data.uid = new_id;
         }
 
         tab_data.content = match tab_content {
@@ -171,15 +177,9 @@ impl TauriCommands {
 
         tab_data.path = None;
         tab_data.is_temporary = true;
+        assign_history_id(&mut tab_data);
 
-        match app_state.file_tabs.lock() {
-            Ok(mut tabs) => {
-                tabs.push(tab_data);
-            }
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        }
+        app_state.lock_file_tabs().push(tab_data);
 
         Ok(true)
     }
@@ -191,61 +191,35 @@ impl TauriCommands {
     ///
     /// # Returns
     /// `Vec<FileTabData>` if the open tabs were returned successfully; Error otherwise.
-    pub async fn get_app_state(app_state: &State<'_, AppState>) -> Result<AppStateResult, String> {
-        let file_tabs = match app_state.file_tabs.lock() {
-            Ok(tabs) => {
-                let file_tabs = tabs.clone();
-                file_tabs
-            }
-            Err(_) => {
-                return Err(t!("messages.failedGetBackendState").into_owned());
-            }
-        };
-
-        let file_ids = match app_state.file_ids.lock() {
-            Ok(ids) => {
-                let file_ids = ids.clone();
-                file_ids
-            }
-            Err(_) => {
-                return Err(t!("messages.failedGetBackendState").into_owned());
-            }
-        };
-
-        let log_stack_lines = match app_state.log_stack_lines.lock() {
-            Ok(lines) => {
-                let log_stack_lines = lines.clone();
-                log_stack_lines
-            }
-            Err(_) => {
-                return Err(t!("messages.failedGetBackendState").into_owned());
-            }
-        };
-
-        let active_tab_id = match app_state.active_tab_id.lock() {
-            Ok(id) => {
-                let active_tab_id = id.clone();
-                active_tab_id
-            }
-            Err(_) => {
-                return Err(t!("messages.failedGetBackendState").into_owned());
-            }
-        };
-
-        match app_state.log_stack.lock() {
-            Ok(stack) => {
-                let log_stack = stack.clone();
-                return Ok(AppStateResult {
-                    log_stack,
-                    file_tabs,
-                    file_ids,
-                    log_stack_lines,
-                    active_tab_id,
-                });
-            }
-            Err(_) => {
-                return Err(t!("messages.failedGetBackendState").into_owned());
-            }
-        }
+    pub async fn get_app_state(app_state: &State<'_, AppState>) -> CommandResult<AppStateResult> {
+        let file_tabs = app_state.lock_file_tabs().clone();
+        let file_ids = app_state.lock_file_ids().clone();
+        let log_stack_lines = app_state
+            .lock_or_reset("log_stack_lines", &app_state.log_stack_lines)
+            .clone();
+        let line_value_snapshots = app_state
+            .lock_or_reset("line_value_snapshots", &app_state.line_value_snapshots)
+            .clone();
+        let active_tab_id = *app_state.lock_active_tab_id();
+        let recent_files = app_state.lock_recent_files().clone();
+        let workspace = app_state.lock_workspace().clone();
+        let project_folder = app_state.lock_project_folder().clone();
+        let sidebar = *app_state.lock_or_reset("sidebar", &app_state.sidebar);
+        let log_stack = app_state
+            .lock_or_reset("log_stack", &app_state.log_stack)
+            .clone();
+
+        Ok(AppStateResult {
+            log_stack,
+            file_tabs,
+            file_ids,
+            log_stack_lines,
+            line_value_snapshots,
+            active_tab_id,
+            recent_files,
+            workspace,
+            project_folder,
+            sidebar,
+        })
     }
 }
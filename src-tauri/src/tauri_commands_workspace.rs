@@ -0,0 +1,298 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::path::Path;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    config::{get_workspaces, save_workspaces, Workspace, WorkspaceLayout, Workspaces},
+    error::{CommandError, CommandResult},
+    tauri_commands::TauriCommands,
+    tauri_commands_state::restore_file_tabs,
+    types::{AppState, FileTabData, WorkspaceInfo},
+};
+
+/// The name of the workspace the open tabs not saved as a workspace are saved to when switching the workspace.
+pub const DEFAULT_WORKSPACE_NAME: &str = "Default";
+
+impl TauriCommands {
+    /// Gets the saved named workspaces.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The summaries of the workspaces; Error if the workspaces could not be loaded.
+    pub async fn get_workspaces(
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<WorkspaceInfo>> {
+        let workspaces = load_workspaces()?;
+        let current = app_state.lock_workspace().clone();
+
+        Ok(workspaces
+            .workspaces
+            .iter()
+            .map(|workspace| WorkspaceInfo {
+                name: workspace.name.clone(),
+                tab_count: workspace.files.len(),
+                saved_at: workspace.saved_at,
+                is_current: current.as_ref() == Some(&workspace.name),
+            })
+            .collect())
+    }
+
+    /// Saves the open tabs as a named workspace replacing a workspace with the same name. The workspace
+    /// becomes the workspace of the open tabs.
+    ///
+    /// # Arguments
+    /// `name` - The name of the workspace.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the workspace was saved successfully; Error otherwise.
+    pub async fn save_workspace(
+        name: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let name = validate_name(&name)?;
+
+        let mut workspaces = load_workspaces()?;
+        store_open_tabs(&mut workspaces, &name, app_state);
+        store_workspaces(workspaces)?;
+
+        *app_state.lock_workspace() = Some(name);
+        TauriCommands::save_open_tabs(app_state).await
+    }
+
+    /// Switches to another named workspace. The open tabs are saved to their workspace first, or to the
+    /// default workspace if they are not saved as a workspace, so no open tab is lost.
+    ///
+    /// # Arguments
+    /// `name` - The name of the workspace to switch to.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the workspace was switched successfully; Error otherwise.
+    pub async fn switch_workspace(
+        name: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let mut workspaces = load_workspaces()?;
+        let workspace = match workspaces.workspaces.iter().find(|w| w.name == name) {
+            Some(workspace) => workspace.clone(),
+            None => {
+                return Err(CommandError::workspace_not_found(&name));
+            }
+        };
+
+        let current = app_state
+            .lock_workspace()
+            .clone()
+            .unwrap_or_else(|| DEFAULT_WORKSPACE_NAME.to_string());
+        if current != name {
+            store_open_tabs(&mut workspaces, &current, app_state);
+            store_workspaces(workspaces)?;
+        }
+
+        // The tab identifiers of the workspaces overlap, so the state by the tab identifier is reset.
+        app_state
+            .lock_or_reset("live_sessions", &app_state.live_sessions)
+            .clear();
+        let mut files = workspace.files;
+        if let Some(layout) = &workspace.layout {
+            order_tabs(&mut files, &layout.tab_order);
+        }
+        restore_file_tabs(
+            app_state,
+            files,
+            workspace.file_ids,
+            workspace.active_tab_id,
+        )
+        .await?;
+
+        // A workspace saved without its layout keeps the current layout.
+        if let Some(layout) = workspace.layout {
+            *app_state.lock_or_reset("sidebar", &app_state.sidebar) = layout.sidebar;
+
+            // A project folder removed since the workspace was saved is not reopened.
+            *app_state.lock_project_folder() = layout
+                .project_folder
+                .filter(|folder| Path::new(folder).is_dir());
+            app_state.sync_project_watcher().await;
+        }
+
+        *app_state.lock_workspace() = Some(workspace.name);
+        TauriCommands::save_open_tabs(app_state).await
+    }
+
+    /// Renames a named workspace.
+    ///
+    /// # Arguments
+    /// `name` - The name of the workspace to rename.
+    /// `new_name` - The new name of the workspace.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the workspace was renamed successfully; Error otherwise.
+    pub async fn rename_workspace(
+        name: String,
+        new_name: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let new_name = validate_name(&new_name)?;
+
+        let mut workspaces = load_workspaces()?;
+        if new_name != name && workspaces.workspaces.iter().any(|w| w.name == new_name) {
+            return Err(CommandError::workspace_exists(&new_name));
+        }
+
+        match workspaces.workspaces.iter_mut().find(|w| w.name == name) {
+            Some(workspace) => workspace.name = new_name.clone(),
+            None => {
+                return Err(CommandError::workspace_not_found(&name));
+            }
+        }
+        store_workspaces(workspaces)?;
+
+        {
+            let mut current = app_state.lock_workspace();
+            if current.as_ref() == Some(&name) {
+                *current = Some(new_name);
+            }
+        }
+
+        TauriCommands::save_open_tabs(app_state).await
+    }
+
+    /// Deletes a named workspace. The open tabs are kept if the workspace is the workspace of the open tabs.
+    ///
+    /// # Arguments
+    /// `name` - The name of the workspace to delete.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// `true` if the workspace was deleted successfully; Error otherwise.
+    pub async fn delete_workspace(
+        name: String,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<bool> {
+        let mut workspaces = load_workspaces()?;
+        let count = workspaces.workspaces.len();
+        workspaces.workspaces.retain(|w| w.name != name);
+        if workspaces.workspaces.len() == count {
+            return Err(CommandError::workspace_not_found(&name));
+        }
+        store_workspaces(workspaces)?;
+
+        {
+            let mut current = app_state.lock_workspace();
+            if current.as_ref() == Some(&name) {
+                *current = None;
+            }
+        }
+
+        TauriCommands::save_open_tabs(app_state).await
+    }
+}
+
+/// Validates a workspace name.
+///
+/// # Arguments
+/// * `name` - The workspace name.
+///
+/// # Returns
+/// The name without the leading and trailing whitespace; Error if the name is empty.
+fn validate_name(name: &str) -> CommandResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(CommandError::workspace_name_empty());
+    }
+
+    Ok(name.to_string())
+}
+
+/// Stores the open tabs as a named workspace replacing a workspace with the same name.
+///
+/// # Arguments
+/// * `workspaces` - The named workspaces.
+/// * `name` - The name of the workspace.
+/// * `app_state` - The Tauri application state.
+fn store_open_tabs(workspaces: &mut Workspaces, name: &str, app_state: &AppState) {
+    let files = app_state.lock_file_tabs().clone();
+    let layout = WorkspaceLayout {
+        sidebar: *app_state.lock_or_reset("sidebar", &app_state.sidebar),
+        project_folder: app_state.lock_project_folder().clone(),
+        tab_order: files.iter().map(|tab| tab.uid).collect(),
+    };
+
+    let workspace = Workspace {
+        name: name.to_string(),
+        files,
+        file_ids: app_state.lock_file_ids().clone(),
+        active_tab_id: *app_state.lock_active_tab_id(),
+        saved_at: Utc::now(),
+        layout: Some(layout),
+    };
+
+    match workspaces.workspaces.iter_mut().find(|w| w.name == name) {
+        Some(existing) => *existing = workspace,
+        None => workspaces.workspaces.push(workspace),
+    }
+}
+
+/// Orders the tabs of a workspace by the tab order of its layout. The tabs missing from the order are
+/// placed last in their current order.
+///
+/// # Arguments
+/// * `files` - The tabs of the workspace.
+/// * `tab_order` - The identifiers of the tabs in their order.
+fn order_tabs(files: &mut [FileTabData], tab_order: &[i32]) {
+    files.sort_by_key(|tab| {
+        tab_order
+            .iter()
+            .position(|uid| *uid == tab.uid)
+            .unwrap_or(usize::MAX)
+    });
+}
+
+/// Loads the named workspaces.
+///
+/// # Returns
+/// The named workspaces; Error if the workspaces could not be loaded.
+fn load_workspaces() -> CommandResult<Workspaces> {
+    get_workspaces().map_err(CommandError::state_load_failed)
+}
+
+/// Saves the named workspaces.
+///
+/// # Arguments
+/// * `workspaces` - The named workspaces.
+///
+/// # Returns
+/// `true` if the workspaces were saved successfully; Error otherwise.
+fn store_workspaces(workspaces: Workspaces) -> CommandResult<bool> {
+    save_workspaces(workspaces).map_err(CommandError::state_save_failed)
+}
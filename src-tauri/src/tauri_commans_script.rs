@@ -22,16 +22,16 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
+use std::{collections::BTreeMap, path::Path};
+
+use jsts_engine::{Engine, RunOptions, RunOutcome, SnapshotMismatch, Snapshots, TestReport};
 use tauri::State;
 
 use crate::{
-    js_helpers::{
-        clear_log_stack, get_log_stack, get_log_stack_by_file_line, js_console_error_capture,
-        js_console_error_capture_lines, js_console_log_capture, js_console_log_capture_lines,
-        js_console_warn_capture, js_console_warn_capture_lines, set_file_line,
-    },
+    error::{CommandError, CommandResult},
     tauri_commands::TauriCommands,
-    types::AppState,
+    types::{AppState, GlobalVariable},
+    variable_inspector::VariableInspector,
 };
 
 impl TauriCommands {
@@ -46,88 +46,33 @@ impl TauriCommands {
     pub async fn run_script(
         code: String,
         app_state: &State<'_, AppState>,
-    ) -> Result<String, String> {
-        clear_log_stack();
-
-        match app_state.log_stack.lock() {
-            Ok(mut stack) => {
-                *stack = vec![];
-            }
-            Err(_) => {}
-        }
-
-        let mut code = code.clone();
-        // The console.log(), console.warn(), and console.error() functions are not
-        // outputted anywhere, so we need to replace them with our own functions.
-        code = code
-            .replace("console.log(", "console_log(")
-            .replace("console.warn(", "console_warn(")
-            .replace("console.error(", "console_error(");
-        let code = code.as_str();
-
-        let isolate = &mut v8::Isolate::new(Default::default());
-
-        let scope = &mut v8::HandleScope::new(isolate);
-        let context = v8::Context::new(scope, Default::default());
-        let scope = &mut v8::ContextScope::new(scope, context);
-
-        let object_template = v8::ObjectTemplate::new(scope);
-
-        // Bind the console.log() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_log_capture);
-        let name = v8::String::new(scope, "console_log").unwrap();
-        object_template.set(name.into(), function_template.into());
-        // Bind the console.warn() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_warn_capture);
-        let name = v8::String::new(scope, "console_warn").unwrap();
-        object_template.set(name.into(), function_template.into());
-        // Bind the console.error() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_error_capture);
-        let name = v8::String::new(scope, "console_error").unwrap();
-        object_template.set(name.into(), function_template.into());
-
-        let context = v8::Context::new(
-            scope,
-            v8::ContextOptions {
-                global_template: Some(object_template),
-                ..Default::default()
-            },
-        );
-
-        let scope = &mut v8::ContextScope::new(scope, context);
+    ) -> CommandResult<String> {
+        // Dropping the previous inspector ends its engine session.
+        *app_state.lock_or_reset("variable_inspector", &app_state.variable_inspector) = None;
 
-        let source = match v8::String::new(scope, code) {
-            Some(source) => source,
-            None => {
-                return Err(t!("messages.failedCreateScript").into_owned());
-            }
+        let Some((inspector, outcome)) = VariableInspector::run(code) else {
+            return Err(CommandError::script_failed());
         };
 
-        let script = match v8::Script::compile(scope, source, None) {
-            Some(script) => script,
-            None => {
-                return Err(t!("messages.failedCompileScript").into_owned());
-            }
-        };
+        *app_state.lock_or_reset("log_stack", &app_state.log_stack) = outcome.log_stack();
 
-        let result = match script.run(scope) {
-            Some(result) => result,
-            None => {
-                return Err(t!("messages.failedRunScript").into_owned());
-            }
-        };
+        let RunOutcome {
+            results,
+            global_variables,
+            ..
+        } = outcome;
 
-        let result = match result.to_string(scope) {
-            Some(result) => result.to_rust_string_lossy(scope),
-            None => t!("messages.failedScriptResultsToString").into_owned(),
-        };
+        // The variables the script defined are captured even if the script run failed midway.
+        *app_state.lock_or_reset("global_variables", &app_state.global_variables) =
+            global_variables;
+        *app_state.lock_or_reset("variable_inspector", &app_state.variable_inspector) =
+            Some(inspector);
 
-        match app_state.log_stack.lock() {
-            Ok(mut stack) => *stack = get_log_stack(),
-            Err(_) => {}
+        match results.into_iter().next() {
+            Some(Ok(value)) => Ok(value.text),
+            Some(Err(exception)) => Err(CommandError::script_exception(&exception)),
+            None => Err(CommandError::script_failed()),
         }
-
-        Ok(result)
     }
 
     /// Runs the script passed from the frontend.
@@ -139,113 +84,176 @@ impl TauriCommands {
     /// # Returns
     /// The result of the script run line by line.    
     pub async fn run_script_line_by_line(
-        mut code: Vec<String>,
+        code: Vec<String>,
         app_state: &State<'_, AppState>,
-    ) -> Result<Vec<String>, String> {
-        clear_log_stack();
-
-        match app_state.log_stack_lines.lock() {
-            Ok(mut stack) => {
-                *stack = vec![];
-            }
-            Err(_) => {}
-        }
-
-        // The console.log(), console.warn(), and console.error() functions are not
-        // outputted anywhere, so we need to replace them with our own functions.
-        for i in 0..code.len() {
-            code[i] = code[i]
-                .replace("console.log(", "console_log(")
-                .replace("console.warn(", "console_warn(")
-                .replace("console.error(", "console_error(");
-        }
-
-        let isolate = &mut v8::Isolate::new(Default::default());
-
-        let scope = &mut v8::HandleScope::new(isolate);
-        let context = v8::Context::new(scope, Default::default());
-        let scope = &mut v8::ContextScope::new(scope, context);
-
-        let object_template = v8::ObjectTemplate::new(scope);
-
-        // Bind the console.log() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_log_capture_lines);
-        let name = v8::String::new(scope, "console_log").unwrap();
-        object_template.set(name.into(), function_template.into());
-        // Bind the console.warn() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_warn_capture_lines);
-        let name = v8::String::new(scope, "console_warn").unwrap();
-        object_template.set(name.into(), function_template.into());
-        // Bind the console.error() function to a custom capture function which will update the data into the Tauri application state.
-        let function_template = v8::FunctionTemplate::new(scope, js_console_error_capture_lines);
-        let name = v8::String::new(scope, "console_error").unwrap();
-        object_template.set(name.into(), function_template.into());
-
-        let context = v8::Context::new(
-            scope,
-            v8::ContextOptions {
-                global_template: Some(object_template),
+    ) -> CommandResult<Vec<String>> {
+        let engine = Engine::new();
+        let outcome = engine.run_lines(
+            &code,
+            &RunOptions {
+                capture_line_values: true,
                 ..Default::default()
             },
         );
 
-        let mut result_all: Vec<String> = Vec::new();
+        *app_state.lock_or_reset("log_stack_lines", &app_state.log_stack_lines) =
+            outcome.log_stack_lines();
 
-        for i in 0..code.len() {
-            set_file_line(Some(i as i32));
+        let RunOutcome {
+            results,
+            line_value_snapshots,
+            ..
+        } = outcome;
 
-            // Skip empty lines
-            if code[i].trim() == "" {
-                result_all.push("".into());
-                continue;
-            }
+        *app_state.lock_or_reset("line_value_snapshots", &app_state.line_value_snapshots) =
+            line_value_snapshots;
 
-            let code = code[i].as_str();
-
-            let scope = &mut v8::ContextScope::new(scope, context);
-
-            let source = match v8::String::new(scope, code) {
-                Some(source) => source,
-                None => {
-                    result_all.push(t!("messages.failedCreateScript").into_owned());
-                    continue;
-                }
-            };
-
-            let script = match v8::Script::compile(scope, source, None) {
-                Some(script) => script,
-                None => {
-                    result_all.push(t!("messages.failedCompileScript").into_owned());
-                    continue;
-                }
-            };
-
-            let result = match script.run(scope) {
-                Some(result) => result,
-                None => {
-                    result_all.push(t!("messages.failedCompileScript").into_owned());
-                    continue;
-                }
-            };
-
-            let result = match result.to_string(scope) {
-                Some(result) => result.to_rust_string_lossy(scope),
-                None => {
-                    result_all.push(t!("messages.failedScriptResultsToString").into_owned());
-                    continue;
-                }
-            };
-
-            result_all.push(result);
-        }
+        let result_all = results
+            .into_iter()
+            .map(|result| match result {
+                Ok(value) => value.text,
+                Err(exception) => exception.message,
+            })
+            .collect();
+
+        Ok(result_all)
+    }
+
+    /// Runs the script passed from the frontend and then the tests it registered with the built-in
+    /// `describe`, `it`/`test`, `beforeEach`, `afterEach` and `expect` functions. The `toMatchSnapshot`
+    /// snapshots are stored in a snapshot file next to the script file if the tab has a file.
+    ///
+    /// # Arguments
+    /// `code` - The script code with the tests.
+    /// `file_name_path` - The file of the script tab; `None` to keep the snapshots in memory.
+    /// `update_snapshots` - A flag indicating whether to update the mismatched and remove the obsolete snapshots.
+    ///
+    /// # Returns
+    /// The test report with the result of each test.
+    pub async fn run_tests(
+        code: String,
+        file_name_path: Option<String>,
+        update_snapshots: bool,
+    ) -> CommandResult<TestReport> {
+        let snapshot_file = file_name_path.map(|file| Snapshots::sidecar_path(Path::new(&file)));
+
+        let mut snapshots = match &snapshot_file {
+            Some(snapshot_file) => load_snapshots(snapshot_file)?,
+            None => Snapshots::default(),
+        };
+        let stored = snapshots.clone();
+
+        let engine = Engine::new();
+        let report = engine.run_tests_with_snapshots(
+            &code,
+            &RunOptions::default(),
+            &mut snapshots,
+            update_snapshots,
+        );
 
-        match app_state.log_stack_lines.lock() {
-            Ok(mut stack) => *stack = get_log_stack_by_file_line(),
-            Err(_) => {}
+        if let Some(snapshot_file) = &snapshot_file {
+            if snapshots != stored {
+                save_snapshots(&snapshots, snapshot_file)?;
+            }
         }
 
-        set_file_line(None);
+        Ok(report)
+    }
 
-        Ok(result_all)
+    /// Gets the stored snapshots of a script file for reviewing.
+    ///
+    /// # Arguments
+    /// `file_name_path` - The script file.
+    ///
+    /// # Returns
+    /// The formatted snapshot values by their keys.
+    pub async fn get_snapshots(file_name_path: String) -> CommandResult<BTreeMap<String, String>> {
+        let snapshot_file = Snapshots::sidecar_path(Path::new(&file_name_path));
+        Ok(load_snapshots(&snapshot_file)?.snapshots)
+    }
+
+    /// Accepts the reviewed mismatched values of a test run as the new snapshots of a script file.
+    ///
+    /// # Arguments
+    /// `file_name_path` - The script file.
+    /// `mismatches` - The mismatched values to store as the snapshots.
+    ///
+    /// # Returns
+    /// The number of the snapshots updated.
+    pub async fn update_snapshots(
+        file_name_path: String,
+        mismatches: Vec<SnapshotMismatch>,
+    ) -> CommandResult<usize> {
+        let snapshot_file = Snapshots::sidecar_path(Path::new(&file_name_path));
+        let mut snapshots = load_snapshots(&snapshot_file)?;
+        let updated = snapshots.accept(&mismatches);
+        save_snapshots(&snapshots, &snapshot_file)?;
+
+        Ok(updated)
+    }
+
+    /// Gets the global variables the last script run defined into the context global object.
+    ///
+    /// # Arguments
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The global variables of the last script run.
+    pub async fn get_global_variables(
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<GlobalVariable>> {
+        let global_variables =
+            app_state.lock_or_reset("global_variables", &app_state.global_variables);
+        Ok(global_variables.clone())
     }
+
+    /// Gets the child values of an expandable global variable or a child value of one.
+    ///
+    /// # Arguments
+    /// `handle` - The handle of the value to expand.
+    /// `app_state` - The Tauri application state.
+    ///
+    /// # Returns
+    /// The child values of the value; Error if the handle was not found.
+    pub async fn get_global_variable_children(
+        handle: u32,
+        app_state: &State<'_, AppState>,
+    ) -> CommandResult<Vec<GlobalVariable>> {
+        let inspector =
+            app_state.lock_or_reset("variable_inspector", &app_state.variable_inspector);
+
+        match inspector
+            .as_ref()
+            .and_then(|inspector| inspector.expand(handle))
+        {
+            Some(children) => Ok(children),
+            None => Err(CommandError::global_variable_not_found(handle)),
+        }
+    }
+}
+
+/// Loads the snapshots of a snapshot file.
+///
+/// # Arguments
+/// `snapshot_file` - The snapshot file.
+///
+/// # Returns
+/// The snapshots; Error if the file could not be read.
+fn load_snapshots(snapshot_file: &Path) -> CommandResult<Snapshots> {
+    Snapshots::load(snapshot_file)
+        .map_err(|e| CommandError::snapshot_read_failed(&snapshot_file.display().to_string(), e))
+}
+
+/// Saves the snapshots into a snapshot file.
+///
+/// # Arguments
+/// `snapshots` - The snapshots to save.
+/// `snapshot_file` - The snapshot file.
+///
+/// # Returns
+/// Ok if the snapshots were saved; Error otherwise.
+fn save_snapshots(snapshots: &Snapshots, snapshot_file: &Path) -> CommandResult<()> {
+    snapshots
+        .save(snapshot_file)
+        .map_err(|e| CommandError::snapshot_write_failed(&snapshot_file.display().to_string(), e))
 }
@@ -0,0 +1,336 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::fmt;
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+
+/// The byte order mark of a UTF-8 file.
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+/// The byte order mark of a little-endian UTF-16 file.
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+/// The byte order mark of a big-endian UTF-16 file.
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// The text encoding of a file.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextEncoding {
+    /// UTF-8 without a byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-8 with a byte order mark.
+    Utf8Bom,
+    /// Little-endian UTF-16 with a byte order mark.
+    Utf16Le,
+    /// Big-endian UTF-16 with a byte order mark.
+    Utf16Be,
+    /// The Windows-1252 superset of Latin-1 (ISO-8859-1).
+    #[serde(rename = "windows-1252")]
+    Windows1252,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 with BOM",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+            TextEncoding::Windows1252 => "Windows-1252",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The line endings of a file.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// The line feed (`\n`) line endings.
+    #[default]
+    Lf,
+    /// The carriage return and line feed (`\r\n`) line endings.
+    CrLf,
+}
+
+/// The text of a file decoded for the editor.
+pub struct DecodedText {
+    /// The text with the line feed line endings.
+    pub text: String,
+    /// The text encoding of the file.
+    pub encoding: TextEncoding,
+    /// The line endings of the file.
+    pub line_ending: LineEnding,
+}
+
+/// Decodes the file contents into text. The encoding is detected from the byte order mark; a file without
+/// one is UTF-8 if it is valid UTF-8 and Windows-1252 otherwise. The line endings are detected by the
+/// majority and the text is returned with the line feed line endings, so mixed line endings are normalised
+/// to the majority when the text is saved with [encode_text].
+///
+/// # Arguments
+/// * `bytes` - The file contents.
+///
+/// # Returns
+/// The decoded text with the detected encoding and line endings.
+pub fn decode_text(bytes: &[u8]) -> DecodedText {
+    let encoding = detect_encoding(bytes);
+    let text = decode_with(bytes, encoding);
+    let line_ending = detect_line_ending(&text);
+
+    DecodedText {
+        text: normalize_line_endings(&text),
+        encoding,
+        line_ending,
+    }
+}
+
+/// Decodes the file contents into text with the specified encoding instead of the detected one.
+///
+/// # Arguments
+/// * `bytes` - The file contents.
+/// * `encoding` - The text encoding of the file.
+///
+/// # Returns
+/// The decoded text with the detected line endings. The invalid byte sequences are replaced with the replacement character.
+pub fn decode_text_with(bytes: &[u8], encoding: TextEncoding) -> DecodedText {
+    let text = decode_with(bytes, encoding);
+    let line_ending = detect_line_ending(&text);
+
+    DecodedText {
+        text: normalize_line_endings(&text),
+        encoding,
+        line_ending,
+    }
+}
+
+/// Encodes the editor text into the file contents. All the lines get the specified line endings, i.e. the
+/// majority line endings of a file with mixed line endings.
+///
+/// # Arguments
+/// * `text` - The editor text.
+/// * `encoding` - The text encoding of the file.
+/// * `line_ending` - The line endings of the file.
+///
+/// # Returns
+/// The file contents; Error with the first character the encoding cannot represent.
+pub fn encode_text(
+    text: &str,
+    encoding: TextEncoding,
+    line_ending: LineEnding,
+) -> Result<Vec<u8>, char> {
+    let text = normalize_line_endings(text);
+    let text = match line_ending {
+        LineEnding::Lf => text,
+        LineEnding::CrLf => text.replace('\n', "\r\n"),
+    };
+
+    match encoding {
+        TextEncoding::Utf8 => Ok(text.into_bytes()),
+        TextEncoding::Utf8Bom => Ok([UTF8_BOM, text.as_bytes()].concat()),
+        TextEncoding::Utf16Le => Ok(UTF16LE_BOM
+            .iter()
+            .copied()
+            .chain(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()))
+            .collect()),
+        TextEncoding::Utf16Be => Ok(UTF16BE_BOM
+            .iter()
+            .copied()
+            .chain(text.encode_utf16().flat_map(|unit| unit.to_be_bytes()))
+            .collect()),
+        TextEncoding::Windows1252 => {
+            let (bytes, _, had_unmappable) = WINDOWS_1252.encode(&text);
+            if had_unmappable {
+                // The encoder replaces the unmappable characters with numeric character references.
+                let unmappable = text
+                    .chars()
+                    .find(|c| WINDOWS_1252.encode(c.encode_utf8(&mut [0; 4])).2)
+                    .unwrap_or(char::REPLACEMENT_CHARACTER);
+                return Err(unmappable);
+            }
+            Ok(bytes.into_owned())
+        }
+    }
+}
+
+/// Detects the text encoding of the file contents.
+///
+/// # Arguments
+/// * `bytes` - The file contents.
+///
+/// # Returns
+/// The detected text encoding.
+fn detect_encoding(bytes: &[u8]) -> TextEncoding {
+    if bytes.starts_with(UTF8_BOM) {
+        TextEncoding::Utf8Bom
+    } else if bytes.starts_with(UTF16LE_BOM) {
+        TextEncoding::Utf16Le
+    } else if bytes.starts_with(UTF16BE_BOM) {
+        TextEncoding::Utf16Be
+    } else if std::str::from_utf8(bytes).is_ok() {
+        TextEncoding::Utf8
+    } else {
+        TextEncoding::Windows1252
+    }
+}
+
+/// Decodes the file contents with the encoding skipping the byte order mark of the encoding.
+///
+/// # Arguments
+/// * `bytes` - The file contents.
+/// * `encoding` - The text encoding of the file.
+///
+/// # Returns
+/// The decoded text.
+fn decode_with(bytes: &[u8], encoding: TextEncoding) -> String {
+    let (decoder, bom): (&'static Encoding, &[u8]) = match encoding {
+        TextEncoding::Utf8 | TextEncoding::Utf8Bom => (encoding_rs::UTF_8, UTF8_BOM),
+        TextEncoding::Utf16Le => (UTF_16LE, UTF16LE_BOM),
+        TextEncoding::Utf16Be => (UTF_16BE, UTF16BE_BOM),
+        TextEncoding::Windows1252 => (WINDOWS_1252, &[]),
+    };
+
+    let bytes = bytes.strip_prefix(bom).unwrap_or(bytes);
+    decoder.decode_without_bom_handling(bytes).0.into_owned()
+}
+
+/// Detects the line endings of the text by the majority.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// The carriage return and line feed line endings if most of the lines end with them; the line feed otherwise.
+fn detect_line_ending(text: &str) -> LineEnding {
+    let line_feeds = text.matches('\n').count();
+    let crlf = text.matches("\r\n").count();
+
+    if crlf > 0 && crlf * 2 >= line_feeds {
+        LineEnding::CrLf
+    } else {
+        LineEnding::Lf
+    }
+}
+
+/// Converts the carriage return and line feed line endings of the text into the line feed line endings.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// The text with the line feed line endings.
+fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_utf8_with_bom() {
+        let decoded = decode_text(b"\xEF\xBB\xBFconst a = 1;\n");
+        assert_eq!(decoded.encoding, TextEncoding::Utf8Bom);
+        assert_eq!(decoded.text, "const a = 1;\n");
+        assert_eq!(
+            encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap(),
+            b"\xEF\xBB\xBFconst a = 1;\n"
+        );
+    }
+
+    #[test]
+    fn decodes_utf16() {
+        let little_endian = [0xFF, 0xFE, b'a', 0x00, 0xE4, 0x00];
+        let decoded = decode_text(&little_endian);
+        assert_eq!(decoded.encoding, TextEncoding::Utf16Le);
+        assert_eq!(decoded.text, "a\u{e4}");
+        assert_eq!(
+            encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap(),
+            little_endian
+        );
+
+        let big_endian = [0xFE, 0xFF, 0x00, b'a', 0x00, 0xE4];
+        let decoded = decode_text(&big_endian);
+        assert_eq!(decoded.encoding, TextEncoding::Utf16Be);
+        assert_eq!(decoded.text, "a\u{e4}");
+        assert_eq!(
+            encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap(),
+            big_endian
+        );
+    }
+
+    #[test]
+    fn falls_back_to_windows_1252() {
+        let decoded = decode_text(b"caf\xE9 \x80");
+        assert_eq!(decoded.encoding, TextEncoding::Windows1252);
+        assert_eq!(decoded.text, "caf\u{e9} \u{20ac}");
+        assert_eq!(
+            encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap(),
+            b"caf\xE9 \x80"
+        );
+    }
+
+    #[test]
+    fn reports_unmappable_character() {
+        assert_eq!(
+            encode_text(
+                "caf\u{e9} \u{65e5}",
+                TextEncoding::Windows1252,
+                LineEnding::Lf
+            ),
+            Err('\u{65e5}')
+        );
+    }
+
+    #[test]
+    fn detects_majority_line_endings() {
+        let decoded = decode_text(b"a\r\nb\r\nc\nd");
+        assert_eq!(decoded.line_ending, LineEnding::CrLf);
+        assert_eq!(decoded.text, "a\nb\nc\nd");
+
+        // The mixed line endings are normalised to the majority on save.
+        assert_eq!(
+            encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap(),
+            b"a\r\nb\r\nc\r\nd"
+        );
+
+        assert_eq!(decode_text(b"a\nb\nc\r\nd").line_ending, LineEnding::Lf);
+    }
+
+    #[test]
+    fn round_trips_text() {
+        let text = "let s = \"\u{e4}\u{f6}\";\r\nconsole.log(s);\r\n";
+        let decoded = decode_text(text.as_bytes());
+        assert_eq!(decoded.encoding, TextEncoding::Utf8);
+        assert_eq!(decoded.line_ending, LineEnding::CrLf);
+
+        let encoded = encode_text(&decoded.text, decoded.encoding, decoded.line_ending).unwrap();
+        assert_eq!(encoded, text.as_bytes());
+
+        let decoded_with = decode_text_with(&encoded, TextEncoding::Utf8);
+        assert_eq!(decoded_with.text, decoded.text);
+    }
+}
@@ -22,16 +22,27 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-use std::sync::Mutex;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct LineByLineLog {
-    pub line_number: i32,
-    pub lines: Vec<String>,
-}
+use crate::{
+    autosave::{EditedContent, RecoverySnapshot},
+    config::SidebarLayout,
+    file_watcher::FileWatcher,
+    language::ModuleKind,
+    live_runner::LiveSession,
+    project_folder::ProjectWatcher,
+    text_encoding::{LineEnding, TextEncoding},
+    variable_inspector::VariableInspector,
+};
+
+pub use jsts_engine::{GlobalVariable, LineByLineLog, LineValueSnapshot};
 
 /// The application state for the Tauri application.
 pub struct AppState {
@@ -39,12 +50,47 @@ pub struct AppState {
     pub log_stack: Mutex<Vec<String>>,
     /// The log stack lines for the run script.
     pub log_stack_lines: Mutex<Vec<LineByLineLog>>,
+    /// The value snapshots of the lines for the script run line by line.
+    pub line_value_snapshots: Mutex<Vec<LineValueSnapshot>>,
     /// The index of the next file.
     pub file_ids: Mutex<Vec<i32>>,
     /// The file tabs currently open.
     pub file_tabs: Mutex<Vec<FileTabData>>,
     /// The identifier of the active tab.
     pub active_tab_id: Mutex<Option<i32>>,
+    /// The global variables defined by the last script run.
+    pub global_variables: Mutex<Vec<GlobalVariable>>,
+    /// The engine session of the last script run to expand its global variables on demand.
+    pub variable_inspector: Mutex<Option<VariableInspector>>,
+    /// The live mode sessions by the tab identifier.
+    pub live_sessions: Mutex<HashMap<i32, LiveSession>>,
+    /// The Tauri application handle to notify the frontend about the recovered application state.
+    pub app_handle: OnceLock<AppHandle>,
+    /// The file system watcher over the files of the non-temporary tabs.
+    pub file_watcher: Mutex<Option<FileWatcher>>,
+    /// The file contents in the file system by the tab identifier when the file was last opened, reloaded
+    /// or saved. The contents are the base of the three-way merges with the changed files.
+    pub saved_contents: Mutex<HashMap<i32, String>>,
+    /// The names and paths of the recently opened files, the most recent first.
+    pub recent_files: Mutex<Vec<String>>,
+    /// The recently closed tabs, the most recently closed first.
+    pub closed_tabs: Mutex<Vec<FileTabData>>,
+    /// The name of the workspace of the open tabs; `None` if the tabs are not saved as a workspace.
+    pub workspace: Mutex<Option<String>>,
+    /// The editor contents pushed by the frontend on each edit by the tab identifier. The contents are
+    /// included in the autosave snapshot, as the open tabs are updated only after a pause in the editing.
+    pub edited_contents: Mutex<HashMap<i32, EditedContent>>,
+    /// The unsaved tab contents of the previous session which ended uncleanly, waiting to be restored or discarded.
+    pub recovery: Mutex<Option<RecoverySnapshot>>,
+    /// The lock serializing the reads and writes of the local history. The lock is held while the blocking
+    /// file operations run in the blocking thread pool.
+    pub local_history: tokio::sync::Mutex<()>,
+    /// The path of the open project folder; `None` if no folder is open.
+    pub project_folder: Mutex<Option<String>>,
+    /// The file system watcher over the open project folder.
+    pub project_watcher: Mutex<Option<ProjectWatcher>>,
+    /// The layout of the project folder sidebar.
+    pub sidebar: Mutex<SidebarLayout>,
 }
 
 /// The application default state for the Tauri application.
@@ -55,7 +101,23 @@ impl ::std::default::Default for AppState {
             file_ids: Mutex::new(vec![]),
             file_tabs: Mutex::new(vec![]),
             log_stack_lines: Mutex::new(vec![]),
+            line_value_snapshots: Mutex::new(vec![]),
             active_tab_id: Mutex::new(None),
+            global_variables: Mutex::new(vec![]),
+            variable_inspector: Mutex::new(None),
+            live_sessions: Mutex::new(HashMap::new()),
+            app_handle: OnceLock::new(),
+            file_watcher: Mutex::new(None),
+            saved_contents: Mutex::new(HashMap::new()),
+            recent_files: Mutex::new(vec![]),
+            closed_tabs: Mutex::new(vec![]),
+            workspace: Mutex::new(None),
+            edited_contents: Mutex::new(HashMap::new()),
+            recovery: Mutex::new(None),
+            local_history: tokio::sync::Mutex::new(()),
+            project_folder: Mutex::new(None),
+            project_watcher: Mutex::new(None),
+            sidebar: Mutex::new(SidebarLayout::default()),
         }
     }
 }
@@ -67,12 +129,35 @@ pub struct AppStateResult {
     pub log_stack: Vec<String>,
     /// The log stack lines for the run script.
     pub log_stack_lines: Vec<LineByLineLog>,
+    /// The value snapshots of the lines for the script run line by line.
+    pub line_value_snapshots: Vec<LineValueSnapshot>,
     /// The index of the next file.
     pub file_ids: Vec<i32>,
     /// The file tabs currently open.
     pub file_tabs: Vec<FileTabData>,
     /// The identifier of the active tab.
     pub active_tab_id: Option<i32>,
+    /// The names and paths of the recently opened files, the most recent first.
+    pub recent_files: Vec<String>,
+    /// The name of the workspace of the open tabs; `None` if the tabs are not saved as a workspace.
+    pub workspace: Option<String>,
+    /// The path of the open project folder; `None` if no folder is open.
+    pub project_folder: Option<String>,
+    /// The layout of the project folder sidebar.
+    pub sidebar: SidebarLayout,
+}
+
+/// The summary of a named workspace.
+#[derive(Serialize, Deserialize)]
+pub struct WorkspaceInfo {
+    /// The name of the workspace.
+    pub name: String,
+    /// The number of the tabs of the workspace.
+    pub tab_count: usize,
+    /// The time the workspace was last saved.
+    pub saved_at: DateTime<Utc>,
+    /// A flag indicating whether the workspace is the workspace of the open tabs.
+    pub is_current: bool,
 }
 
 /// The file tab data for a single file.
@@ -88,14 +173,44 @@ pub struct FileTabData {
     pub file_name_path: Option<String>,
     /// A flag indicating if the file is temporary.
     pub is_temporary: bool,
-    /// The language of the script.
+    /// The language of the script for the editor, either `javascript` or `typescript`.
     pub script_language: String,
+    /// A flag indicating whether the script has JSX elements, i.e. the file is a `.jsx` or a `.tsx` file.
+    #[serde(default)]
+    pub jsx: bool,
+    /// The module system of the script file.
+    #[serde(default)]
+    pub module_kind: ModuleKind,
     /// The optional content of the file. The content can be in the file system also if the file is not temporary.
     pub content: Option<String>,
     /// The last modified date of the file.
     pub modified_at: Option<DateTime<Utc>>,
     /// A flag indicating if the file differs from the file system.
     pub modified_at_state: Option<DateTime<Utc>>,
+    /// The SHA-256 hash of the file contents in the file system when the file was last opened, reloaded or saved.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// The size of the file contents in bytes in the file system when the file was last opened, reloaded or saved.
+    #[serde(default)]
+    pub content_size: Option<u64>,
+    /// The persistent unique identifier of a temporary tab keying its revisions in the local history.
+    #[serde(default)]
+    pub history_id: Option<String>,
+    /// The text encoding of the file in the file system.
+    #[serde(default)]
+    pub encoding: TextEncoding,
+    /// The line endings of the file in the file system.
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// A flag indicating whether the file is too large to edit and is opened read-only in pages.
+    #[serde(default)]
+    pub read_only: bool,
+    /// The zero-based number of the page in the content of a file opened read-only in pages.
+    #[serde(default)]
+    pub page: u64,
+    /// The number of the pages of a file opened read-only in pages.
+    #[serde(default)]
+    pub page_count: u64,
     /** A flag indicating whether to evaluate each line separately or the entire file content at once. */
     pub evalueate_per_line: bool,
 }
@@ -22,10 +22,18 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-use chrono::{DateTime, Utc};
+use std::{fs::Metadata, io};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use sha2::{Digest, Sha256};
 use tauri::{AppHandle, Manager};
 use tokio::fs;
 
+use crate::text_encoding::{decode_text, decode_text_with, DecodedText, TextEncoding};
+
+/// The coarsest last modified date resolution of the common file systems, i.e. the two seconds of FAT.
+const MODIFIED_AT_RESOLUTION: TimeDelta = TimeDelta::seconds(2);
+
 pub fn first_missing_in_sequence(vec: &Vec<i32>) -> i32 {
     // Dont' iterate entire i32 positive range if there is nothing to check for.
     if vec.len() == 0 {
@@ -40,29 +48,87 @@ pub fn first_missing_in_sequence(vec: &Vec<i32>) -> i32 {
     1
 }
 
-pub async fn get_file_contents_and_modified_at(
+/// The contents of a file read into the editor.
+pub struct FileContents {
+    /// The decoded text of the file with its encoding and line endings.
+    pub decoded: DecodedText,
+    /// The hash of the raw file contents.
+    pub content_hash: String,
+    /// The size of the raw file contents in bytes.
+    pub content_size: u64,
+    /// The last modified date of the file.
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// Reads a file and decodes its contents into text detecting the line endings.
+///
+/// # Arguments
+/// * `path` - The file to read.
+/// * `encoding` - The text encoding to decode the file with; `None` to detect it.
+///
+/// # Returns
+/// The decoded file contents with the hash of the raw contents and the last modified date; Error if the file could not be read.
+pub async fn read_file_contents(
     path: &str,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let metadata = match fs::metadata(path).await {
-        Ok(metadata) => metadata,
-        Err(e) => {
-            return Err(e.to_string());
-        }
-    };
+    encoding: Option<TextEncoding>,
+) -> io::Result<FileContents> {
+    let metadata = fs::metadata(path).await?;
 
     let modified_at = match metadata.modified() {
         Ok(modified_at) => Some(modified_at.into()),
         Err(_) => None,
     };
 
-    let content = match fs::read_to_string(path).await {
-        Ok(content) => content,
-        Err(e) => {
-            return Err(e.to_string());
-        }
+    let bytes = fs::read(path).await?;
+
+    Ok(FileContents {
+        decoded: match encoding {
+            Some(encoding) => decode_text_with(&bytes, encoding),
+            None => decode_text(&bytes),
+        },
+        content_hash: hash_content(&bytes),
+        content_size: bytes.len() as u64,
+        modified_at,
+    })
+}
+
+/// Checks whether the file metadata alone tells that the file is unchanged since its contents were last
+/// read or written. An unchanged last modified date is not enough if the size changed, if the date has no
+/// sub-second part, e.g. with a coarse date resolution or a checkout restoring whole-second dates, or if
+/// the date is so recent that a later write could still get the same date.
+///
+/// # Arguments
+/// * `metadata` - The metadata of the file.
+/// * `modified_at` - The known last modified date of the file.
+/// * `content_size` - The known size of the file contents in bytes.
+///
+/// # Returns
+/// `true` if the file is known to be unchanged; `false` if the file contents must be compared.
+pub fn is_unchanged_by_metadata(
+    metadata: &Metadata,
+    modified_at: Option<DateTime<Utc>>,
+    content_size: Option<u64>,
+) -> bool {
+    let Ok(file_modified_at) = metadata.modified() else {
+        return false;
     };
+    let file_modified_at: DateTime<Utc> = file_modified_at.into();
 
-    Ok((content, modified_at))
+    Some(file_modified_at) == modified_at
+        && content_size == Some(metadata.len())
+        && file_modified_at.timestamp_subsec_nanos() != 0
+        && Utc::now() - file_modified_at > MODIFIED_AT_RESOLUTION
+}
+
+/// Computes the SHA-256 hash of the file contents.
+///
+/// # Arguments
+/// * `contents` - The file contents.
+///
+/// # Returns
+/// The hash as a lowercase hexadecimal string.
+pub fn hash_content(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
 }
 
 pub fn show_window(app: &AppHandle) {
@@ -79,3 +145,32 @@ pub fn show_window(app: &AppHandle) {
                 .as_str(),
         );
 }
+
+/// A temporary directory for the tests, removed with its contents when dropped.
+#[cfg(test)]
+pub struct TestDir {
+    /// The path of the directory.
+    pub path: std::path::PathBuf,
+}
+
+#[cfg(test)]
+impl TestDir {
+    /// Creates a new empty temporary directory.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the directory unique within the tests.
+    pub fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("js_ts_runner_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,97 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+use jsts_engine::{Engine, GlobalVariable, RunOptions, RunOutcome};
+
+/// A request to expand a global variable of the inspected script run.
+struct ExpandRequest {
+    /// The handle of the value to expand.
+    handle: u32,
+    /// The sender for the child values of the value; `None` if the handle was not found.
+    reply: Sender<Option<Vec<GlobalVariable>>>,
+}
+
+/// An engine session kept alive on its own thread after a script run so that the global variables the
+/// script defined can be expanded on demand. The thread ends when the inspector is dropped.
+pub struct VariableInspector {
+    /// The sender for the expand requests.
+    sender: Sender<ExpandRequest>,
+}
+
+impl VariableInspector {
+    /// Runs a script in a new engine session collecting the global variables it defines.
+    ///
+    /// # Arguments
+    /// * `code` - The script code to run.
+    ///
+    /// # Returns
+    /// The inspector over the script run and the outcome of the run; `None` if the session thread ended unexpectedly.
+    pub fn run(code: String) -> Option<(Self, RunOutcome)> {
+        let (sender, receiver) = mpsc::channel::<ExpandRequest>();
+        let (outcome_sender, outcome_receiver) = mpsc::channel::<RunOutcome>();
+
+        thread::spawn(move || {
+            let mut session = Engine::new().create_session();
+            let outcome = session.run(
+                &code,
+                &RunOptions {
+                    collect_globals: true,
+                    ..Default::default()
+                },
+            );
+
+            if outcome_sender.send(outcome).is_err() {
+                return;
+            }
+
+            // The requests end when the inspector is dropped.
+            while let Ok(request) = receiver.recv() {
+                let _ = request
+                    .reply
+                    .send(session.expand_global_variable(request.handle));
+            }
+        });
+
+        let outcome = outcome_receiver.recv().ok()?;
+        Some((Self { sender }, outcome))
+    }
+
+    /// Gets the child values of an expandable global variable or a child value of one.
+    ///
+    /// # Arguments
+    /// * `handle` - The handle of the value to expand.
+    ///
+    /// # Returns
+    /// The child values of the value; `None` if the handle was not found.
+    pub fn expand(&self, handle: u32) -> Option<Vec<GlobalVariable>> {
+        let (reply, receiver) = mpsc::channel();
+        self.sender.send(ExpandRequest { handle, reply }).ok()?;
+        receiver.recv().ok()?
+    }
+}
@@ -4,6 +4,7 @@ import { Editor } from "@monaco-editor/react";
 import "./App.css";
 import classNames from "classnames";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { UnlistenFn, listen } from "@tauri-apps/api/event";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { StyledTitle } from "./components/app/WindowTitle";
 import { useTranslate } from "./localization/Localization";
@@ -13,25 +14,55 @@ import { PreferencesPopup } from "./components/popups/PreferencesPopup";
 import { useSettings } from "./utilities/app/Settings";
 import { useWindowStateSaver } from "./hooks/UseWindowStateListener";
 import { useAntdTheme, useAntdToken } from "./context/AntdThemeContext";
-import { CommonProps, FileTabData, ScriptType } from "./components/Types";
+import { CommonProps, FileTabData, LineEnding, ScriptType, TextEncoding } from "./components/Types";
 import { AppMenuToolbar } from "./menu/AppMenuToolbar";
 import { TabbedEditor } from "./components/app/TabbedEditor";
+import { ProjectSidebar } from "./components/app/ProjectSidebar";
 import {
     AppStateResult,
+    CommandError,
+    WorkspaceInfo,
+    RecoverySnapshot,
+    FileChange,
+    ProjectFile,
+    ProjectFilesChange,
+    SidebarLayout,
     addNewTab,
+    clearRecentFiles,
+    closeProjectFolder,
+    convertFileEncoding,
+    deleteWorkspace,
     getAppState,
     getNewTabId,
+    getProjectFiles,
+    getRecoverySnapshot,
+    setSidebarLayout,
+    getWorkspaces,
     isExistingFileMissingInFs,
     isFileChangedInFs,
     isFileOpened,
     loadFileState,
+    mergeFileChanges,
     openExistingFile,
+    openFileInPages,
+    openProjectFolder,
+    readFilePage,
+    recordRunRevision,
     reloadFileContents,
+    reopenClosedTab,
+    renameWorkspace,
+    reopenFileWithEncoding,
+    restoreHistoryRevision,
+    restoreRecoverySnapshot,
     saveFileContents,
+    saveMergedFile,
     saveOpenTabs,
+    saveWorkspace,
     setActiveTabId,
     setI18nLocale,
     setKeepCurrentFileInEditor,
+    switchWorkspace,
+    StateRecovery,
     test_function_call,
     updateOpenTabs,
 } from "./components/app/TauriWrappers";
@@ -39,8 +70,11 @@ import { useNotify } from "./utilities/app/Notify";
 import { useDebounce } from "./hooks/useDebounce";
 import { transpileTypeSctiptToJs } from "./utilities/app/TypeSciptTranspile";
 import { ToolBarItems } from "./menu/ToolbarItems";
-import { DialogButtons, DialogResult, PopupType, PopupTypeOk } from "./components/Enums";
+import { DialogButtons, DialogResult, PopupType, PopupTypeOk, SaveConflictResult } from "./components/Enums";
 import { ConfirmPopup } from "./components/popups/ConfirmPopup";
+import { SaveConflictPopup } from "./components/popups/SaveConflictPopup";
+import { InputPopup } from "./components/popups/InputPopup";
+import { HistoryPopup } from "./components/popups/HistoryPopup";
 import { evalueateValue, evalueateValueByLines } from "./utilities/app/Code";
 import { genNewTab, getDialogFilter, getOpenDialogFilter, saveTab } from "./utilities/app/FileTabs";
 import { MessagePopup } from "./components/popups/MessagePopup";
@@ -79,9 +113,26 @@ const App = ({ className }: AppProps) => {
     const [fileSaveQueryVisible, setFileSaveQueryVisible] = React.useState(false);
     const [messagePopupVisible, setMessagePopupVisible] = React.useState(false);
     const [messagePopupMessage, setMessagePopupMessage] = React.useState("");
+    const [fileChange, setFileChange] = React.useState<FileChange | null>(null);
+    const [saveConflictTab, setSaveConflictTab] = React.useState<FileTabData | null>(null);
+    const [recentFiles, setRecentFiles] = React.useState<string[]>([]);
+    const [workspaces, setWorkspaces] = React.useState<WorkspaceInfo[]>([]);
+    const [currentWorkspace, setCurrentWorkspace] = React.useState<string | null>(null);
+    const [workspaceInput, setWorkspaceInput] = React.useState<"save" | "rename" | null>(null);
+    const [deleteWorkspaceVisible, setDeleteWorkspaceVisible] = React.useState(false);
+    const [recoverySnapshot, setRecoverySnapshot] = React.useState<RecoverySnapshot | null>(null);
+    const [largeFile, setLargeFile] = React.useState<{ file: string; limit: number } | null>(null);
+    const [historyTab, setHistoryTab] = React.useState<FileTabData | null>(null);
+    const [projectFolder, setProjectFolder] = React.useState<string | null>(null);
+    const [projectFiles, setProjectFiles] = React.useState<ProjectFile[]>([]);
+    const [sidebar, setSidebar] = React.useState<SidebarLayout>({ visible: true, width: 250 });
 
     const fileNameRef = React.useRef<string>("");
     const lostFileNameRef = React.useRef<string>("");
+    // The identifiers of the inactive tabs whose files the file watcher reported as modified.
+    const changedTabIdsRef = React.useRef<Set<number>>(new Set());
+    // The hashes of the file system contents the unsaved merges of the tabs were made against by the tab identifier.
+    const pendingMergesRef = React.useRef<Map<number, string>>(new Map());
 
     const setSelectedValue = React.useCallback(
         (key: "language" | "oneLineEvaluation", value: unknown) => {
@@ -113,14 +164,17 @@ const App = ({ className }: AppProps) => {
         if (activeTabKey) {
             const tabScript = fileTabs.find(tab => tab.uid === activeTabKey);
             if (tabScript && settings) {
+                // Keep the version which was run in the local history.
+                recordRunRevision(tabScript).catch(error => notification("error", error));
+
                 if (tabScript.evalueate_per_line) {
-                    evalueateValueByLines(tabScript.content, settings.skip_undefined_on_js, settings.skip_empty_on_js, tabScript.script_language)
+                    evalueateValueByLines(tabScript.content, settings.skip_undefined_on_js, settings.skip_empty_on_js, tabScript.script_language, tabScript.jsx)
                         .then(value => {
                             setEvaluationResult(value.map(f => `${translate("line", "Line")} ${f}`));
                         })
                         .catch(error => notification("error", error));
                 } else {
-                    evalueateValue(tabScript.content, true, tabScript.script_language)
+                    evalueateValue(tabScript.content, true, tabScript.script_language, tabScript.jsx)
                         .then(value => {
                             setEvaluationResult(value);
                         })
@@ -133,6 +187,10 @@ const App = ({ className }: AppProps) => {
     // Updates the frontend state to the provided value from the [Rust] backend.
     const setAppStateToState = React.useCallback((state: AppStateResult) => {
         setFileTabs(state.file_tabs);
+        setRecentFiles(state.recent_files);
+        setCurrentWorkspace(state.workspace);
+        setProjectFolder(state.project_folder);
+        setSidebar(state.sidebar);
         if (state.active_tab_id !== null && state.active_tab_id > 0) {
             setActiveTabKey(state.active_tab_id);
         }
@@ -153,26 +211,84 @@ const App = ({ className }: AppProps) => {
                         evaluateActiveCode();
                     })
                     .catch(error => notification("error", error));
+
+                // Offer to restore the unsaved contents if the previous session ended uncleanly.
+                getRecoverySnapshot()
+                    .then(snapshot => setRecoverySnapshot(snapshot))
+                    .catch(error => notification("error", error));
             })
             .catch(error => notification("error", error));
 
         void setI18nLocale().catch(error => notification("error", error));
     }, [appStateLoaded, evaluateActiveCode, notification, setAppStateToState, settingsLoaded]);
 
+    // Merge the changed file into the unsaved editor content or ask to reload the file if the tab has no unsaved changes.
+    const onTabFileChanged = React.useCallback(
+        (tabScript: FileTabData) => {
+            // A file opened in pages has no unsaved changes, so the current page is just reloaded.
+            if (tabScript.read_only) {
+                readFilePage(tabScript, tabScript.page ?? 0)
+                    .then(() => getAppState())
+                    .then((result: AppStateResult) => setAppStateToState(result))
+                    .catch(error => notification("error", error));
+                return;
+            }
+
+            mergeFileChanges(tabScript)
+                .then(merge => {
+                    if (merge === null) {
+                        fileNameRef.current = tabScript.file_name;
+                        setReloadConfirmVisible(true);
+                        return;
+                    }
+
+                    pendingMergesRef.current.set(tabScript.uid, merge.theirs_hash);
+                    setFileTabs(tabs => tabs.map(tab => (tab.uid === tabScript.uid ? { ...tab, content: merge.merged, modified_at_state: new Date() } : tab)));
+
+                    if (merge.conflicts.length > 0) {
+                        notification(
+                            "warning",
+                            translate(
+                                "fileMergedWithConflicts",
+                                "The changes of the file '{{file}}' in the file system were merged into the editor with {{count}} conflicts. Resolve the marked conflicts and save the file.",
+                                { file: tabScript.file_name, count: merge.conflicts.length }
+                            )
+                        );
+                    } else {
+                        notification(
+                            "info",
+                            translate(
+                                "fileMerged",
+                                "The changes of the file '{{file}}' in the file system were merged into the editor. Save the file to keep the merged contents.",
+                                { file: tabScript.file_name }
+                            )
+                        );
+                    }
+                })
+                .catch(error => notification("error", error));
+        },
+        [notification, setAppStateToState, translate]
+    );
+
     // Check if the active tab's file has been changed in the filesystem.
     const checkTabFileChanged = React.useCallback(() => {
         const tabScript = fileTabs.find(tab => tab.uid === activeTabKey);
         if (tabScript) {
+            // The file watcher has already updated the modified date of the file, so the change is not noticed by polling.
+            if (changedTabIdsRef.current.delete(tabScript.uid)) {
+                onTabFileChanged(tabScript);
+                return;
+            }
+
             isFileChangedInFs(tabScript)
                 .then(result => {
                     if (result) {
-                        fileNameRef.current = tabScript.file_name;
-                        setReloadConfirmVisible(true);
+                        onTabFileChanged(tabScript);
                     }
                 })
                 .catch(error => notification("error", error));
         }
-    }, [fileTabs, activeTabKey, notification]);
+    }, [fileTabs, activeTabKey, notification, onTabFileChanged]);
 
     // Check if the active tab's file has disappeared from the filesystem.
     const checkFileLostFs = React.useCallback(() => {
@@ -217,6 +333,95 @@ const App = ({ className }: AppProps) => {
         }
     }, [activeTabKey, checkFileLostFs, checkTabFileChanged, enableDisableMenuToolbarItem, fileTabs, setSelectedValue]);
 
+    // Notify the user if the backend had to recover a corrupted application state.
+    React.useEffect(() => {
+        let unlistenStateRecovered: UnlistenFn | undefined;
+
+        void listen<StateRecovery>("state-recovered", event => notification("warning", event.payload.message))
+            .then(unlisten => {
+                unlistenStateRecovered = unlisten;
+            })
+            .catch(error => notification("error", error));
+
+        return () => {
+            unlistenStateRecovered?.();
+        };
+    }, [notification]);
+
+    // Listen to the changes of the open files in the file system reported by the file watcher.
+    React.useEffect(() => {
+        let unlistenFileChanged: UnlistenFn | undefined;
+
+        void listen<FileChange>("file-changed", event => setFileChange(event.payload))
+            .then(unlisten => {
+                unlistenFileChanged = unlisten;
+            })
+            .catch(error => notification("error", error));
+
+        return () => {
+            unlistenFileChanged?.();
+        };
+    }, [notification]);
+
+    // List the script files of the project folder when the folder changes.
+    React.useEffect(() => {
+        if (projectFolder === null) {
+            setProjectFiles([]);
+            return;
+        }
+
+        getProjectFiles()
+            .then(files => setProjectFiles(files))
+            .catch(error => notification("error", error));
+    }, [notification, projectFolder]);
+
+    // Listen to the script files added to or removed from the project folder reported by the project folder watcher.
+    React.useEffect(() => {
+        let unlistenProjectFilesChanged: UnlistenFn | undefined;
+
+        void listen<ProjectFilesChange>("project-files-changed", event => {
+            const { added, removed } = event.payload;
+            setProjectFiles(files =>
+                files
+                    .filter(file => !removed.some(f => f.file_name_path === file.file_name_path))
+                    .concat(added)
+                    .sort((a, b) => a.relative_path.localeCompare(b.relative_path))
+            );
+        })
+            .then(unlisten => {
+                unlistenProjectFilesChanged = unlisten;
+            })
+            .catch(error => notification("error", error));
+
+        return () => {
+            unlistenProjectFilesChanged?.();
+        };
+    }, [notification]);
+
+    // Ask to reload or keep the changed file of the active tab; the inactive tabs are checked when activated.
+    React.useEffect(() => {
+        if (fileChange === null) {
+            return;
+        }
+        setFileChange(null);
+
+        const tabScript = fileTabs.find(tab => tab.uid === fileChange.tab_id);
+        if (!tabScript) {
+            return;
+        }
+
+        if (fileChange.kind === "modified") {
+            if (tabScript.uid === activeTabKey) {
+                onTabFileChanged(tabScript);
+            } else {
+                changedTabIdsRef.current.add(tabScript.uid);
+            }
+        } else if (tabScript.uid === activeTabKey) {
+            lostFileNameRef.current = tabScript.file_name;
+            setKeepFileInEditorVisible(true);
+        }
+    }, [activeTabKey, fileChange, fileTabs, onTabFileChanged]);
+
     // Restore the window state.
     React.useEffect(() => {
         if (settingsLoaded && settings !== null) {
@@ -235,8 +440,8 @@ const App = ({ className }: AppProps) => {
 
     // Check if any popups are visible and return true if so. This will avoid the auto-save debounce to run.
     const postPoneDebounce = React.useCallback(() => {
-        return reloadConfirmVisible || keepFileInEditorVisible || fileSaveQueryVisible;
-    }, [fileSaveQueryVisible, keepFileInEditorVisible, reloadConfirmVisible]);
+        return reloadConfirmVisible || keepFileInEditorVisible || fileSaveQueryVisible || largeFile !== null || workspaceInput !== null || deleteWorkspaceVisible || recoverySnapshot !== null;
+    }, [deleteWorkspaceVisible, fileSaveQueryVisible, keepFileInEditorVisible, largeFile, recoverySnapshot, reloadConfirmVisible, workspaceInput]);
 
     // A debounced callback to save the current file tabs.
     const saveFileTabs = React.useCallback(
@@ -306,6 +511,7 @@ const App = ({ className }: AppProps) => {
         const newTabs = [...fileTabs];
         const index = newTabs.findIndex(f => f.uid === activeTabKey);
         if (index !== -1 && newTabs[index].file_name_path !== null) {
+            pendingMergesRef.current.delete(newTabs[index].uid);
             reloadFileContents(newTabs[index])
                 .then(() => {
                     reloadAppState();
@@ -314,6 +520,37 @@ const App = ({ className }: AppProps) => {
         }
     }, [activeTabKey, fileTabs, reloadAppState, notification]);
 
+    // A callback to convert the current file into another text encoding or line endings on the next save.
+    const convertCurrentFileEncoding = React.useCallback(
+        (encoding?: TextEncoding, lineEnding?: LineEnding) => {
+            const tab = fileTabs.find(f => f.uid === activeTabKey);
+            if (tab) {
+                convertFileEncoding(tab, encoding ?? tab.encoding ?? "utf-8", lineEnding ?? tab.line_ending ?? "lf")
+                    .then(() => {
+                        reloadAppState();
+                    })
+                    .catch(error => notification("error", error));
+            }
+        },
+        [activeTabKey, fileTabs, reloadAppState, notification]
+    );
+
+    // A callback to reopen the current file from the disk with another text encoding.
+    const reopenCurrentFileWithEncoding = React.useCallback(
+        (encoding: TextEncoding) => {
+            const tab = fileTabs.find(f => f.uid === activeTabKey);
+            if (tab && tab.file_name_path !== null) {
+                pendingMergesRef.current.delete(tab.uid);
+                reopenFileWithEncoding(tab, encoding)
+                    .then(() => {
+                        reloadAppState();
+                    })
+                    .catch(error => notification("error", error));
+            }
+        },
+        [activeTabKey, fileTabs, reloadAppState, notification]
+    );
+
     // A callback to open an existing file.
     const openExistingFileWrapped = React.useCallback(
         (filePath: string) => {
@@ -329,7 +566,14 @@ const App = ({ className }: AppProps) => {
                         })
                         .catch(error => notification("error", error));
                 })
-                .catch(error => notification("error", error));
+                .catch(error => {
+                    // Offer to open the file read-only in pages if it is too large to edit.
+                    if (error instanceof CommandError && error.code === "FileTooLarge") {
+                        setLargeFile({ file: filePath, limit: Math.floor(Number(error.params.limit) / 1_000_000) });
+                        return;
+                    }
+                    notification("error", error);
+                });
         },
         [notification, setAppStateToState]
     );
@@ -347,24 +591,204 @@ const App = ({ className }: AppProps) => {
             .catch(error => notification("error", error));
     }, [notification, setAppStateToState]);
 
-    // Enable or disable the "Save", "Save As" and "Evaluate Code" menu and toolbar items
+    // Enable or disable the "Save", "Save As", "Encoding", "Local History" and "Evaluate Code" menu and toolbar items
     // based on the currently selected tab.
     React.useEffect(() => {
         const tab = fileTabs.findIndex(tab => tab.uid === activeTabKey);
         enableDisableMenuToolbarItem("save", tab !== -1);
         enableDisableMenuToolbarItem("saveAs", tab !== -1);
+        enableDisableMenuToolbarItem("encodingMenu", tab !== -1);
+        enableDisableMenuToolbarItem("localHistory", tab !== -1);
         enableDisableMenuToolbarItem("evaluateCode", tab !== -1);
     }, [activeTabKey, enableDisableMenuToolbarItem, fileTabs]);
 
+    // Enable or disable the "Close Folder" and "Toggle Sidebar" menu items based on whether a project folder is open.
+    React.useEffect(() => {
+        enableDisableMenuToolbarItem("closeFolder", projectFolder !== null);
+        enableDisableMenuToolbarItem("toggleSidebar", projectFolder !== null);
+    }, [enableDisableMenuToolbarItem, projectFolder]);
+
     // A callback to save the tab specified by the tab key.
     const saveFileCallback = React.useCallback(
         async (tabkey: number) => {
-            return saveTab(tabkey, fileTabs, translate, saveAppStateReload, notification);
+            // A merged tab is saved only if the file has not changed again since the merge.
+            const theirsHash = pendingMergesRef.current.get(tabkey);
+            const tab = fileTabs.find(f => f.uid === tabkey);
+            if (theirsHash !== undefined && tab) {
+                try {
+                    await saveMergedFile(tab, theirsHash);
+                    pendingMergesRef.current.delete(tabkey);
+                    await saveAppStateReload();
+                    return true;
+                } catch (error) {
+                    notification("error", error);
+                    return false;
+                }
+            }
+
+            return saveTab(tabkey, fileTabs, translate, saveAppStateReload, notification, setSaveConflictTab);
         },
         [fileTabs, translate, saveAppStateReload, notification]
     );
 
+    // A callback to save the tab into a file selected with the save dialog.
+    const saveFileAsCallback = React.useCallback(
+        (tab: FileTabData) => {
+            save(getDialogFilter(translate, tab))
+                .then((fileName: string | null) => {
+                    if (fileName) {
+                        saveFileContents(tab, fileName)
+                            .then(() => saveAppStateReload())
+                            .catch(error => notification("error", error));
+                    }
+                })
+                .catch(error => notification("error", error));
+        },
+        [notification, saveAppStateReload, translate]
+    );
+
+    // A callback after the save conflict popup is closed with the selected action.
+    const onSaveConflictClose = React.useCallback(
+        (result: SaveConflictResult) => {
+            const tab = saveConflictTab;
+            setSaveConflictTab(null);
+            if (tab === null) {
+                return;
+            }
+
+            switch (result) {
+                case SaveConflictResult.Overwrite: {
+                    saveFileContents(tab, tab.file_name_path, true)
+                        .then(() => saveAppStateReload())
+                        .catch(error => notification("error", error));
+                    break;
+                }
+                case SaveConflictResult.Merge: {
+                    onTabFileChanged(tab);
+                    break;
+                }
+                case SaveConflictResult.SaveAs: {
+                    saveFileAsCallback(tab);
+                    break;
+                }
+                default: {
+                    break;
+                }
+            }
+        },
+        [notification, onTabFileChanged, saveAppStateReload, saveConflictTab, saveFileAsCallback]
+    );
+
+    // A callback after the local history popup is closed with the revision to restore.
+    const onHistoryClose = React.useCallback(
+        (revisionId: number | null) => {
+            const tab = historyTab;
+            setHistoryTab(null);
+            if (tab === null || revisionId === null) {
+                return;
+            }
+
+            // Only the restored tab is updated, so the unsaved edits of the other tabs are kept.
+            restoreHistoryRevision(tab, revisionId)
+                .then(content => {
+                    setFileTabs(tabs => tabs.map(f => (f.uid === tab.uid && f.content !== content ? { ...f, content, modified_at_state: new Date() } : f)));
+                })
+                .catch(error => notification("error", error));
+        },
+        [historyTab, notification]
+    );
+
+    // A callback to open a file selected from the project folder sidebar; an already opened file is activated.
+    const onProjectFileOpen = React.useCallback(
+        (file: string) => {
+            const tab = fileTabs.find(f => f.file_name_path === file);
+            if (tab) {
+                setActiveTabKey(tab.uid);
+            } else {
+                openFileIfNotOpened(file);
+            }
+        },
+        [fileTabs, openFileIfNotOpened]
+    );
+
+    // A callback to close the project folder.
+    const closeProjectFolderCallback = React.useCallback(() => {
+        closeProjectFolder()
+            .then(() => {
+                setProjectFolder(null);
+                return saveOpenTabs();
+            })
+            .catch(error => notification("error", error));
+    }, [notification]);
+
+    // A callback to update the layout of the project folder sidebar saved with the open tabs and the workspaces.
+    const updateSidebar = React.useCallback(
+        (layout: SidebarLayout) => {
+            setSidebar(layout);
+            setSidebarLayout(layout).catch(error => notification("error", error));
+        },
+        [notification]
+    );
+
+    // A callback to resize the project folder sidebar.
+    const onSidebarResize = React.useCallback(
+        (width: number) => {
+            updateSidebar({ ...sidebar, width });
+        },
+        [sidebar, updateSidebar]
+    );
+
     // A callback to handle menu item and toolbar item clicks.
+    // A callback to reload the saved workspaces.
+    const reloadWorkspaces = React.useCallback(() => {
+        getWorkspaces()
+            .then(result => setWorkspaces(result))
+            .catch(error => notification("error", error));
+    }, [notification]);
+
+    // Reload the saved workspaces when the workspace of the open tabs changes.
+    React.useEffect(() => {
+        if (appStateLoaded) {
+            reloadWorkspaces();
+        }
+    }, [appStateLoaded, currentWorkspace, reloadWorkspaces]);
+
+    // A callback to update the tabs in the editor and the active tab into the application state before storing them into a workspace.
+    const syncTabsForWorkspace = React.useCallback(() => {
+        return updateOpenTabs(fileTabs).then(() => setActiveTabId(activeTabKey));
+    }, [activeTabKey, fileTabs]);
+
+    // A callback to switch to another workspace. The tabs in the editor are saved to the current workspace first.
+    const switchToWorkspace = React.useCallback(
+        (name: string) => {
+            syncTabsForWorkspace()
+                .then(() => switchWorkspace(name))
+                .then(() => {
+                    reloadAppState();
+                    reloadWorkspaces();
+                })
+                .catch(error => notification("error", error));
+        },
+        [notification, reloadAppState, reloadWorkspaces, syncTabsForWorkspace]
+    );
+
+    // A callback to open an existing file unless the file is already opened.
+    const openFileIfNotOpened = React.useCallback(
+        (file: string) => {
+            isFileOpened(file)
+                .then(opened => {
+                    if (opened) {
+                        setMessagePopupMessage(translate("fileAlreadyOpened", "The file '{{file}}' is already opened in the editor.", { file }));
+                        setMessagePopupVisible(true);
+                    } else {
+                        void openExistingFileWrapped(file).catch(error => notification("error", error));
+                    }
+                })
+                .catch(error => notification("error", error));
+        },
+        [notification, openExistingFileWrapped, translate]
+    );
+
     const onMenuItemClick = React.useCallback(
         (key: unknown, checked?: boolean) => {
             const keyValue = key as MenuKeys;
@@ -389,14 +813,21 @@ const App = ({ className }: AppProps) => {
                     void open(getOpenDialogFilter(translate))
                         .then(files => {
                             if (files) {
-                                isFileOpened(files)
-                                    .then(opened => {
-                                        if (opened) {
-                                            setMessagePopupMessage(translate("fileAlreadyOpened", "The file '{{file}}' is already opened in the editor.", { file: files }));
-                                            setMessagePopupVisible(true);
-                                        } else {
-                                            void openExistingFileWrapped(files).catch(error => notification("error", error));
-                                        }
+                                openFileIfNotOpened(files);
+                            }
+                        })
+                        .catch(error => notification("error", error));
+                    break;
+                }
+                case "openFolder": {
+                    void open({ directory: true })
+                        .then(folder => {
+                            if (folder) {
+                                openProjectFolder(folder)
+                                    .then(files => {
+                                        setProjectFolder(folder);
+                                        setProjectFiles(files);
+                                        return saveOpenTabs();
                                     })
                                     .catch(error => notification("error", error));
                             }
@@ -404,6 +835,38 @@ const App = ({ className }: AppProps) => {
                         .catch(error => notification("error", error));
                     break;
                 }
+                case "closeFolder": {
+                    closeProjectFolderCallback();
+                    break;
+                }
+                case "toggleSidebar": {
+                    updateSidebar({ ...sidebar, visible: !sidebar.visible });
+                    break;
+                }
+                case "clearRecentFiles": {
+                    clearRecentFiles()
+                        .then(() => reloadAppState())
+                        .catch(error => notification("error", error));
+                    break;
+                }
+                case "saveWorkspace": {
+                    setWorkspaceInput("save");
+                    break;
+                }
+                case "renameWorkspace": {
+                    setWorkspaceInput("rename");
+                    break;
+                }
+                case "deleteWorkspace": {
+                    setDeleteWorkspaceVisible(true);
+                    break;
+                }
+                case "reopenClosedTab": {
+                    reopenClosedTab()
+                        .then(() => reloadAppState())
+                        .catch(error => notification("error", error));
+                    break;
+                }
                 case "addNewTab": {
                     getNewTabId()
                         .then(uid => {
@@ -424,6 +887,46 @@ const App = ({ className }: AppProps) => {
                     reloadCurrentFileContents();
                     break;
                 }
+                case "localHistory": {
+                    setHistoryTab(fileTabs.find(f => f.uid === activeTabKey) ?? null);
+                    break;
+                }
+                case "convertToUtf8": {
+                    convertCurrentFileEncoding("utf-8");
+                    break;
+                }
+                case "convertToUtf8Bom": {
+                    convertCurrentFileEncoding("utf-8-bom");
+                    break;
+                }
+                case "convertToUtf16Le": {
+                    convertCurrentFileEncoding("utf-16-le");
+                    break;
+                }
+                case "convertToUtf16Be": {
+                    convertCurrentFileEncoding("utf-16-be");
+                    break;
+                }
+                case "convertToWindows1252": {
+                    convertCurrentFileEncoding("windows-1252");
+                    break;
+                }
+                case "convertToLf": {
+                    convertCurrentFileEncoding(undefined, "lf");
+                    break;
+                }
+                case "convertToCrLf": {
+                    convertCurrentFileEncoding(undefined, "crlf");
+                    break;
+                }
+                case "reopenAsUtf8": {
+                    reopenCurrentFileWithEncoding("utf-8");
+                    break;
+                }
+                case "reopenAsWindows1252": {
+                    reopenCurrentFileWithEncoding("windows-1252");
+                    break;
+                }
                 case "convertToJs": {
                     const index = fileTabs.findIndex(f => f.uid === activeTabKey);
                     if (index !== -1 && fileTabs[index].script_language === "typescript") {
@@ -432,7 +935,7 @@ const App = ({ className }: AppProps) => {
                                 let newFileName = translate("newFileWithIndex", "New file {{index}}", { index: uid });
                                 newFileName += ".js";
 
-                                void addNewTab(genNewTab("javascript", newFileName), transpileTypeSctiptToJs(fileTabs[index].content ?? "", true))
+                                void addNewTab(genNewTab("javascript", newFileName), transpileTypeSctiptToJs(fileTabs[index].content ?? "", true, fileTabs[index].jsx))
                                     .then(() => {
                                         saveAppStateReload().catch(error => notification("error", error));
                                     })
@@ -453,16 +956,7 @@ const App = ({ className }: AppProps) => {
                 case "saveAs": {
                     const index = fileTabs.findIndex(f => f.uid === activeTabKey);
                     if (index !== -1) {
-                        const tab = fileTabs[index];
-                        save(getDialogFilter(translate, tab))
-                            .then((fileName: string | null) => {
-                                if (fileName) {
-                                    saveFileContents(tab, fileName)
-                                        .then(() => saveAppStateReload())
-                                        .catch(error => notification("error", error));
-                                }
-                            })
-                            .catch(error => notification("error", error));
+                        saveFileAsCallback(fileTabs[index]);
                     }
                     break;
                 }
@@ -497,6 +991,20 @@ const App = ({ className }: AppProps) => {
                     break;
                 }
                 default: {
+                    // The recent files have the menu keys by their index, e.g. `recentFile0`.
+                    if (keyValue.startsWith("recentFile")) {
+                        const file = recentFiles[Number.parseInt(keyValue.substring("recentFile".length))];
+                        if (file) {
+                            openFileIfNotOpened(file);
+                        }
+                    }
+                    // The workspaces have the menu keys by their index, e.g. `workspace0`.
+                    if (keyValue.startsWith("workspace")) {
+                        const workspace = workspaces[Number.parseInt(keyValue.substring("workspace".length))];
+                        if (workspace && !workspace.is_current) {
+                            switchToWorkspace(workspace.name);
+                        }
+                    }
                     break;
                 }
             }
@@ -504,17 +1012,27 @@ const App = ({ className }: AppProps) => {
         [
             activeTabKey,
             appWindow,
+            closeProjectFolderCallback,
+            convertCurrentFileEncoding,
             evaluateActiveCode,
             fileTabs,
             notification,
             onClose,
-            openExistingFileWrapped,
+            openFileIfNotOpened,
+            recentFiles,
+            reloadAppState,
             reloadCurrentFileContents,
+            reopenCurrentFileWithEncoding,
             saveAppStateReload,
+            saveFileAsCallback,
             saveFileCallback,
             selectedValues,
             setSelectedValue,
+            sidebar,
+            switchToWorkspace,
             translate,
+            updateSidebar,
+            workspaces,
         ]
     );
 
@@ -624,6 +1142,77 @@ const App = ({ className }: AppProps) => {
         [reloadAppState, reloadCurrentFileContents]
     );
 
+    // A callback after the open in pages confirm popup is closed and a result from the popup is received.
+    const onLargeFileConfirmClose = React.useCallback(
+        (result: DialogResult) => {
+            const file = largeFile?.file;
+            setLargeFile(null);
+            if (result === DialogResult.Yes && file) {
+                openFileInPages(file)
+                    .then(() => saveAppStateReload())
+                    .catch(error => notification("error", error));
+            }
+        },
+        [largeFile, notification, saveAppStateReload]
+    );
+
+    // A callback to load another page of a file opened read-only in pages.
+    const onPageChange = React.useCallback(
+        (tab: FileTabData, page: number) => {
+            readFilePage(tab, page)
+                .then(() => reloadAppState())
+                .catch(error => notification("error", error));
+        },
+        [notification, reloadAppState]
+    );
+
+    // A callback after the recovery confirm popup is closed; the unsaved contents are restored or discarded.
+    const onRecoveryConfirmClose = React.useCallback(
+        (result: DialogResult) => {
+            setRecoverySnapshot(null);
+            restoreRecoverySnapshot(result === DialogResult.Yes)
+                .then(() => reloadAppState())
+                .catch(error => notification("error", error));
+        },
+        [notification, reloadAppState]
+    );
+
+    // A callback after the workspace name input popup is closed; the open tabs are saved as a workspace or the workspace is renamed.
+    const onWorkspaceInputClose = React.useCallback(
+        (name: string | null) => {
+            const mode = workspaceInput;
+            setWorkspaceInput(null);
+            if (name === null) {
+                return;
+            }
+
+            const action = mode === "rename" && currentWorkspace !== null ? renameWorkspace(currentWorkspace, name) : syncTabsForWorkspace().then(() => saveWorkspace(name));
+            action
+                .then(() => {
+                    reloadAppState();
+                    reloadWorkspaces();
+                })
+                .catch(error => notification("error", error));
+        },
+        [currentWorkspace, notification, reloadAppState, reloadWorkspaces, syncTabsForWorkspace, workspaceInput]
+    );
+
+    // A callback after the delete workspace confirm popup is closed and a result from the popup is received.
+    const onDeleteWorkspaceClose = React.useCallback(
+        (result: DialogResult) => {
+            setDeleteWorkspaceVisible(false);
+            if (result === DialogResult.Yes && currentWorkspace !== null) {
+                deleteWorkspace(currentWorkspace)
+                    .then(() => {
+                        reloadAppState();
+                        reloadWorkspaces();
+                    })
+                    .catch(error => notification("error", error));
+            }
+        },
+        [currentWorkspace, notification, reloadAppState, reloadWorkspaces]
+    );
+
     // A callback after the keep file in editor confirm popup is closed and a result from the popup is received.
     const keepFileInEditorConfirmClose = React.useCallback(
         (result: DialogResult) => {
@@ -680,33 +1269,49 @@ const App = ({ className }: AppProps) => {
                 onSelectChange={onSelectedValueChanged}
                 disabledItems={disabledItems}
                 darkMode={previewDarkMode ?? settings.dark_mode ?? false}
+                recentFiles={recentFiles}
+                workspaces={workspaces}
             />
             <div className={classNames(App.name, className)}>
-                <div id="mainView" className="App-itemsView">
-                    <TabbedEditor //
-                        darkMode={previewDarkMode ?? settings.dark_mode ?? false}
-                        fileTabs={fileTabs}
-                        activeTabKey={activeTabKey}
-                        settings={settings}
-                        fileSaveQueryVisible={fileSaveQueryVisible}
-                        setActiveTabKey={setActiveTabKey}
-                        onNewOutput={onNewOutput}
-                        setFileTabs={setFileTabs}
-                        setActiveTabScriptType={setScriptStype}
-                        saveFileTabs={saveFileTabs}
-                        saveTab={saveFileCallback}
-                        notification={notification}
-                        setFileSaveQueryVisible={setFileSaveQueryVisible}
-                    />
-                    <div className="EditorResultContainer">
-                        {translate("result", "Result")}
-                        <Editor //
-                            // eslint-disable-next-line prettier/prettier
-                            theme={(previewDarkMode ?? settings.dark_mode ?? false) ? "vs-dark" : "light"}
-                            className="EditorResult"
-                            value={evaluateEditorValue}
-                            options={{ readOnly: true }}
+                <div className="App-projectView">
+                    {projectFolder !== null && sidebar.visible && (
+                        <ProjectSidebar //
+                            folder={projectFolder}
+                            files={projectFiles}
+                            activeFile={fileTabs.find(f => f.uid === activeTabKey)?.file_name_path}
+                            width={sidebar.width}
+                            onFileOpen={onProjectFileOpen}
+                            onClose={closeProjectFolderCallback}
+                            onResize={onSidebarResize}
+                        />
+                    )}
+                    <div id="mainView" className="App-itemsView">
+                        <TabbedEditor //
+                            darkMode={previewDarkMode ?? settings.dark_mode ?? false}
+                            fileTabs={fileTabs}
+                            activeTabKey={activeTabKey}
+                            settings={settings}
+                            fileSaveQueryVisible={fileSaveQueryVisible}
+                            setActiveTabKey={setActiveTabKey}
+                            onNewOutput={onNewOutput}
+                            setFileTabs={setFileTabs}
+                            setActiveTabScriptType={setScriptStype}
+                            saveFileTabs={saveFileTabs}
+                            saveTab={saveFileCallback}
+                            onPageChange={onPageChange}
+                            notification={notification}
+                            setFileSaveQueryVisible={setFileSaveQueryVisible}
                         />
+                        <div className="EditorResultContainer">
+                            {translate("result", "Result")}
+                            <Editor //
+                                // eslint-disable-next-line prettier/prettier
+                                theme={(previewDarkMode ?? settings.dark_mode ?? false) ? "vs-dark" : "light"}
+                                className="EditorResult"
+                                value={evaluateEditorValue}
+                                options={{ readOnly: true }}
+                            />
+                        </div>
                     </div>
                 </div>
             </div>
@@ -741,6 +1346,60 @@ const App = ({ className }: AppProps) => {
                 buttons={DialogButtons.Yes | DialogButtons.No}
                 onClose={keepFileInEditorConfirmClose}
             />
+            <ConfirmPopup //
+                visible={largeFile !== null}
+                mode={PopupType.Confirm}
+                message={translate("openFileInPages", "The file '{{file}}' is larger than the {{limit}} MB limit. Open the file read-only in pages?", {
+                    file: largeFile?.file,
+                    limit: largeFile?.limit,
+                })}
+                buttons={DialogButtons.Yes | DialogButtons.No}
+                onClose={onLargeFileConfirmClose}
+            />
+            <ConfirmPopup //
+                visible={recoverySnapshot !== null}
+                mode={PopupType.Confirm}
+                message={translate("restoreUnsavedContents", "The application was not shut down properly. Restore the unsaved contents of {{count}} tabs saved at {{time}}?", {
+                    count: recoverySnapshot?.tabs.length,
+                    time: recoverySnapshot ? new Date(recoverySnapshot.saved_at).toLocaleString() : "",
+                })}
+                buttons={DialogButtons.Yes | DialogButtons.No}
+                onClose={onRecoveryConfirmClose}
+            />
+            <InputPopup //
+                visible={workspaceInput !== null}
+                title={translate("workspaceName", "Workspace name")}
+                message={
+                    workspaceInput === "rename"
+                        ? translate("renameWorkspaceMessage", "The new name of the workspace '{{name}}':", { name: currentWorkspace })
+                        : translate("saveWorkspaceMessage", "Save the open tabs as a workspace with the name:")
+                }
+                initialValue={workspaceInput === "rename" ? (currentWorkspace ?? "") : ""}
+                onClose={onWorkspaceInputClose}
+            />
+            <ConfirmPopup //
+                visible={deleteWorkspaceVisible}
+                mode={PopupType.Confirm}
+                message={translate("deleteWorkspaceConfirm", "Delete the workspace '{{name}}'? The open tabs are kept.", { name: currentWorkspace })}
+                buttons={DialogButtons.Yes | DialogButtons.No}
+                onClose={onDeleteWorkspaceClose}
+            />
+            <HistoryPopup //
+                visible={historyTab !== null}
+                tab={historyTab}
+                darkMode={previewDarkMode ?? settings.dark_mode ?? false}
+                notification={notification}
+                onClose={onHistoryClose}
+            />
+            <SaveConflictPopup //
+                visible={saveConflictTab !== null}
+                message={translate(
+                    "saveConflict",
+                    "The file '{{file}}' was changed outside the editor after it was loaded. Overwrite the file, merge the changes or save the file with another name.",
+                    { file: saveConflictTab?.file_name }
+                )}
+                onClose={onSaveConflictClose}
+            />
             <MessagePopup //
                 visible={messagePopupVisible}
                 onClose={onMessagePopupClose}
@@ -755,12 +1414,19 @@ const SyledApp = styled(App)`
     height: 100%;
     width: 100%;
     display: contents;
+    .App-projectView {
+        display: flex;
+        flex: auto;
+        flex-direction: row;
+        min-height: 0px;
+    }
     .App-itemsView {
         display: flex;
         flex: auto; // 100% breaks this
         width: 100%;
         flex-direction: column;
         min-height: 0px;
+        min-width: 0px;
     }
     .TabsContainer {
         height: 100%;
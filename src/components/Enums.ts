@@ -91,3 +91,17 @@ export enum PopupTypeOk {
     /** The popup is a error popup. */
     Error,
 }
+
+/**
+ * An enumeration of the button clicked in the {@link SaveConflictPopup}.
+ */
+export enum SaveConflictResult {
+    /** The file is overwritten with the editor contents. */
+    Overwrite,
+    /** The file changes are merged into the editor contents. */
+    Merge,
+    /** The editor contents are saved into another file. */
+    SaveAs,
+    /** The save is canceled. */
+    Cancel,
+}
@@ -24,6 +24,10 @@ type FileTabData = {
     is_temporary: boolean;
     /** The language of the script. */
     script_language: ScriptType;
+    /** A flag indicating whether the script has JSX elements, i.e. the file is a `.jsx` or a `.tsx` file. */
+    jsx?: boolean;
+    /** The module system of the script file. */
+    module_kind?: ModuleKind;
     /** The content of the file. */
     content: string | null;
     /** The last modified date of the file. */
@@ -32,10 +36,41 @@ type FileTabData = {
     modified_at_state: Date | null;
     /** A flag indicating whether to evaluate each line separately or the entire file content at once. */
     evalueate_per_line: boolean;
+    /** The SHA-256 hash of the file contents in the file system when the file was last opened, reloaded or saved. */
+    content_hash?: string | null;
+    /** The size of the file contents in bytes in the file system when the file was last opened, reloaded or saved. */
+    content_size?: number | null;
+    /** The persistent unique identifier of a temporary tab keying its revisions in the local history. */
+    history_id?: string | null;
+    /** The text encoding of the file in the file system. */
+    encoding?: TextEncoding;
+    /** The line endings of the file in the file system. */
+    line_ending?: LineEnding;
+    /** A flag indicating whether the file is too large to edit and is opened read-only in pages. */
+    read_only?: boolean;
+    /** The zero-based number of the page shown of a file opened in pages. */
+    page?: number;
+    /** The number of the pages of a file opened in pages. */
+    page_count?: number;
 };
 
 type ScriptType = "typescript" | "javascript";
 
+/**
+ * The module system of a script file.
+ */
+type ModuleKind = "esmodule" | "commonjs";
+
+/**
+ * The text encoding of a file.
+ */
+type TextEncoding = "utf-8" | "utf-8-bom" | "utf-16-le" | "utf-16-be" | "windows-1252";
+
+/**
+ * The line endings of a file.
+ */
+type LineEnding = "lf" | "crlf";
+
 type Cursor =
     | "alias"
     | "all-scroll"
@@ -74,4 +109,4 @@ type Cursor =
     | "zoom-in"
     | "zoom-out";
 
-export type { CommonProps, FileTabData, ScriptType, Cursor };
+export type { CommonProps, FileTabData, ScriptType, ModuleKind, TextEncoding, LineEnding, Cursor };
@@ -0,0 +1,225 @@
+/*
+MIT License
+
+Copyright (c) 2024 VPKSoft
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+import * as React from "react";
+import { styled } from "styled-components";
+import classNames from "classnames";
+import { Button, Tooltip, Tree } from "antd";
+import { DataNode } from "antd/es/tree";
+import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
+import { faXmark } from "@fortawesome/free-solid-svg-icons";
+import { CommonProps } from "../Types";
+import { ProjectFile } from "./TauriWrappers";
+import { useTranslate } from "../../localization/Localization";
+
+/**
+ * The props for the {@link ProjectSidebar} component.
+ */
+type ProjectSidebarProps = {
+    /** The path of the project folder. */
+    folder: string;
+    /** The script files of the project folder. */
+    files: ProjectFile[];
+    /** The name and path of the file of the active tab. */
+    activeFile?: string | null;
+    /** The width of the sidebar in pixels. */
+    width: number;
+    /** Occurs when the sidebar has been resized by dragging its edge. The new width in pixels is passed as a parameter to the callback. */
+    onResize: (width: number) => void;
+    /** Occurs when a file is selected from the file tree. The name and path of the file is passed as a parameter to the callback. */
+    onFileOpen: (fileNamePath: string) => void;
+    /** Occurs when the close button of the project folder is clicked. */
+    onClose: () => void;
+} & CommonProps;
+
+/** The minimum width of the sidebar in pixels. */
+const MIN_SIDEBAR_WIDTH = 150;
+
+/**
+ * Builds the file tree nodes of the project files. The directories are listed before the files.
+ * @param files The script files of the project folder.
+ * @returns The root nodes of the file tree.
+ */
+const buildTree = (files: ProjectFile[]): DataNode[] => {
+    const root: DataNode[] = [];
+
+    for (const file of files) {
+        const parts = file.relative_path.split("/");
+        let nodes = root;
+        let directory = "";
+
+        for (const part of parts.slice(0, -1)) {
+            directory = directory === "" ? part : `${directory}/${part}`;
+            let node = nodes.find(f => f.key === `directory:${directory}`);
+            if (!node) {
+                node = { key: `directory:${directory}`, title: part, children: [] };
+                nodes.push(node);
+            }
+            nodes = node.children ?? [];
+        }
+
+        nodes.push({ key: file.file_name_path, title: file.file_name, isLeaf: true });
+    }
+
+    const sortNodes = (nodes: DataNode[]) => {
+        nodes.sort((a, b) => Number(a.isLeaf ?? false) - Number(b.isLeaf ?? false) || String(a.title).localeCompare(String(b.title)));
+        for (const node of nodes) {
+            if (node.children) {
+                sortNodes(node.children);
+            }
+        }
+    };
+    sortNodes(root);
+
+    return root;
+};
+
+/**
+ * A sidebar component listing the script files of the project folder as a file tree.
+ * @param param0 The component props: {@link ProjectSidebarProps}.
+ * @returns A component.
+ */
+const ProjectSidebarComponent = ({
+    className, //
+    folder,
+    files,
+    activeFile,
+    width,
+    onFileOpen,
+    onClose,
+    onResize,
+}: ProjectSidebarProps) => {
+    // The i18n translation hook.
+    const { translate } = useTranslate();
+
+    // The width while the edge of the sidebar is being dragged.
+    const [dragWidth, setDragWidth] = React.useState<number | null>(null);
+
+    const treeData = React.useMemo(() => buildTree(files), [files]);
+
+    // The name of the project folder without its path.
+    const folderName = React.useMemo(() => folder.split(/[\\/]/).filter(f => f !== "").pop() ?? folder, [folder]);
+
+    // A node of the file tree was selected; the files are opened and the directories just expand.
+    const onSelect = React.useCallback(
+        (_: React.Key[], info: { node: DataNode }) => {
+            if (info.node.isLeaf) {
+                onFileOpen(String(info.node.key));
+            }
+        },
+        [onFileOpen]
+    );
+
+    // The edge of the sidebar was pressed; the sidebar is resized until the mouse button is released.
+    const onResizeStart = React.useCallback(
+        (event: React.MouseEvent) => {
+            event.preventDefault();
+            const startX = event.clientX;
+            const newWidth = (clientX: number) => Math.max(MIN_SIDEBAR_WIDTH, Math.round(width + clientX - startX));
+
+            const onMouseMove = (moveEvent: MouseEvent) => {
+                setDragWidth(newWidth(moveEvent.clientX));
+            };
+
+            const onMouseUp = (upEvent: MouseEvent) => {
+                window.removeEventListener("mousemove", onMouseMove);
+                window.removeEventListener("mouseup", onMouseUp);
+                setDragWidth(null);
+                onResize(newWidth(upEvent.clientX));
+            };
+
+            window.addEventListener("mousemove", onMouseMove);
+            window.addEventListener("mouseup", onMouseUp);
+        },
+        [onResize, width]
+    );
+
+    const currentWidth = dragWidth ?? width;
+
+    return (
+        <div //
+            className={classNames(ProjectSidebarComponent.name, className)}
+            style={{ width: currentWidth, minWidth: currentWidth }}
+        >
+            <div className="ProjectSidebar-header">
+                <Tooltip title={folder}>
+                    <div className="ProjectSidebar-title">{folderName}</div>
+                </Tooltip>
+                <Tooltip title={translate("closeFolder", "Close Folder")}>
+                    <Button //
+                        type="text"
+                        size="small"
+                        icon={<FontAwesomeIcon icon={faXmark} />}
+                        onClick={onClose}
+                    />
+                </Tooltip>
+            </div>
+            <Tree.DirectoryTree //
+                className="ProjectSidebar-tree"
+                treeData={treeData}
+                selectedKeys={activeFile ? [activeFile] : []}
+                onSelect={onSelect}
+                expandAction="click"
+                defaultExpandAll
+            />
+            <div className="ProjectSidebar-resizer" onMouseDown={onResizeStart} />
+        </div>
+    );
+};
+
+const ProjectSidebar = styled(ProjectSidebarComponent)`
+    display: flex;
+    position: relative;
+    flex-direction: column;
+    min-height: 0;
+    .ProjectSidebar-header {
+        display: flex;
+        flex-direction: row;
+        align-items: center;
+        justify-content: space-between;
+        padding: 4px;
+    }
+    .ProjectSidebar-title {
+        font-weight: bold;
+        overflow: hidden;
+        text-overflow: ellipsis;
+        white-space: nowrap;
+    }
+    .ProjectSidebar-tree {
+        overflow: auto;
+        flex: auto;
+        min-height: 0;
+    }
+    .ProjectSidebar-resizer {
+        position: absolute;
+        top: 0;
+        right: 0;
+        bottom: 0;
+        width: 4px;
+        cursor: col-resize;
+    }
+`;
+
+export { ProjectSidebar };
@@ -25,8 +25,9 @@ SOFTWARE.
 import * as React from "react";
 import { styled } from "styled-components";
 import classNames from "classnames";
-import { Editor } from "@monaco-editor/react";
+import { Editor, Monaco } from "@monaco-editor/react";
 import { Tab } from "rc-tabs/lib/interface";
+import { Pagination } from "antd";
 import { CommonProps, FileTabData, ScriptType } from "../Types";
 import { useDebounce } from "../../hooks/useDebounce";
 import { JavaScriptLogo, TypeScriptLogo } from "../../utilities/app/Images";
@@ -37,6 +38,7 @@ import { NotificationType } from "../../utilities/app/Notify";
 import { evalueateValue, evalueateValueByLines } from "../../utilities/app/Code";
 import { Settings } from "../../utilities/app/Settings";
 import { DraggableTabs } from "../wrappers/DraggableTabs";
+import { updateTabContent } from "./TauriWrappers";
 
 /**
  * The props for the {@link TabbedEditor} component.
@@ -54,6 +56,7 @@ type TabbedEditorProps = {
     setFileTabs: (fileTabs: FileTabData[]) => void;
     onNewOutput: (output: string | string[]) => void;
     saveTab: (activeTabKey: number) => Promise<boolean>;
+    onPageChange: (tab: FileTabData, page: number) => void;
     notification: (type: NotificationType, title: string | null | undefined | Error | unknown, duration?: number) => void;
 } & CommonProps;
 
@@ -76,6 +79,7 @@ const TabbedEditorComponent = ({
     onNewOutput,
     setFileTabs,
     saveTab,
+    onPageChange,
     notification,
 }: TabbedEditorProps) => {
     const [saveQueryResult, setSaveQueryResult] = React.useState<DialogResult | undefined>();
@@ -98,6 +102,13 @@ const TabbedEditorComponent = ({
         [fileTabs, setActiveTabKey, setActiveTabScriptType]
     );
 
+    // Allow the JSX elements in the editor models of the .jsx and .tsx files.
+    const enableJsx = React.useCallback((monaco: Monaco) => {
+        const { typescriptDefaults, javascriptDefaults, JsxEmit } = monaco.languages.typescript;
+        typescriptDefaults.setCompilerOptions({ ...typescriptDefaults.getCompilerOptions(), jsx: JsxEmit.React });
+        javascriptDefaults.setCompilerOptions({ ...javascriptDefaults.getCompilerOptions(), jsx: JsxEmit.React });
+    }, []);
+
     // The active tab's content changed.
     const onEditValueChange = React.useCallback(
         (value: string | undefined) => {
@@ -106,8 +117,11 @@ const TabbedEditorComponent = ({
             newTabs[index].content = value ?? null;
             newTabs[index].modified_at_state = new Date();
             setFileTabs(newTabs);
+
+            // The backend autosave uses the edited content until the debounced save updates the open tabs.
+            void updateTabContent(activeTabKey, value ?? "").catch(error => notification("error", error));
         },
-        [activeTabKey, fileTabs, setFileTabs]
+        [activeTabKey, fileTabs, notification, setFileTabs]
     );
 
     // Use an effect to update the tab items instead of memoizing them to allow reordering.
@@ -115,26 +129,48 @@ const TabbedEditorComponent = ({
         const items: Tab[] = [];
 
         for (const tab of fileTabs) {
+            const editor = (
+                <Editor //
+                    className="Editor"
+                    height={tab.read_only ? "calc(100% - 32px)" : "100%"}
+                    language={tab.script_language}
+                    path={tab.jsx ? `tab-${tab.uid}.${tab.script_language === "typescript" ? "tsx" : "jsx"}` : undefined}
+                    beforeMount={enableJsx}
+                    theme={darkMode ? "vs-dark" : "light"}
+                    value={tab.content ?? undefined}
+                    options={{ readOnly: tab.read_only ?? false }}
+                    onChange={onEditValueChange}
+                />
+            );
+
             items.push({
                 label: `${tab.file_name_path === null || tab.modified_at_state !== tab.modified_at ? "* " : ""}${tab.file_name}`,
                 key: tab.uid.toString(),
                 closable: true,
                 className: "TabPane",
                 icon: <img className="IconStyle" src={tab.script_language === "typescript" ? TypeScriptLogo : JavaScriptLogo} width="16px" height="16px" />,
-                children: (
-                    <Editor //
-                        className="Editor"
-                        language={tab.script_language}
-                        theme={darkMode ? "vs-dark" : "light"}
-                        value={tab.content ?? undefined}
-                        onChange={onEditValueChange}
-                    />
+                // The files opened read-only in pages have a pager above the editor.
+                children: tab.read_only ? (
+                    <>
+                        <Pagination //
+                            className="Pager"
+                            simple
+                            size="small"
+                            current={(tab.page ?? 0) + 1}
+                            total={tab.page_count ?? 1}
+                            pageSize={1}
+                            onChange={page => onPageChange(tab, page - 1)}
+                        />
+                        {editor}
+                    </>
+                ) : (
+                    editor
                 ),
             });
         }
 
         setTabItems(items);
-    }, [darkMode, fileTabs, onEditValueChange]);
+    }, [darkMode, enableJsx, fileTabs, onEditValueChange, onPageChange]);
 
     // Set the active tab key if there are opened tabs and the active tab key is not valid.
     React.useEffect(() => {
@@ -143,15 +179,21 @@ const TabbedEditorComponent = ({
         }
     }, [fileTabs, activeTabKey, setActiveTabKey]);
 
-    const [newContent, setNewContent] = React.useState<{ content: string | null; script_language: ScriptType; evalueate_per_line: boolean } | null>(null);
+    const [newContent, setNewContent] = React.useState<{ content: string | null; script_language: ScriptType; jsx: boolean; evalueate_per_line: boolean } | null>(null);
 
     // Keep the current tab data the same if it has not actually been changed, so the code won't be re-evaluated all the time.
     React.useEffect(() => {
         const tab = fileTabs.find(f => f.uid === activeTabKey);
-        if (tab && (newContent?.content !== tab.content || newContent?.script_language !== tab.script_language || newContent?.evalueate_per_line !== tab.evalueate_per_line)) {
-            setNewContent({ content: tab.content, script_language: tab.script_language, evalueate_per_line: tab.evalueate_per_line });
+        const jsx = tab?.jsx ?? false;
+        // A page of a file opened in pages is not a complete script to evaluate.
+        if (
+            tab &&
+            !tab.read_only &&
+            (newContent?.content !== tab.content || newContent?.script_language !== tab.script_language || newContent?.jsx !== jsx || newContent?.evalueate_per_line !== tab.evalueate_per_line)
+        ) {
+            setNewContent({ content: tab.content, script_language: tab.script_language, jsx, evalueate_per_line: tab.evalueate_per_line });
         }
-    }, [activeTabKey, fileTabs, newContent?.content, newContent?.evalueate_per_line, newContent?.script_language]);
+    }, [activeTabKey, fileTabs, newContent?.content, newContent?.evalueate_per_line, newContent?.jsx, newContent?.script_language]);
 
     // Evaluate the active tab's code.
     const evalueateCallback = React.useCallback(async () => {
@@ -160,10 +202,10 @@ const TabbedEditorComponent = ({
 
             try {
                 if (newContent.evalueate_per_line && settings) {
-                    value = await evalueateValueByLines(newContent.content, settings.skip_undefined_on_js, settings.skip_empty_on_js, newContent.script_language);
+                    value = await evalueateValueByLines(newContent.content, settings.skip_undefined_on_js, settings.skip_empty_on_js, newContent.script_language, newContent.jsx);
                     value = value.map(f => `${translate("line", "Line")} ${f}`);
                 } else {
-                    value = await evalueateValue(newContent.content, true, newContent.script_language);
+                    value = await evalueateValue(newContent.content, true, newContent.script_language, newContent.jsx);
                 }
             } catch (error) {
                 notification("error", error);
@@ -312,6 +354,12 @@ const TabbedEditor = styled(TabbedEditorComponent)`
     .IconStyle {
         vertical-align: middle;
     }
+    .Pager {
+        height: 32px;
+        display: flex;
+        align-items: center;
+        justify-content: center;
+    }
 `;
 
 export { TabbedEditor };
@@ -23,19 +23,416 @@ SOFTWARE.
 */
 
 import { invoke } from "@tauri-apps/api/core";
-import { FileTabData } from "../Types";
+import { FileTabData, LineEnding, TextEncoding } from "../Types";
+
+type ErrorCode =
+    | "FileNotFound"
+    | "FileTooLarge"
+    | "FileReadFailed"
+    | "FileWriteFailed"
+    | "DirectoryReadFailed"
+    | "FilePathNotSpecified"
+    | "FileContentNotSpecified"
+    | "TabNotFound"
+    | "StateLoadFailed"
+    | "StateSaveFailed"
+    | "ScriptException"
+    | "ScriptFailed"
+    | "GlobalVariableNotFound"
+    | "LiveModeFailed"
+    | "SnapshotReadFailed"
+    | "SnapshotWriteFailed"
+    | "TaskFailed"
+    | "MergeConflictsUnresolved"
+    | "FileChangedDuringMerge"
+    | "SaveConflict"
+    | "EncodingFailed"
+    | "FileReadOnly"
+    | "WorkspaceNotFound"
+    | "WorkspaceExists"
+    | "WorkspaceNameEmpty"
+    | "HistoryFailed"
+    | "RevisionNotFound"
+    | "FolderNotFound";
+
+/**
+ * An error of a Tauri command with a code and the parameters of the error. The message is localized by the backend.
+ */
+class CommandError extends Error {
+    /** The error code, e.g. `FileNotFound`; null if the error did not come from a command. */
+    public code: ErrorCode | null;
+    /** The parameters of the error, e.g. the file name. */
+    public params: Record<string, string>;
+
+    public constructor(message: string, code: ErrorCode | null = null, params: Record<string, string> = {}) {
+        super(message);
+        this.name = "CommandError";
+        this.code = code;
+        this.params = params;
+    }
+}
+
+/**
+ * Converts an error thrown by a Tauri API call into a {@link CommandError}.
+ * @param {unknown} error - The error thrown by the Tauri API call.
+ * @returns {CommandError} The command error.
+ */
+const toCommandError = (error: unknown): CommandError => {
+    if (error !== null && typeof error === "object" && "code" in error && "message" in error) {
+        const { code, message, params } = error as { code: ErrorCode; message: string; params?: Record<string, string> };
+        return new CommandError(message, code, params ?? {});
+    }
+
+    return new CommandError(`${error}`);
+};
 
 type LineByLineLog = {
     line_number: number;
     lines: string[];
 };
 
+type ValueSnapshot = {
+    /** The type of the value, e.g. `number`, `string`, `array` or `object`. */
+    value_type: string;
+    /** A short human readable preview of the value. */
+    preview: string;
+    /** The value serialized as JSON; `null` if the value is not serializable. */
+    json: string | null;
+};
+
+type NamedValueSnapshot = {
+    /** The name of the value. */
+    name: string;
+    /** The snapshot of the value. */
+    value: ValueSnapshot;
+};
+
+type LineValueSnapshot = {
+    /** The line number the snapshots were captured for. */
+    line_number: number;
+    /** The completion value of the line; `null` if the line failed to evaluate. */
+    value: ValueSnapshot | null;
+    /** The variables assigned on the line. */
+    assigned: NamedValueSnapshot[];
+};
+
+type GlobalVariable = {
+    /** The name of the variable or the property key of the child value. */
+    name: string;
+    /** The type of the value, e.g. `number`, `string`, `array` or `object`. */
+    value_type: string;
+    /** A short human readable preview of the value. */
+    preview: string;
+    /** The handle used to expand the child values of the value; `null` if the value is not expandable. */
+    handle: number | null;
+};
+
+type LiveResult = {
+    /** The identifier of the tab the result belongs to. */
+    tab_id: number;
+    /** The results of the script run; a single value unless evaluated line by line. */
+    results: string[];
+    /** The log stack of the script run. */
+    log_stack: string[];
+    /** The log stack lines of the script run line by line. */
+    log_stack_lines: LineByLineLog[];
+    /** The value snapshots of the lines of the script run line by line. */
+    line_value_snapshots: LineValueSnapshot[];
+};
+
+type LogEntry = {
+    /** The level of the console call. */
+    level: "log" | "warn" | "error";
+    /** The console call arguments joined with a space. */
+    message: string;
+    /** The line being evaluated in the line-by-line mode; null if the entire code was evaluated at once. */
+    line_number: number | null;
+};
+
+type ScriptException = {
+    /** The exception message, e.g. `Error: Something went wrong`. */
+    message: string;
+    /** The line number where the exception was thrown. */
+    line_number: number | null;
+    /** The column number where the exception was thrown. */
+    column: number | null;
+    /** The source code line where the exception was thrown. */
+    source_line: string | null;
+    /** The stack trace of the exception. */
+    stack: string | null;
+};
+
+type TestStatus = "passed" | "failed" | "skipped";
+
+type TestFailure = {
+    /** The failure message. */
+    message: string;
+    /** The name of the failed matcher, e.g. `toEqual` or `not.toBe`; null if the test threw an exception. */
+    matcher: string | null;
+    /** The formatted expected value of the failed matcher. */
+    expected: string | null;
+    /** The formatted actual value of the failed matcher. */
+    actual: string | null;
+    /** The unified line diff between the expected and the actual value. */
+    diff: string | null;
+    /** The stack trace of the failure. */
+    stack: string | null;
+};
+
+type TestCaseResult = {
+    /** The name of the test. */
+    name: string;
+    /** The names of the `describe` blocks of the test from the outermost to the innermost. */
+    suite: string[];
+    /** The status of the test. */
+    status: TestStatus;
+    /** The duration of the test with its hooks in milliseconds. */
+    duration_ms: number;
+    /** The details of the failure if the test failed. */
+    failure: TestFailure | null;
+};
+
+type SnapshotMismatch = {
+    /** The key of the snapshot, i.e. the full name of the test with the snapshot number. */
+    key: string;
+    /** The stored snapshot. */
+    expected: string;
+    /** The formatted value the test produced. */
+    actual: string;
+    /** The unified line diff between the stored snapshot and the value. */
+    diff: string;
+};
+
+type SnapshotSummary = {
+    /** The number of the values matching their stored snapshots. */
+    matched: number;
+    /** The number of the new snapshots stored. */
+    written: number;
+    /** The number of the stored snapshots replaced in the update mode. */
+    updated: number;
+    /** The values which did not match their stored snapshots. */
+    mismatched: SnapshotMismatch[];
+    /** The keys of the stored snapshots no test uses anymore; removed in the update mode. */
+    obsolete: string[];
+};
+
+type TestReport = {
+    /** The results of the tests in the registration order. */
+    tests: TestCaseResult[];
+    /** The number of the passed tests. */
+    passed: number;
+    /** The number of the failed tests. */
+    failed: number;
+    /** The number of the skipped tests. */
+    skipped: number;
+    /** The duration of the script body and the test run in milliseconds. */
+    duration_ms: number;
+    /** The exception thrown by the script body outside of the tests. */
+    script_exception: ScriptException | null;
+    /** The captured console calls of the script body and the tests. */
+    logs: LogEntry[];
+    /** The results of the snapshot assertions of the tests. */
+    snapshots: SnapshotSummary;
+};
+
+type RunStatus = "success" | "exception" | "skipped" | "file_error";
+
+type BatchReportFormat = "junit" | "markdown";
+
+type BatchFileResult = {
+    /** The name of the script file. */
+    name: string;
+    /** The path of the script file. */
+    file: string;
+    /** The status of the script run. */
+    status: RunStatus;
+    /** The duration of the script run in milliseconds. */
+    duration_ms: number;
+    /** The exception message or the file error message if the script run failed. */
+    error: string | null;
+    /** The stack trace of the exception if the script threw one. */
+    stack: string | null;
+};
+
+type BatchSummary = {
+    /** The directory of the script files. */
+    directory: string;
+    /** The time the batch run started. */
+    started_at: string;
+    /** The duration of the batch run in milliseconds. */
+    duration_ms: number;
+    /** The number of the script files. */
+    total: number;
+    /** The number of the script files which ran successfully. */
+    passed: number;
+    /** The number of the script files which failed. */
+    failed: number;
+    /** The results of the script files in the file name order. */
+    files: BatchFileResult[];
+};
+
+type RecoveryAction = "restored" | "reset" | "kept";
+
+type StateRecovery = {
+    /** The name of the recovered application state field, e.g. `file_tabs`. */
+    state: string;
+    /** The action taken to recover the state. */
+    action: RecoveryAction;
+    /** The time the poisoned state was recovered. */
+    recovered_at: string;
+    /** The localized message describing the recovery. */
+    message: string;
+};
+
+type FileChangeKind = "modified" | "removed" | "renamed";
+
+type FileChange = {
+    /** The identifier of the tab of the file. */
+    tab_id: number;
+    /** The name and path of the file. */
+    file_name_path: string;
+    /** The kind of the change. */
+    kind: FileChangeKind;
+    /** The new name and path of the file if the file was renamed. */
+    new_file_name_path: string | null;
+    /** The new last modified date of the file if the file was modified. */
+    modified_at: string | null;
+};
+
+type MergeConflict = {
+    /** The one-based line number of the first conflict marker line in the merged content. */
+    start_line: number;
+    /** The one-based line number of the last conflict marker line in the merged content. */
+    end_line: number;
+    /** The lines of the last saved content. */
+    base: string;
+    /** The lines of the editor content. */
+    ours: string;
+    /** The lines of the file system content. */
+    theirs: string;
+};
+
+type FileMerge = {
+    /** The merged content with the conflicts marked with the conflict marker lines. */
+    merged: string;
+    /** The conflicts of the merge. */
+    conflicts: MergeConflict[];
+    /** The hash of the file system content the merge was made against. */
+    theirs_hash: string;
+    /** The last modified date of the file system content the merge was made against. */
+    modified_at: string | null;
+};
+
 type AppStateResult = {
     log_stack: string[];
     log_stack_lines: LineByLineLog[];
+    line_value_snapshots: LineValueSnapshot[];
     file_ids: number[];
     file_tabs: FileTabData[];
     active_tab_id: number | null;
+    recent_files: string[];
+    workspace: string | null;
+    project_folder: string | null;
+    sidebar: SidebarLayout;
+};
+
+/**
+ * The layout of the project folder sidebar.
+ */
+type SidebarLayout = {
+    /** A flag indicating whether the sidebar is visible while a project folder is open. */
+    visible: boolean;
+    /** The width of the sidebar in pixels. */
+    width: number;
+};
+
+/**
+ * A script file in the project folder.
+ */
+type ProjectFile = {
+    /** The name and path of the file. */
+    file_name_path: string;
+    /** The path of the file relative to the project folder with `/` separators. */
+    relative_path: string;
+    /** The name of the file. */
+    file_name: string;
+};
+
+/**
+ * The script files added to or removed from the project folder, emitted by the project folder watcher.
+ */
+type ProjectFilesChange = {
+    /** The path of the project folder. */
+    folder: string;
+    /** The added files. */
+    added: ProjectFile[];
+    /** The removed files. */
+    removed: ProjectFile[];
+};
+
+/**
+ * The unsaved tab contents snapshotted by the autosave.
+ */
+type RecoverySnapshot = {
+    /** The time the snapshot was taken. */
+    saved_at: Date;
+    /** The tabs with unsaved contents. */
+    tabs: FileTabData[];
+};
+
+/**
+ * The event which created a revision of a file in the local history.
+ */
+type RevisionKind = "save" | "run";
+
+/**
+ * A revision of a file in the local history.
+ */
+type Revision = {
+    /** The unique identifier of the revision. */
+    id: number;
+    /** The key of the file the revision belongs to. */
+    key: string;
+    /** The name of the file when the revision was created. */
+    file_name: string;
+    /** The event which created the revision. */
+    kind: RevisionKind;
+    /** The time the revision was created. */
+    created_at: Date;
+    /** The SHA-256 hash of the content. */
+    hash: string;
+    /** The size of the content in bytes. */
+    size: number;
+};
+
+/**
+ * The difference of two revisions of a file.
+ */
+type RevisionDiff = {
+    /** The older revision. */
+    from: Revision;
+    /** The newer revision. */
+    to: Revision;
+    /** The unified diff of the contents of the revisions. */
+    unified_diff: string;
+    /** The number of the inserted lines. */
+    insertions: number;
+    /** The number of the deleted lines. */
+    deletions: number;
+};
+
+/**
+ * The summary of a named workspace.
+ */
+type WorkspaceInfo = {
+    /** The name of the workspace. */
+    name: string;
+    /** The number of the tabs of the workspace. */
+    tab_count: number;
+    /** The time the workspace was last saved. */
+    saved_at: Date;
+    /** A flag indicating whether the workspace is the workspace of the open tabs. */
+    is_current: boolean;
 };
 
 /**
@@ -48,7 +445,7 @@ const runScript = async (code: string): Promise<string> => {
     try {
         return await invoke("run_script", { code });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -56,20 +453,155 @@ const runScriptLineByLine = async (code: string[]): Promise<string[]> => {
     try {
         return await invoke("run_script_line_by_line", { code });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the global variables the last script run defined using the Tauri API call.
+ * @returns {Promise<GlobalVariable[]>} The global variables of the last script run.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getGlobalVariables = async (): Promise<GlobalVariable[]> => {
+    try {
+        return await invoke("get_global_variables");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the child values of an expandable global variable using the Tauri API call.
+ * @param {number} handle - The handle of the value to expand.
+ * @returns {Promise<GlobalVariable[]>} The child values of the value.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getGlobalVariableChildren = async (handle: number): Promise<GlobalVariable[]> => {
+    try {
+        return await invoke("get_global_variable_children", { handle });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Pushes the script content of a tab into the live mode using the Tauri API call.
+ * The result is emitted as the `live-result` event containing {@link LiveResult} data.
+ * @param {number} tabId - The identifier of the tab.
+ * @param {string[]} code - The script code lines. The lines are joined together unless evaluated line by line.
+ * @param {boolean} evaluatePerLine - A value indicating whether to evaluate each line separately.
+ * @returns {Promise<boolean>} A value indicating whether the content was pushed successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const updateLiveContent = async (tabId: number, code: string[], evaluatePerLine: boolean): Promise<boolean> => {
+    try {
+        return await invoke("update_live_content", { tabId, code, evaluatePerLine });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Stops the live mode of a tab using the Tauri API call.
+ * @param {number} tabId - The identifier of the tab.
+ * @returns {Promise<boolean>} A value indicating whether the live mode was running for the tab.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const stopLiveMode = async (tabId: number): Promise<boolean> => {
+    try {
+        return await invoke("stop_live_mode", { tabId });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Runs the script and the tests it registered with the built-in `describe`, `it`/`test` and `expect` functions using the Tauri API call.
+ * The `toMatchSnapshot` snapshots are stored in a snapshot file next to the script file.
+ * @param {string} code - The JavaScript code with the tests.
+ * @param {string | null} fileNamePath - The file of the script tab; null to keep the snapshots in memory.
+ * @param {boolean} updateSnapshots - A flag indicating whether to update the mismatched and remove the obsolete snapshots.
+ * @returns {Promise<TestReport>} The test report with the result of each test.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const runTests = async (code: string, fileNamePath: string | null, updateSnapshots: boolean = false): Promise<TestReport> => {
+    try {
+        return await invoke("run_tests", { code, fileNamePath, updateSnapshots });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the stored snapshots of a script file for reviewing using the Tauri API call.
+ * @param {string} fileNamePath - The script file.
+ * @returns {Promise<Record<string, string>>} The formatted snapshot values by their keys.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getSnapshots = async (fileNamePath: string): Promise<Record<string, string>> => {
+    try {
+        return await invoke("get_snapshots", { fileNamePath });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Accepts the reviewed mismatched values of a test run as the new snapshots of a script file using the Tauri API call.
+ * @param {string} fileNamePath - The script file.
+ * @param {SnapshotMismatch[]} mismatches - The mismatched values to store as the snapshots.
+ * @returns {Promise<number>} The number of the snapshots updated.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const updateSnapshots = async (fileNamePath: string, mismatches: SnapshotMismatch[]): Promise<number> => {
+    try {
+        return await invoke("update_snapshots", { fileNamePath, mismatches });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Runs every JavaScript and TypeScript file of a directory in parallel isolates using the Tauri API call.
+ * @param {string} directory - The directory of the script files.
+ * @param {number | null} jobs - The number of the files to run in parallel; null to use the number of the CPU cores.
+ * @returns {Promise<BatchSummary>} The summary of the batch run.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const runBatch = async (directory: string, jobs: number | null): Promise<BatchSummary> => {
+    try {
+        return await invoke("run_batch", { directory, jobs });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Exports the summary of a batch run into a report file using the Tauri API call.
+ * @param {BatchSummary} summary - The summary of the batch run.
+ * @param {BatchReportFormat} format - The report format, either JUnit XML or Markdown.
+ * @param {string} fileName - The file name and path to save the report into.
+ * @returns {Promise<boolean>} A value indicating whether the report was saved successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const saveBatchReport = async (summary: BatchSummary, format: BatchReportFormat, fileName: string): Promise<boolean> => {
+    try {
+        return await invoke("save_batch_report", { summary, format, fileName });
+    } catch (error) {
+        throw toCommandError(error);
     }
 };
 
 /**
  * Gets the application state from the Tauri API.
  * @returns {Promise<AppStateResult>} The application state.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const getAppState = async (): Promise<AppStateResult> => {
     try {
         return await invoke("get_app_state");
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -77,26 +609,26 @@ const getAppState = async (): Promise<AppStateResult> => {
  * Adds a new tab to the application.
  * @param {FileTabData} tab_data - The tab data to add.
  * @returns {Promise<boolean>} A value indicating whether the tab was added successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const addNewTab = async (tab_data: FileTabData, tabContent?: string | null) => {
     try {
         return await invoke("add_new_tab", { tabData: tab_data, tabContent: tabContent ?? null });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
 /**
  * Saves the open tabs using the Tauri API call.
  * @returns {Promise<boolean>} A value indicating whether the tabs were saved successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const saveOpenTabs = async (): Promise<boolean> => {
     try {
         return await invoke("save_open_tabs");
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -104,39 +636,54 @@ const saveOpenTabs = async (): Promise<boolean> => {
  * Updates the open tabs using the Tauri API call.
  * @param {FileTabData[]} tabs - The tabs to update.
  * @returns {Promise<boolean>} A value indicating whether the tabs were updated successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const updateOpenTabs = async (tabs: FileTabData[]): Promise<boolean> => {
     try {
         return await invoke("update_open_tabs", { tabData: tabs });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Updates the editor content of a tab for the autosave using the Tauri API call. The open tabs are not updated.
+ * @param {number} uid - The unique id of the tab.
+ * @param {string} content - The content in the editor.
+ * @returns {Promise<boolean>} A value indicating whether the content was updated successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const updateTabContent = async (uid: number, content: string): Promise<boolean> => {
+    try {
+        return await invoke("update_tab_content", { uid, content });
+    } catch (error) {
+        throw toCommandError(error);
     }
 };
 
 /**
  * Loads the file state using the Tauri API call into the Rust backend state.
  * @returns {Promise<boolean>} A value indicating whether the file state was loaded successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const loadFileState = async (): Promise<boolean> => {
     try {
         return await invoke("load_file_state");
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
 /**
  * Gets a new tab unique id using the Tauri API call.
  * @returns {Promise<number>} The new tab unique id.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const getNewTabId = async (): Promise<number> => {
     try {
         return await invoke("get_new_tab_id");
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -144,13 +691,13 @@ const getNewTabId = async (): Promise<number> => {
  * Opens an existing file using the Tauri API call.
  * @param {string} fileName - The name of the file to open.
  * @returns {Promise<boolean>} A value indicating whether the file was opened successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const openExistingFile = async (fileName: string): Promise<boolean> => {
     try {
         return invoke("open_existing_file", { fileName });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -158,7 +705,7 @@ const openExistingFile = async (fileName: string): Promise<boolean> => {
  * Checks if a file has changed in the file system using the Tauri API call.
  * @param {FileTabData} data - The file data to check.
  * @returns {Promise<boolean>} A value indicating whether the file has changed in the file system.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const isFileChangedInFs = async (data: FileTabData): Promise<boolean> => {
     // Don't bother for an API call if the file is not temporary.
@@ -169,7 +716,7 @@ const isFileChangedInFs = async (data: FileTabData): Promise<boolean> => {
     try {
         return await invoke("is_file_changed_in_fs", { data });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -177,7 +724,7 @@ const isFileChangedInFs = async (data: FileTabData): Promise<boolean> => {
  * Checks if an existing file is missing in the file system using the Tauri API call.
  * @param {FileTabData} data - The file data to check.
  * @returns {Promise<boolean>} A value indicating whether the existing file is missing in the file system.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const isExistingFileMissingInFs = async (data: FileTabData): Promise<boolean> => {
     // Don't bother for an API call if the file is not temporary.
@@ -188,7 +735,7 @@ const isExistingFileMissingInFs = async (data: FileTabData): Promise<boolean> =>
     try {
         return await invoke("is_existing_file_missing_in_fs", { data });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -196,13 +743,13 @@ const isExistingFileMissingInFs = async (data: FileTabData): Promise<boolean> =>
  * Reloads the file contents using the Tauri API call.
  * @param {FileTabData} data - The file data to reload.
  * @returns {Promise<boolean>} A value indicating whether the file contents were reloaded successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const reloadFileContents = async (data: FileTabData) => {
     try {
         return await invoke("reload_file_contents", { data });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -210,13 +757,13 @@ const reloadFileContents = async (data: FileTabData) => {
  * Sets the current file to kept in the editor using the Tauri API call.
  * @param {FileTabData} data - The file data to set.
  * @returns {Promise<boolean>} A value indicating whether the current file was set successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const setKeepCurrentFileInEditor = async (data: FileTabData) => {
     try {
         return await invoke("set_current_file_keep_in_editor", { data });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -224,14 +771,379 @@ const setKeepCurrentFileInEditor = async (data: FileTabData) => {
  * Saves the file contents using the Tauri API call.
  * @param {FileTabData} data - The file data to save.
  * @param {string | null} fileNamePath - The name and path of the file to save.
+ * @param {boolean} overwrite - A value indicating whether to overwrite the file even if it was changed outside the editor after it was loaded.
  * @returns {Promise<boolean>} A value indicating whether the file contents were saved successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails. The code is `SaveConflict` if the file was changed outside the editor.
+ */
+const saveFileContents = async (data: FileTabData, fileNamePath: string | null, overwrite: boolean = false) => {
+    try {
+        return await invoke("save_file_contents", { data, fileNamePath, overwrite });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Merges the unsaved editor content of the tab with the changed file in the file system using the Tauri API call.
+ * @param {FileTabData} data - The file data with the editor content.
+ * @returns {Promise<FileMerge | null>} The merge or null if the tab has no unsaved changes and the file can be reloaded.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const mergeFileChanges = async (data: FileTabData): Promise<FileMerge | null> => {
+    try {
+        return await invoke("merge_file_changes", { data });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Saves the merged content of the tab if the file has not changed since the merge using the Tauri API call.
+ * @param {FileTabData} data - The file data with the merged content.
+ * @param {string} theirsHash - The hash of the file system content the merge was made against.
+ * @returns {Promise<boolean>} A value indicating whether the merged content was saved successfully.
+ * @throws {CommandError} If the Tauri API call fails.
  */
-const saveFileContents = async (data: FileTabData, fileNamePath: string | null) => {
+const saveMergedFile = async (data: FileTabData, theirsHash: string): Promise<boolean> => {
     try {
-        return await invoke("save_file_contents", { data, fileNamePath });
+        return await invoke("save_merged_file", { data, theirsHash });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Converts the file of the tab into another text encoding and line endings using the Tauri API call. The file is written with them on the next save.
+ * @param {FileTabData} data - The file data with the editor content.
+ * @param {TextEncoding} encoding - The new text encoding of the file.
+ * @param {LineEnding} lineEnding - The new line endings of the file.
+ * @returns {Promise<boolean>} A value indicating whether the file was converted successfully.
+ * @throws {CommandError} If the Tauri API call fails. The code is `EncodingFailed` if the encoding cannot represent the editor content.
+ */
+const convertFileEncoding = async (data: FileTabData, encoding: TextEncoding, lineEnding: LineEnding): Promise<boolean> => {
+    try {
+        return await invoke("convert_file_encoding", { data, encoding, lineEnding });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Reopens the file of the tab from the file system with the specified text encoding using the Tauri API call. The unsaved changes are discarded.
+ * @param {FileTabData} data - The data of the file to reopen.
+ * @param {TextEncoding} encoding - The text encoding to decode the file with.
+ * @returns {Promise<boolean>} A value indicating whether the file was reopened successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const reopenFileWithEncoding = async (data: FileTabData, encoding: TextEncoding): Promise<boolean> => {
+    try {
+        return await invoke("reopen_file_with_encoding", { data, encoding });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Opens a file too large to edit read-only in pages using the Tauri API call.
+ * @param {string} fileName - The name of the file to open.
+ * @returns {Promise<boolean>} A value indicating whether the file was opened successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const openFileInPages = async (fileName: string): Promise<boolean> => {
+    try {
+        return await invoke("open_file_in_pages", { fileName });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Loads a page of a file opened read-only in pages as the content of the tab using the Tauri API call.
+ * @param {FileTabData} data - The data of the file.
+ * @param {number} page - The zero-based number of the page to load.
+ * @returns {Promise<boolean>} A value indicating whether the page was loaded successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const readFilePage = async (data: FileTabData, page: number): Promise<boolean> => {
+    try {
+        return await invoke("read_file_page", { data, page });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the recently opened files using the Tauri API call. The files no longer in the file system are removed from the list.
+ * @returns {Promise<string[]>} The names and paths of the recently opened files, the most recent first.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getRecentFiles = async (): Promise<string[]> => {
+    try {
+        return await invoke("get_recent_files");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the recently closed tabs using the Tauri API call.
+ * @returns {Promise<FileTabData[]>} The recently closed tabs, the most recently closed first.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getClosedTabs = async (): Promise<FileTabData[]> => {
+    try {
+        return await invoke("get_closed_tabs");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Reopens a recently closed tab and makes it the active tab using the Tauri API call.
+ * @param {number | null} index - The index of the tab in the recently closed tabs; null for the most recently closed tab.
+ * @returns {Promise<number | null>} The identifier of the reopened tab; null if there is no such closed tab.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const reopenClosedTab = async (index: number | null = null): Promise<number | null> => {
+    try {
+        return await invoke("reopen_closed_tab", { index });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Clears the recently opened files using the Tauri API call.
+ * @returns {Promise<boolean>} A value indicating whether the recently opened files were cleared successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const clearRecentFiles = async (): Promise<boolean> => {
+    try {
+        return await invoke("clear_recent_files");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Clears the recently closed tabs using the Tauri API call.
+ * @returns {Promise<boolean>} A value indicating whether the recently closed tabs were cleared successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const clearClosedTabs = async (): Promise<boolean> => {
+    try {
+        return await invoke("clear_closed_tabs");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the saved named workspaces using the Tauri API call.
+ * @returns {Promise<WorkspaceInfo[]>} The summaries of the workspaces.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getWorkspaces = async (): Promise<WorkspaceInfo[]> => {
+    try {
+        return await invoke("get_workspaces");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Saves the open tabs as a named workspace replacing a workspace with the same name using the Tauri API call.
+ * @param {string} name - The name of the workspace.
+ * @returns {Promise<boolean>} A value indicating whether the workspace was saved successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const saveWorkspace = async (name: string): Promise<boolean> => {
+    try {
+        return await invoke("save_workspace", { name });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Switches to another named workspace using the Tauri API call. The open tabs are saved to their workspace first.
+ * @param {string} name - The name of the workspace to switch to.
+ * @returns {Promise<boolean>} A value indicating whether the workspace was switched successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const switchWorkspace = async (name: string): Promise<boolean> => {
+    try {
+        return await invoke("switch_workspace", { name });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Renames a named workspace using the Tauri API call.
+ * @param {string} name - The name of the workspace to rename.
+ * @param {string} newName - The new name of the workspace.
+ * @returns {Promise<boolean>} A value indicating whether the workspace was renamed successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const renameWorkspace = async (name: string, newName: string): Promise<boolean> => {
+    try {
+        return await invoke("rename_workspace", { name, newName });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Deletes a named workspace using the Tauri API call. The open tabs are kept.
+ * @param {string} name - The name of the workspace to delete.
+ * @returns {Promise<boolean>} A value indicating whether the workspace was deleted successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const deleteWorkspace = async (name: string): Promise<boolean> => {
+    try {
+        return await invoke("delete_workspace", { name });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the unsaved tab contents of the previous session which ended uncleanly using the Tauri API call.
+ * @returns {Promise<RecoverySnapshot | null>} The snapshot of the unsaved tab contents; null if there is nothing to recover.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getRecoverySnapshot = async (): Promise<RecoverySnapshot | null> => {
+    try {
+        return await invoke("get_recovery_snapshot");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Restores or discards the unsaved tab contents of the previous session which ended uncleanly using the Tauri API call.
+ * @param {boolean} restore - True to restore the unsaved tab contents; false to discard them.
+ * @returns {Promise<boolean>} A value indicating whether the contents were restored or discarded.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const restoreRecoverySnapshot = async (restore: boolean): Promise<boolean> => {
+    try {
+        return await invoke("restore_recovery_snapshot", { restore });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Records the content of a tab as a run revision in the local history using the Tauri API call.
+ * @param {FileTabData} data - The file data with the editor content.
+ * @returns {Promise<Revision | null>} The recorded revision; null if the content equals the latest revision.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const recordRunRevision = async (data: FileTabData): Promise<Revision | null> => {
+    try {
+        return await invoke("record_run_revision", { data });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the revisions of the file of a tab in the local history using the Tauri API call.
+ * @param {FileTabData} data - The data of the file.
+ * @returns {Promise<Revision[]>} The revisions of the file, the newest first.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getHistoryRevisions = async (data: FileTabData): Promise<Revision[]> => {
+    try {
+        return await invoke("get_history_revisions", { data });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Compares two revisions in the local history using the Tauri API call.
+ * @param {number} fromId - The identifier of the older revision.
+ * @param {number} toId - The identifier of the newer revision.
+ * @returns {Promise<RevisionDiff>} The difference of the revisions.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const diffHistoryRevisions = async (fromId: number, toId: number): Promise<RevisionDiff> => {
+    try {
+        return await invoke("diff_history_revisions", { fromId, toId });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Restores a revision from the local history as the content of a tab using the Tauri API call.
+ * @param {FileTabData} data - The data of the file.
+ * @param {number} id - The identifier of the revision to restore.
+ * @returns {Promise<string>} The restored content to update the editor with.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const restoreHistoryRevision = async (data: FileTabData, id: number): Promise<string> => {
+    try {
+        return await invoke("restore_history_revision", { data, id });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Opens a folder as the project folder replacing the previous one using the Tauri API call.
+ * @param {string} folder - The path of the folder to open.
+ * @returns {Promise<ProjectFile[]>} The script files of the folder sorted by their relative path.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const openProjectFolder = async (folder: string): Promise<ProjectFile[]> => {
+    try {
+        return await invoke("open_project_folder", { folder });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Closes the project folder using the Tauri API call.
+ * @returns {Promise<boolean>} A value indicating whether a project folder was closed.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const closeProjectFolder = async (): Promise<boolean> => {
+    try {
+        return await invoke("close_project_folder");
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Sets the layout of the project folder sidebar using the Tauri API call.
+ * @param {SidebarLayout} sidebar - The layout of the sidebar.
+ * @returns {Promise<boolean>} A value indicating whether the layout was saved successfully.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const setSidebarLayout = async (sidebar: SidebarLayout): Promise<boolean> => {
+    try {
+        return await invoke("set_sidebar_layout", { sidebar });
+    } catch (error) {
+        throw toCommandError(error);
+    }
+};
+
+/**
+ * Gets the script files of the project folder using the Tauri API call.
+ * @returns {Promise<ProjectFile[]>} The script files of the project folder sorted by their relative path; empty if no folder is open.
+ * @throws {CommandError} If the Tauri API call fails.
+ */
+const getProjectFiles = async (): Promise<ProjectFile[]> => {
+    try {
+        return await invoke("get_project_files");
+    } catch (error) {
+        throw toCommandError(error);
     }
 };
 
@@ -239,26 +1151,26 @@ const saveFileContents = async (data: FileTabData, fileNamePath: string | null)
  * Sets the active tab id using the Tauri API call.
  * @param {number} tabId - The active tab id.
  * @returns {Promise<boolean>} A value indicating whether the active tab id was set successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const setActiveTabId = async (tabId: number) => {
     try {
         return await invoke("set_active_tab_id", { tabId });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
 /**
  * Sets the i18n locale of the backend using the Tauri API call.
  * @returns {Promise<boolean>} A value indicating whether the i18n locale was set successfully.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const setI18nLocale = async () => {
     try {
         return await invoke("set_i18n_locale");
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -266,20 +1178,20 @@ const setI18nLocale = async () => {
  * Checks if a file is opened using the Tauri API call.
  * @param {string} fileName - The name of the file to check.
  * @returns {Promise<boolean>} A value indicating whether the file is opened.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const isFileOpened = async (fileName: string): Promise<boolean> => {
     try {
         return invoke("is_file_opened", { fileName });
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
 /**
  * Tests the Tauri API call.
  * @returns {Promise<string[]>} The test results.
- * @throws {Error} If the Tauri API call fails.
+ * @throws {CommandError} If the Tauri API call fails.
  */
 const test_function_call = async (): Promise<string[]> => {
     if (process.env.NODE_ENV !== "development") {
@@ -289,7 +1201,7 @@ const test_function_call = async (): Promise<string[]> => {
     try {
         return (await invoke("test_function_call")) as string[];
     } catch (error) {
-        throw new Error(`${error}`);
+        throw toCommandError(error);
     }
 };
 
@@ -301,6 +1213,7 @@ export {
     addNewTab,
     saveOpenTabs,
     updateOpenTabs,
+    updateTabContent,
     loadFileState,
     getNewTabId,
     openExistingFile,
@@ -313,6 +1226,77 @@ export {
     setI18nLocale,
     isFileOpened,
     setActiveTabId,
+    getGlobalVariables,
+    getGlobalVariableChildren,
+    updateLiveContent,
+    stopLiveMode,
+    runBatch,
+    saveBatchReport,
+    runTests,
+    getSnapshots,
+    updateSnapshots,
+    mergeFileChanges,
+    saveMergedFile,
+    convertFileEncoding,
+    reopenFileWithEncoding,
+    openFileInPages,
+    readFilePage,
+    getRecentFiles,
+    getClosedTabs,
+    reopenClosedTab,
+    clearRecentFiles,
+    clearClosedTabs,
+    getWorkspaces,
+    saveWorkspace,
+    switchWorkspace,
+    renameWorkspace,
+    deleteWorkspace,
+    getRecoverySnapshot,
+    restoreRecoverySnapshot,
+    recordRunRevision,
+    getHistoryRevisions,
+    diffHistoryRevisions,
+    restoreHistoryRevision,
+    openProjectFolder,
+    closeProjectFolder,
+    getProjectFiles,
+    setSidebarLayout,
+    CommandError,
 };
 
-export type { AppStateResult, LineByLineLog };
+export type {
+    ErrorCode,
+    AppStateResult,
+    WorkspaceInfo,
+    RecoverySnapshot,
+    RevisionKind,
+    Revision,
+    RevisionDiff,
+    ProjectFile,
+    ProjectFilesChange,
+    SidebarLayout,
+    LineByLineLog,
+    GlobalVariable,
+    ValueSnapshot,
+    NamedValueSnapshot,
+    LineValueSnapshot,
+    LiveResult,
+    RunStatus,
+    BatchReportFormat,
+    BatchFileResult,
+    BatchSummary,
+    LogEntry,
+    ScriptException,
+    TestStatus,
+    TestFailure,
+    TestCaseResult,
+    TestReport,
+    SnapshotMismatch,
+    SnapshotSummary,
+    RecoveryAction,
+    StateRecovery,
+    FileChangeKind,
+    FileChange,
+    MergeConflict,
+    FileMerge,
+};
@@ -0,0 +1,208 @@
+/*
+MIT License
+
+Copyright (c) 2024 Petteri Kautonen
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+import * as React from "react";
+import classNames from "classnames";
+import { styled } from "styled-components";
+import { Button, Modal, Table } from "antd";
+import { ColumnsType } from "antd/es/table";
+import { Editor } from "@monaco-editor/react";
+import { CommonProps, FileTabData } from "../Types";
+import { Revision, RevisionDiff, diffHistoryRevisions, getHistoryRevisions } from "../app/TauriWrappers";
+import { useTranslate } from "../../localization/Localization";
+import { NotificationType } from "../../utilities/app/Notify";
+
+/**
+ * The props for the {@link HistoryPopup} component.
+ */
+type HistoryPopupProps = {
+    /** A value indicating whether this popup is visible. */
+    visible: boolean;
+    /** The tab whose local history to display. */
+    tab: FileTabData | null;
+    /** A value indicating whether the dark mode is enabled. */
+    darkMode: boolean;
+    notification: (type: NotificationType, title: string | null | undefined | Error, duration?: number) => void;
+    /** Occurs when the popup is closed. The identifier of the revision to restore is passed as a parameter to the callback; `null` if nothing is restored. */
+    onClose: (revisionId: number | null) => void;
+} & CommonProps;
+
+/**
+ * A popup component to list the saved and run revisions of a file in the local history, compare two of them and restore one.
+ * @param param0 The component props: {@link HistoryPopupProps}.
+ * @returns A component.
+ */
+const HistoryPopupComponent = ({
+    className, //
+    visible,
+    tab,
+    darkMode,
+    notification,
+    onClose,
+}: HistoryPopupProps) => {
+    const [revisions, setRevisions] = React.useState<Revision[]>([]);
+    const [selectedIds, setSelectedIds] = React.useState<number[]>([]);
+    const [diff, setDiff] = React.useState<RevisionDiff | null>(null);
+    // The i18n translation hook.
+    const { translate } = useTranslate();
+
+    // Load the revisions of the tab when the popup is shown.
+    React.useEffect(() => {
+        setSelectedIds([]);
+        setDiff(null);
+        if (visible && tab) {
+            getHistoryRevisions(tab)
+                .then(result => setRevisions(result))
+                .catch(error => notification("error", error));
+        } else {
+            setRevisions([]);
+        }
+    }, [notification, tab, visible]);
+
+    const columns: ColumnsType<Revision> = React.useMemo(
+        () => [
+            {
+                title: translate("revisionTime", "Time"),
+                dataIndex: "created_at",
+                render: (value: Date) => new Date(value).toLocaleString(),
+            },
+            {
+                title: translate("revisionKind", "Event"),
+                dataIndex: "kind",
+                render: (value: Revision["kind"]) => (value === "save" ? translate("revisionSave", "Save") : translate("revisionRun", "Run")),
+            },
+            {
+                title: translate("revisionSize", "Size (bytes)"),
+                dataIndex: "size",
+            },
+        ],
+        [translate]
+    );
+
+    // The selected revisions changed.
+    const onSelectionChange = React.useCallback((keys: React.Key[]) => {
+        setSelectedIds(keys.map(Number));
+        setDiff(null);
+    }, []);
+
+    // The Compare button was clicked. The older revision is compared to the newer one.
+    const onCompareClick = React.useCallback(() => {
+        if (selectedIds.length !== 2) {
+            return;
+        }
+
+        const [fromId, toId] = [...selectedIds].sort((a, b) => a - b);
+        diffHistoryRevisions(fromId, toId)
+            .then(result => setDiff(result))
+            .catch(error => notification("error", error));
+    }, [notification, selectedIds]);
+
+    // The Restore button was clicked.
+    const onRestoreClick = React.useCallback(() => {
+        if (selectedIds.length === 1) {
+            onClose(selectedIds[0]);
+        }
+    }, [onClose, selectedIds]);
+
+    // The Close button was clicked or the popup was closed.
+    const onCancelClick = React.useCallback(() => {
+        onClose(null);
+    }, [onClose]);
+
+    return (
+        <Modal //
+            title={translate("localHistoryTitle", "Local history of '{{file}}'", { file: tab?.file_name })}
+            open={visible}
+            width={800}
+            footer={null}
+            onCancel={onCancelClick}
+            centered
+        >
+            <div className={classNames(HistoryPopupComponent.name, className)}>
+                <Table //
+                    rowKey="id"
+                    size="small"
+                    columns={columns}
+                    dataSource={revisions}
+                    pagination={false}
+                    scroll={{ y: 240 }}
+                    rowSelection={{ selectedRowKeys: selectedIds, onChange: onSelectionChange }}
+                />
+                {diff && (
+                    <div className="Popup-diff">
+                        <div>{translate("revisionDiffSummary", "{{insertions}} lines inserted, {{deletions}} lines deleted", { insertions: diff.insertions, deletions: diff.deletions })}</div>
+                        <Editor //
+                            theme={darkMode ? "vs-dark" : "light"}
+                            height={240}
+                            language="diff"
+                            value={diff.unified_diff}
+                            options={{ readOnly: true, minimap: { enabled: false } }}
+                        />
+                    </div>
+                )}
+                <div className="Popup-ButtonRow">
+                    <Button //
+                        onClick={onCompareClick}
+                        disabled={selectedIds.length !== 2}
+                    >
+                        {translate("compare", "Compare")}
+                    </Button>
+                    <Button //
+                        onClick={onRestoreClick}
+                        disabled={selectedIds.length !== 1 || tab?.read_only === true}
+                        type="primary"
+                    >
+                        {translate("restore", "Restore")}
+                    </Button>
+                    <Button //
+                        onClick={onCancelClick}
+                    >
+                        {translate("close")}
+                    </Button>
+                </div>
+            </div>
+        </Modal>
+    );
+};
+
+const HistoryPopup = styled(HistoryPopupComponent)`
+    display: flex;
+    flex-direction: column;
+    gap: 10px;
+    .Popup-diff {
+        display: flex;
+        flex-direction: column;
+        gap: 5px;
+    }
+    .Popup-ButtonRow {
+        display: flex;
+        width: 100%;
+        flex-direction: row;
+        justify-content: flex-end;
+        gap: 10px;
+    }
+`;
+
+export { HistoryPopup };
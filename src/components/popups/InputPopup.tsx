@@ -0,0 +1,139 @@
+/*
+MIT License
+
+Copyright (c) 2024 Petteri Kautonen
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+import * as React from "react";
+import classNames from "classnames";
+import { styled } from "styled-components";
+import { Button, Input, Modal } from "antd";
+import { CommonProps } from "../Types";
+import { useTranslate } from "../../localization/Localization";
+
+/**
+ * The props for the {@link InputPopup} component.
+ */
+type InputPopupProps = {
+    /** A value indicating whether this popup is visible. */
+    visible: boolean;
+    /** The title of the popup. */
+    title: string;
+    /** The message to display above the input. */
+    message: string;
+    /** The initial value of the input. */
+    initialValue?: string;
+    /** Occurs when the popup is closed. The entered value is passed as a parameter to the callback; `null` if the popup was canceled. */
+    onClose: (value: string | null) => void;
+} & CommonProps;
+
+/**
+ * A popup component to ask the user for a single line of text, e.g. a name.
+ * @param param0 The component props: {@link InputPopupProps}.
+ * @returns A component.
+ */
+const InputPopupComponent = ({
+    className, //
+    visible,
+    title,
+    message,
+    initialValue,
+    onClose,
+}: InputPopupProps) => {
+    const [value, setValue] = React.useState("");
+    // The i18n translation hook.
+    const { translate } = useTranslate();
+
+    // Reset the input to the initial value when the popup is shown.
+    React.useEffect(() => {
+        if (visible) {
+            setValue(initialValue ?? "");
+        }
+    }, [initialValue, visible]);
+
+    // The input value changed.
+    const onChange = React.useCallback((e: React.ChangeEvent<HTMLInputElement>) => {
+        setValue(e.target.value);
+    }, []);
+
+    // The OK button was clicked or the Enter key was pressed.
+    const onOkClick = React.useCallback(() => {
+        if (value.trim() !== "") {
+            onClose(value.trim());
+        }
+    }, [onClose, value]);
+
+    // The Cancel button was clicked or the popup was closed.
+    const onCancelClick = React.useCallback(() => {
+        onClose(null);
+    }, [onClose]);
+
+    return (
+        <Modal //
+            title={title}
+            open={visible}
+            width={400}
+            footer={null}
+            onCancel={onCancelClick}
+            centered
+        >
+            <div className={classNames(InputPopupComponent.name, className)}>
+                <div className="Popup-messageText">{message}</div>
+                <Input //
+                    value={value}
+                    onChange={onChange}
+                    onPressEnter={onOkClick}
+                    autoFocus
+                />
+                <div className="Popup-ButtonRow">
+                    <Button //
+                        onClick={onOkClick}
+                        type="primary"
+                        disabled={value.trim() === ""}
+                    >
+                        {translate("ok")}
+                    </Button>
+                    <Button //
+                        onClick={onCancelClick}
+                    >
+                        {translate("cancel")}
+                    </Button>
+                </div>
+            </div>
+        </Modal>
+    );
+};
+
+const InputPopup = styled(InputPopupComponent)`
+    display: flex;
+    flex-direction: column;
+    height: 100%;
+    gap: 10px;
+    .Popup-ButtonRow {
+        display: flex;
+        width: 100%;
+        flex-direction: row;
+        justify-content: flex-end;
+        gap: 10px;
+    }
+`;
+
+export { InputPopup };
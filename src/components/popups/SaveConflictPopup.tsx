@@ -0,0 +1,136 @@
+/*
+MIT License
+
+Copyright (c) 2024 Petteri Kautonen
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+import * as React from "react";
+import classNames from "classnames";
+import { styled } from "styled-components";
+import { Button, Modal } from "antd";
+import { SaveConflictResult } from "../Enums";
+import { CommonProps } from "../Types";
+import { useTranslate } from "../../localization/Localization";
+
+/**
+ * The props for the {@link SaveConflictPopup} component.
+ */
+type SaveConflictPopupProps = {
+    /** A value indicating whether this popup is visible. */
+    visible: boolean;
+    /** The message to display on the popup contents. */
+    message: string;
+    /** Occurs when the popup is closed. The selected action is passed as a parameter to the callback. */
+    onClose: (result: SaveConflictResult) => void;
+} & CommonProps;
+
+/**
+ * A popup component to select how to save a file which was changed outside the editor after it was loaded.
+ * @param param0 The component props: {@link SaveConflictPopupProps}.
+ * @returns A component.
+ */
+const SaveConflictPopupComponent = ({
+    className, //
+    visible,
+    message,
+    onClose,
+}: SaveConflictPopupProps) => {
+    // The i18n translation hook.
+    const { translate } = useTranslate();
+
+    // The Overwrite button was clicked.
+    const onOverwriteClick = React.useCallback(() => {
+        onClose(SaveConflictResult.Overwrite);
+    }, [onClose]);
+
+    // The Merge button was clicked.
+    const onMergeClick = React.useCallback(() => {
+        onClose(SaveConflictResult.Merge);
+    }, [onClose]);
+
+    // The Save as button was clicked.
+    const onSaveAsClick = React.useCallback(() => {
+        onClose(SaveConflictResult.SaveAs);
+    }, [onClose]);
+
+    // The Cancel button was clicked or the popup was closed.
+    const onCancelClick = React.useCallback(() => {
+        onClose(SaveConflictResult.Cancel);
+    }, [onClose]);
+
+    return (
+        <Modal //
+            title={translate("warning")}
+            open={visible}
+            width={500}
+            footer={null}
+            onCancel={onCancelClick}
+            centered
+        >
+            <div className={classNames(SaveConflictPopupComponent.name, className)}>
+                <div className="Popup-messageText">{message}</div>
+                <div className="Popup-ButtonRow">
+                    <Button //
+                        onClick={onOverwriteClick}
+                        danger
+                    >
+                        {translate("overwrite")}
+                    </Button>
+                    <Button //
+                        onClick={onMergeClick}
+                        type="primary"
+                    >
+                        {translate("merge")}
+                    </Button>
+                    <Button //
+                        onClick={onSaveAsClick}
+                    >
+                        {translate("saveAs")}
+                    </Button>
+                    <Button //
+                        onClick={onCancelClick}
+                    >
+                        {translate("cancel")}
+                    </Button>
+                </div>
+            </div>
+        </Modal>
+    );
+};
+
+const SaveConflictPopup = styled(SaveConflictPopupComponent)`
+    display: flex;
+    flex-direction: column;
+    height: 100%;
+    .Popup-messageText {
+        height: 100%;
+    }
+    .Popup-ButtonRow {
+        display: flex;
+        width: 100%;
+        flex-direction: row;
+        justify-content: flex-end;
+        gap: 10px;
+    }
+`;
+
+export { SaveConflictPopup };
@@ -31,6 +31,7 @@ import { AppMenu } from "./AppMenu";
 import { AppToolbar } from "./AppToolbar";
 import { appMenuItems, MenuKeys } from "./MenuItems";
 import { appToolbarItems, ToolBarItems } from "./ToolbarItems";
+import { WorkspaceInfo } from "../components/app/TauriWrappers";
 
 /**
  * The props for the {@link AppMenuToolbar} component.
@@ -41,6 +42,8 @@ type AppMenuToolbarProps = {
     };
     disabledItems?: (MenuKeys | ToolBarItems)[];
     darkMode?: boolean;
+    recentFiles?: string[];
+    workspaces?: WorkspaceInfo[];
     onItemClick: (key: MenuKeys, checked?: boolean) => void;
     onSelectChange(value: string, name?: string): void;
 } & CommonProps;
@@ -55,6 +58,8 @@ const AppMenuToolbarComponent = ({
     selectValues,
     disabledItems,
     darkMode,
+    recentFiles,
+    workspaces,
     onItemClick,
     onSelectChange,
 }: AppMenuToolbarProps) => {
@@ -72,7 +77,7 @@ const AppMenuToolbarComponent = ({
             className={classNames(AppMenuToolbar.name, className)}
         >
             <AppMenu //
-                items={appMenuItems(translate, darkMode, disabledItems as MenuKeys[] | undefined)}
+                items={appMenuItems(translate, darkMode, disabledItems as MenuKeys[] | undefined, recentFiles, workspaces)}
                 onItemClick={onToolbarItemInternal}
             />
             <AppToolbar //
@@ -1,9 +1,10 @@
 //@ts-expect-error - React is required for JSX
 import * as React from "react";
 import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
-import { faFile, faDoorOpen, faCircleQuestion, faInfo, faGear, faFolderOpen, faArrowsRotate, faPlay, faCodeFork, faCode } from "@fortawesome/free-solid-svg-icons";
+import { faFile, faDoorOpen, faCircleQuestion, faInfo, faGear, faFolderOpen, faArrowsRotate, faPlay, faCodeFork, faCode, faFileLines, faClockRotateLeft, faRotateLeft, faLayerGroup, faCheck, faCodeCompare, faFolderTree, faFolderClosed, faTableColumns } from "@fortawesome/free-solid-svg-icons";
 import { LocalizeFunction } from "../localization/Localization";
 import { SaveAsIcon, SaveIcon } from "../img/ImageExports";
+import { WorkspaceInfo } from "../components/app/TauriWrappers";
 import { filterUsableMenuItems, ItemTypeShortcut, MenuItems, renderShortcut, SubItemTypeShortcut } from "./AppMenu";
 
 /**
@@ -12,11 +13,13 @@ import { filterUsableMenuItems, ItemTypeShortcut, MenuItems, renderShortcut, Sub
  * @param {LocalizeFunction} localize The localization function to use.
  * @param {boolean} darkMode Whether the dark mode is enabled or not.
  * @param {MenuKeys[]} disabledItems The keys of the menu items that should be disabled.
+ * @param {string[]} recentFiles The names and paths of the recently opened files, the most recent first.
+ * @param {WorkspaceInfo[]} workspaces The saved named workspaces.
  * @return {MenuItems} The menu items for the application.
  *
  * @remarks This function creates an *abstraction* to the use of the `antd` menu. Do not add more logic here to deepen the *abstraction*. If more complex logic is required, use the `antd` api directly.
  */
-export const appMenuItems = (localize?: LocalizeFunction, darkMode?: boolean, disabledItems?: MenuKeys[]): MenuItems => {
+export const appMenuItems = (localize?: LocalizeFunction, darkMode?: boolean, disabledItems?: MenuKeys[], recentFiles?: string[], workspaces?: WorkspaceInfo[]): MenuItems => {
     const result = [
         {
             key: "fileMenu",
@@ -43,6 +46,85 @@ export const appMenuItems = (localize?: LocalizeFunction, darkMode?: boolean, di
                         ctrlOrMeta: true,
                     },
                 },
+                {
+                    key: "openFolder",
+                    label: localize?.("openFolder") ?? "Open Folder...",
+                    icon: <FontAwesomeIcon icon={faFolderTree} />,
+                    disabled: disabledItems?.includes("openFolder"),
+                },
+                {
+                    key: "closeFolder",
+                    label: localize?.("closeFolder") ?? "Close Folder",
+                    icon: <FontAwesomeIcon icon={faFolderClosed} />,
+                    disabled: disabledItems?.includes("closeFolder"),
+                },
+                {
+                    key: "toggleSidebar",
+                    label: localize?.("toggleSidebar") ?? "Toggle Sidebar",
+                    icon: <FontAwesomeIcon icon={faTableColumns} />,
+                    disabled: disabledItems?.includes("toggleSidebar"),
+                },
+                {
+                    key: "recentFilesMenu",
+                    label: localize?.("recentFilesMenu") ?? "Recent Files",
+                    icon: <FontAwesomeIcon icon={faClockRotateLeft} />,
+                    disabled: disabledItems?.includes("recentFilesMenu") || (recentFiles?.length ?? 0) === 0,
+                    children: [
+                        ...(recentFiles ?? []).map((file, index) => ({
+                            key: `recentFile${index}`,
+                            label: file,
+                        })),
+                        {
+                            type: "divider",
+                        },
+                        {
+                            key: "clearRecentFiles",
+                            label: localize?.("clearRecentFiles") ?? "Clear Recent Files",
+                            disabled: disabledItems?.includes("clearRecentFiles"),
+                        },
+                    ],
+                },
+                {
+                    key: "workspacesMenu",
+                    label: localize?.("workspacesMenu") ?? "Workspaces",
+                    icon: <FontAwesomeIcon icon={faLayerGroup} />,
+                    disabled: disabledItems?.includes("workspacesMenu"),
+                    children: [
+                        ...(workspaces ?? []).map((workspace, index) => ({
+                            key: `workspace${index}`,
+                            label: workspace.name,
+                            icon: workspace.is_current ? <FontAwesomeIcon icon={faCheck} /> : undefined,
+                        })),
+                        ...((workspaces?.length ?? 0) > 0 ? [{ type: "divider" }] : []),
+                        {
+                            key: "saveWorkspace",
+                            label: localize?.("saveWorkspace") ?? "Save Workspace As...",
+                            disabled: disabledItems?.includes("saveWorkspace"),
+                        },
+                        {
+                            key: "renameWorkspace",
+                            label: localize?.("renameWorkspace") ?? "Rename Workspace...",
+                            disabled: disabledItems?.includes("renameWorkspace") || !workspaces?.some(f => f.is_current),
+                        },
+                        {
+                            key: "deleteWorkspace",
+                            label: localize?.("deleteWorkspace") ?? "Delete Workspace",
+                            disabled: disabledItems?.includes("deleteWorkspace") || !workspaces?.some(f => f.is_current),
+                        },
+                    ],
+                },
+                {
+                    key: "reopenClosedTab",
+                    label: localize?.("reopenClosedTab") ?? "Reopen Closed Tab",
+                    icon: <FontAwesomeIcon icon={faRotateLeft} />,
+                    disabled: disabledItems?.includes("reopenClosedTab"),
+                    shortcut: {
+                        // The key is in the upper case with the shift key pressed.
+                        keyboardKey: "T",
+                        ctrlOrMeta: true,
+                        shift: true,
+                    },
+                },
                 {
                     key: "save",
                     label: localize?.("save") ?? "Save",
@@ -77,6 +159,71 @@ export const appMenuItems = (localize?: LocalizeFunction, darkMode?: boolean, di
                         ctrlOrMeta: true,
                     },
                 },
+                {
+                    key: "localHistory",
+                    label: localize?.("localHistory") ?? "Local History...",
+                    icon: <FontAwesomeIcon icon={faCodeCompare} />,
+                    disabled: disabledItems?.includes("localHistory"),
+                },
+                {
+                    key: "encodingMenu",
+                    label: localize?.("encodingMenu") ?? "Encoding",
+                    icon: <FontAwesomeIcon icon={faFileLines} />,
+                    disabled: disabledItems?.includes("encodingMenu"),
+                    children: [
+                        {
+                            key: "convertToUtf8",
+                            label: localize?.("convertToUtf8") ?? "Convert to UTF-8",
+                            disabled: disabledItems?.includes("convertToUtf8"),
+                        },
+                        {
+                            key: "convertToUtf8Bom",
+                            label: localize?.("convertToUtf8Bom") ?? "Convert to UTF-8 with BOM",
+                            disabled: disabledItems?.includes("convertToUtf8Bom"),
+                        },
+                        {
+                            key: "convertToUtf16Le",
+                            label: localize?.("convertToUtf16Le") ?? "Convert to UTF-16 LE",
+                            disabled: disabledItems?.includes("convertToUtf16Le"),
+                        },
+                        {
+                            key: "convertToUtf16Be",
+                            label: localize?.("convertToUtf16Be") ?? "Convert to UTF-16 BE",
+                            disabled: disabledItems?.includes("convertToUtf16Be"),
+                        },
+                        {
+                            key: "convertToWindows1252",
+                            label: localize?.("convertToWindows1252") ?? "Convert to Windows-1252",
+                            disabled: disabledItems?.includes("convertToWindows1252"),
+                        },
+                        {
+                            type: "divider",
+                        },
+                        {
+                            key: "convertToLf",
+                            label: localize?.("convertToLf") ?? "Use LF line endings",
+                            disabled: disabledItems?.includes("convertToLf"),
+                        },
+                        {
+                            key: "convertToCrLf",
+                            label: localize?.("convertToCrLf") ?? "Use CRLF line endings",
+                            disabled: disabledItems?.includes("convertToCrLf"),
+                        },
+                        {
+                            type: "divider",
+                        },
+                        {
+                            key: "reopenAsUtf8",
+                            label: localize?.("reopenAsUtf8") ?? "Reopen as UTF-8",
+                            disabled: disabledItems?.includes("reopenAsUtf8"),
+                        },
+                        {
+                            key: "reopenAsWindows1252",
+                            label: localize?.("reopenAsWindows1252") ?? "Reopen as Windows-1252",
+                            disabled: disabledItems?.includes("reopenAsWindows1252"),
+                        },
+                    ],
+                },
                 {
                     type: "divider",
                 },
@@ -152,7 +299,30 @@ export type MenuKeys =
     | "addNewTab"
     | "convertToJs"
     | "openFile"
+    | "openFolder"
+    | "closeFolder"
+    | "toggleSidebar"
+    | "recentFilesMenu"
+    | `recentFile${number}`
+    | "clearRecentFiles"
+    | "reopenClosedTab"
+    | "workspacesMenu"
+    | `workspace${number}`
+    | "saveWorkspace"
+    | "renameWorkspace"
+    | "deleteWorkspace"
     | "reloadFromDisk"
+    | "localHistory"
+    | "encodingMenu"
+    | "convertToUtf8"
+    | "convertToUtf8Bom"
+    | "convertToUtf16Le"
+    | "convertToUtf16Be"
+    | "convertToWindows1252"
+    | "convertToLf"
+    | "convertToCrLf"
+    | "reopenAsUtf8"
+    | "reopenAsWindows1252"
     | "save"
     | "saveAs"
     | "codeMenu"
@@ -22,7 +22,7 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 
-import { getAppState, runScript, runScriptLineByLine } from "../../components/app/TauriWrappers";
+import { CommandError, getAppState, runScript, runScriptLineByLine } from "../../components/app/TauriWrappers";
 import { ScriptType } from "../../components/Types";
 import { transpileTypeSctiptToJs } from "./TypeSciptTranspile";
 
@@ -31,9 +31,10 @@ import { transpileTypeSctiptToJs } from "./TypeSciptTranspile";
  * @param {string} content - The JavaScript / TypeScript code to evaluate.
  * @param {boolean} skipUndefined - Whether to skip undefined result values by returning an empty string instead.
  * @param {ScriptType} scriptType - The script type. Either "javascript" or "typescript".
+ * @param {boolean} jsx - Whether the code has JSX elements to transpile.
  * @returns {Promise<string>} The result of the evaluation.
  */
-const evalueateValue = async (content: string | undefined | null, skipUndefined: boolean, scriptType: ScriptType) => {
+const evalueateValue = async (content: string | undefined | null, skipUndefined: boolean, scriptType: ScriptType, jsx: boolean = false) => {
     if (content !== undefined && content !== null) {
         // If the content is empty, return an empty string
         if (content.replaceAll(/\s/g, "") === "") {
@@ -43,9 +44,9 @@ const evalueateValue = async (content: string | undefined | null, skipUndefined:
         const scriptValue = content;
         let script = "";
 
-        if (scriptType === "typescript") {
+        if (scriptType === "typescript" || jsx) {
             try {
-                script = transpileTypeSctiptToJs(content, true);
+                script = transpileTypeSctiptToJs(content, true, jsx);
             } catch (error) {
                 return `${error}`;
             }
@@ -57,7 +58,8 @@ const evalueateValue = async (content: string | undefined | null, skipUndefined:
         try {
             value = await runScript(script);
         } catch (error) {
-            value = `${error}`;
+            // The script exception message already contains the error type, e.g. `TypeError: ...`.
+            value = error instanceof CommandError && error.code === "ScriptException" ? error.message : `${error}`;
         }
 
         try {
@@ -81,17 +83,18 @@ const evalueateValue = async (content: string | undefined | null, skipUndefined:
  * @param {boolean} skipUndefined - Whether to skip undefined result values by returning an empty string instead.
  * @param {boolean} skipEmptyLines - Whether to skip empty line evaluation in the result.
  * @param {ScriptType} scriptType - The script type. Either "javascript" or "typescript".
+ * @param {boolean} jsx - Whether the code has JSX elements to transpile.
  * @returns {Promise<string>} The result of the evaluation.
  */
-const evalueateValueByLines = async (content: string | undefined | null, skipUndefined: boolean, skipEmptyLines: boolean, scriptType: ScriptType) => {
+const evalueateValueByLines = async (content: string | undefined | null, skipUndefined: boolean, skipEmptyLines: boolean, scriptType: ScriptType, jsx: boolean = false) => {
     if (content !== undefined && content !== null) {
         const scriptValue = content;
         let script: string[] = [];
-        if (scriptType === "typescript") {
+        if (scriptType === "typescript" || jsx) {
             const scriptLines = content.split("\n");
             for (let i = 0; i < scriptLines.length; i++) {
                 try {
-                    scriptLines[i] = transpileTypeSctiptToJs(scriptLines[i], true);
+                    scriptLines[i] = transpileTypeSctiptToJs(scriptLines[i], true, jsx);
                 } catch (error) {
                     scriptLines[i] = `${error}`;
                 }
@@ -25,7 +25,7 @@ SOFTWARE.
 import { save, SaveDialogOptions } from "@tauri-apps/plugin-dialog";
 import { FileTabData, ScriptType } from "../../components/Types";
 import { LocalizeFunction } from "../../localization/Localization";
-import { saveFileContents } from "../../components/app/TauriWrappers";
+import { CommandError, saveFileContents } from "../../components/app/TauriWrappers";
 import { NotificationType } from "./Notify";
 
 /**
@@ -41,12 +41,22 @@ const genNewTab = (script_language: ScriptType, newFileName: string): FileTabDat
         path: null,
         is_temporary: true,
         script_language: script_language,
+        jsx: false,
+        module_kind: "esmodule",
         content: null,
         file_name: newFileName,
         modified_at: null,
         file_name_path: null,
         modified_at_state: new Date(),
         evalueate_per_line: false,
+        content_hash: null,
+        content_size: null,
+        history_id: null,
+        encoding: "utf-8",
+        line_ending: "lf",
+        read_only: false,
+        page: 0,
+        page_count: 0,
     };
 };
 
@@ -54,6 +64,7 @@ const genNewTab = (script_language: ScriptType, newFileName: string): FileTabDat
  * Saves the contents of the active tab to the file.
  * @param {FileTabData} tab - The tab to save.
  * @param {string | null} fileNamePath - The name and path of the file to save.
+ * @param {(tab: FileTabData) => void} onSaveConflict - A callback to ask how to save the file if it was changed outside the editor after it was loaded.
  * @returns {Promise<boolean>} A value indicating whether the file contents were saved successfully.
  */
 const saveTab = async (
@@ -61,7 +72,8 @@ const saveTab = async (
     fileTabs: FileTabData[],
     translate: LocalizeFunction,
     saveAppStateReload: () => Promise<void>,
-    notification: (type: NotificationType, title: string | null | undefined | Error | unknown, duration?: number) => void
+    notification: (type: NotificationType, title: string | null | undefined | Error | unknown, duration?: number) => void,
+    onSaveConflict?: (tab: FileTabData) => void
 ): Promise<boolean> => {
     const index = fileTabs.findIndex(f => f.uid === activeTabKey);
     if (index !== -1) {
@@ -94,7 +106,11 @@ const saveTab = async (
                 await saveFileContents(tab, tab.file_name_path);
                 await saveAppStateReload();
             } catch (error) {
-                notification("error", error);
+                if (onSaveConflict && error instanceof CommandError && error.code === "SaveConflict") {
+                    onSaveConflict(tab);
+                } else {
+                    notification("error", error);
+                }
                 return false;
             }
         }
@@ -111,8 +127,8 @@ const saveTab = async (
 const getDialogFilter = (translate: LocalizeFunction, data: FileTabData): SaveDialogOptions => {
     const result: SaveDialogOptions =
         data.script_language === "typescript"
-            ? { filters: [{ name: translate("typeScriptFiles", "TypeScript files"), extensions: ["ts"] }] }
-            : { filters: [{ name: translate("javaScriptFiles", "JavaScript files"), extensions: ["js"] }] };
+            ? { filters: [{ name: translate("typeScriptFiles", "TypeScript files"), extensions: data.jsx ? ["tsx", "ts"] : ["ts", "mts", "cts", "tsx"] }] }
+            : { filters: [{ name: translate("javaScriptFiles", "JavaScript files"), extensions: data.jsx ? ["jsx", "js"] : ["js", "mjs", "cjs", "jsx"] }] };
 
     result.defaultPath = data.file_name_path ?? data.file_name;
 
@@ -123,6 +139,11 @@ const getDialogFilter = (translate: LocalizeFunction, data: FileTabData): SaveDi
  * Returns the open dialog filter.
  * @returns {OpenDialogOptions} The open dialog filter.
  */
-const getOpenDialogFilter = (translate: LocalizeFunction) => ({ filters: [{ name: translate("scriptFiles", "Script Files"), extensions: ["js", "ts"] }] });
+const getOpenDialogFilter = (translate: LocalizeFunction) => ({
+    filters: [
+        { name: translate("scriptFiles", "Script Files"), extensions: ["js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx"] },
+        { name: translate("allFiles", "All Files"), extensions: ["*"] },
+    ],
+});
 
 export { genNewTab, saveTab, getDialogFilter, getOpenDialogFilter };
@@ -44,6 +44,16 @@ export type Settings = {
     skip_undefined_on_js: boolean;
     /** A value indicating whether to skip empty result values on JavaScript / TypeScript evaluation. */
     skip_empty_on_js: boolean;
+    /** The time in milliseconds to wait for further content updates before evaluating the content in the live mode. */
+    live_mode_debounce_ms: number;
+    /** The number of the rotating backup copies of the previous contents to keep when saving a file; `0` to keep none. */
+    save_backup_count: number;
+    /** The size limit in megabytes of the files opened for editing; the larger files can be opened read-only in pages. */
+    file_size_limit_mb: number;
+    /** The interval in seconds to snapshot the unsaved tab contents into the recovery directory; `0` to disable the autosave. */
+    autosave_interval_seconds: number;
+    /** The size limit in megabytes of the local history of the saved and run file versions; the oldest versions are removed first. */
+    history_size_limit_mb: number;
 };
 
 /**
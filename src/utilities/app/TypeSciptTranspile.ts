@@ -4,10 +4,11 @@ import ts from "typescript";
  * Transpiles the TypeScript code to JavaScript.
  * @param {string} code The TypeScript code.
  * @param {boolean} noExceptions Whether to throw exceptions on failed transpilation or not.
+ * @param {boolean} jsx Whether the code has JSX elements. The elements are transpiled into `React.createElement` calls.
  * @returns {string} The transpiled JavaScript code.
  * @throws {Error} If `noExceptions` is `false` and the transpilation fails.
  */
-const transpileTypeSctiptToJs = (code: string | undefined | null, noExceptions: boolean) => {
+const transpileTypeSctiptToJs = (code: string | undefined | null, noExceptions: boolean, jsx: boolean = false) => {
     if (code === undefined || code === null) {
         return "";
     }
@@ -20,7 +21,9 @@ const transpileTypeSctiptToJs = (code: string | undefined | null, noExceptions:
                 target: ts.ScriptTarget.ES2023,
                 module: ts.ModuleKind.ESNext,
                 noEmit: noExceptions,
+                jsx: jsx ? ts.JsxEmit.React : undefined,
             },
+            fileName: jsx ? "script.tsx" : "script.ts",
         });
 
         return transpile.outputText;